@@ -4,8 +4,9 @@
  */
 
 use aws_sigv4::http_request::{
-    PayloadChecksumKind, PercentEncodingMode, SessionTokenMode, SignableBody, SignatureLocation,
-    SigningInstructions, SigningSettings, UriPathNormalizationMode,
+    ChunkSigner, PayloadChecksumKind, PercentEncodingMode, SessionTokenMode, SignableBody,
+    SignatureLocation, SigningInstructions, SigningParams, SigningSettings,
+    UriPathNormalizationMode,
 };
 use aws_smithy_runtime_api::box_error::BoxError;
 use aws_smithy_runtime_api::client::auth::AuthSchemeEndpointConfig;
@@ -22,6 +23,9 @@ use std::time::Duration;
 /// Auth implementations for SigV4.
 pub mod sigv4;
 
+/// Deferred signing of `aws-chunked` bodies with chunk signatures.
+pub mod chunked;
+
 #[cfg(feature = "sigv4a")]
 /// Auth implementations for SigV4a.
 pub mod sigv4a;
@@ -265,3 +269,20 @@ fn apply_signing_instructions(
     }
     Ok(())
 }
+
+/// Sends a chunk signer seeded with the request's `signature` if the request has an
+/// `aws-chunked` body with chunk signatures.
+fn send_chunk_signer(
+    config_bag: &ConfigBag,
+    signature: &str,
+    params: &SigningParams<'_>,
+) -> Result<(), BoxError> {
+    if let Some(sender) = config_bag.load::<chunked::DeferredChunkSignerSender>() {
+        let signer = ChunkSigner::new(signature, params)?;
+        if sender.send(signer).is_err() {
+            // The body was dropped, so there's nothing left to sign
+            tracing::debug!("the aws-chunked body was dropped before its chunk signer was sent");
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,112 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+use aws_sigv4::http_request::ChunkSigner;
+use aws_sigv4::SignatureVersion;
+use aws_smithy_types::config_bag::{Storable, StoreReplace};
+use std::sync::{mpsc, Arc, Mutex};
+
+/// A sender that gets placed in the request config to wire up a chunk signer after signing.
+///
+/// When the SigV4 and SigV4a signers find this in the config bag, they send a [`ChunkSigner`]
+/// that's seeded with the signature of the request.
+#[derive(Debug)]
+pub struct DeferredChunkSignerSender(Mutex<mpsc::Sender<ChunkSigner>>);
+
+impl DeferredChunkSignerSender {
+    /// Sends a signer on the channel
+    pub fn send(&self, signer: ChunkSigner) -> Result<(), mpsc::SendError<ChunkSigner>> {
+        self.0.lock().unwrap().send(signer)
+    }
+}
+
+impl Storable for DeferredChunkSignerSender {
+    type Storer = StoreReplace<Self>;
+}
+
+/// Deferred chunk signer to allow a signer to be wired up later.
+///
+/// The `aws-chunked` body is established before the HTTP request is signed, but the chunk
+/// signatures are chained off of the request's signature. This establishes a channel so that the
+/// sender can be placed in the request's config, and the HTTP signer can send the chunk signer
+/// once the request has been signed.
+///
+/// Clones share the channel so that a body rebuilt for a retry picks up the signer of the retried
+/// request.
+#[derive(Clone, Debug)]
+pub struct DeferredChunkSigner {
+    rx: Arc<Mutex<mpsc::Receiver<ChunkSigner>>>,
+    signature_version: SignatureVersion,
+}
+
+impl DeferredChunkSigner {
+    /// Creates a new `DeferredChunkSigner` for the given signature version, and the sender to
+    /// place in the request's config.
+    pub fn new(signature_version: SignatureVersion) -> (Self, DeferredChunkSignerSender) {
+        let (tx, rx) = mpsc::channel();
+        (
+            Self {
+                rx: Arc::new(Mutex::new(rx)),
+                signature_version,
+            },
+            DeferredChunkSignerSender(Mutex::new(tx)),
+        )
+    }
+
+    /// Returns the length of the chunk signatures, as they're written in the body.
+    pub fn signature_len(&self) -> usize {
+        ChunkSigner::signature_len_for(self.signature_version)
+    }
+
+    /// Takes the most recently sent signer, if a signer was sent.
+    ///
+    /// The encoder of the body calls this once the request has been signed.
+    pub fn take(&self) -> Option<ChunkSigner> {
+        let rx = self.rx.lock().unwrap();
+        rx.try_iter().last()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::DeferredChunkSigner;
+    use aws_credential_types::Credentials;
+    use aws_sigv4::http_request::{ChunkSigner, SigningParams, SigningSettings};
+    use aws_sigv4::sign::v4;
+    use aws_sigv4::SignatureVersion;
+    use aws_smithy_runtime_api::client::identity::Identity;
+    use std::time::SystemTime;
+
+    fn chunk_signer(identity: &Identity, seed: &str) -> ChunkSigner {
+        let params: SigningParams<'_> = v4::SigningParams::builder()
+            .identity(identity)
+            .region("us-east-1")
+            .name("s3")
+            .time(SystemTime::UNIX_EPOCH)
+            .settings(SigningSettings::default())
+            .build()
+            .unwrap()
+            .into();
+        ChunkSigner::new(seed, &params).unwrap()
+    }
+
+    #[test]
+    fn takes_the_latest_signer() {
+        let identity = Credentials::for_tests().into();
+        let (deferred, sender) = DeferredChunkSigner::new(SignatureVersion::V4);
+        assert_eq!(64, deferred.signature_len());
+        assert!(deferred.take().is_none());
+
+        sender.send(chunk_signer(&identity, "first")).unwrap();
+        sender.send(chunk_signer(&identity, "second")).unwrap();
+        let retried = deferred.clone();
+        let mut signer = retried.take().expect("signer was sent");
+        assert_eq!(
+            chunk_signer(&identity, "second").sign_chunk(&[]),
+            signer.sign_chunk(&[])
+        );
+        assert!(deferred.take().is_none());
+    }
+}
@@ -171,10 +171,14 @@ impl Sign for SigV4Signer {
             Self::settings(&operation_config)
         };
 
-        let signing_params =
-            Self::signing_params(settings, identity, &operation_config, request_time)?;
-
-        let (signing_instructions, _signature) = {
+        let signing_params = SigningParams::V4(Self::signing_params(
+            settings,
+            identity,
+            &operation_config,
+            request_time,
+        )?);
+
+        let (signing_instructions, signature) = {
             // A body that is already in memory can be signed directly. A body that is not in memory
             // (any sort of streaming body or presigned request) will be signed via UNSIGNED-PAYLOAD.
             let signable_body = operation_config
@@ -198,10 +202,13 @@ impl Sign for SigV4Signer {
                 request.headers().iter(),
                 signable_body,
             )?;
-            sign(signable_request, &SigningParams::V4(signing_params))?
+            sign(signable_request, &signing_params)?
         }
         .into_parts();
 
+        // If this request has an aws-chunked body with chunk signatures, set up the chunk signer
+        auth::send_chunk_signer(config_bag, &signature, &signing_params)?;
+
         // If this is an event stream operation, set up the event stream signer
         #[cfg(feature = "event-stream")]
         {
@@ -214,7 +221,7 @@ impl Sign for SigV4Signer {
                 let name = operation_config.name.clone().unwrap();
                 signer_sender
                     .send(Box::new(SigV4MessageSigner::new(
-                        _signature,
+                        signature,
                         identity.clone(),
                         region,
                         name,
@@ -457,4 +464,52 @@ mod tests {
         assert_eq!(result.name, Some(SigningName::from_static("qldb")));
         assert!(matches!(result, Cow::Borrowed(_)));
     }
+
+    #[test]
+    fn sends_a_chunk_signer_for_streaming_signed_payloads() {
+        use crate::auth::chunked::DeferredChunkSigner;
+        use aws_sigv4::SignatureVersion;
+        use aws_smithy_async::time::StaticTimeSource;
+        use aws_smithy_runtime_api::client::runtime_components::RuntimeComponentsBuilder;
+
+        let (deferred, sender) = DeferredChunkSigner::new(SignatureVersion::V4);
+        let mut layer = Layer::new("test");
+        layer.store_put(SigV4OperationSigningConfig {
+            region: Some(SigningRegion::from_static("us-east-1")),
+            name: Some(SigningName::from_static("s3")),
+            signing_options: SigningOptions {
+                content_sha256_header: true,
+                payload_override: Some(SignableBody::StreamingSignedPayload),
+                ..Default::default()
+            },
+            ..Default::default()
+        });
+        layer.store_put(sender);
+        let cfg = ConfigBag::of_layers(vec![layer]);
+        let identity = Credentials::for_tests().into();
+        let runtime_components = RuntimeComponentsBuilder::for_tests()
+            .with_time_source(Some(StaticTimeSource::from_secs(1369353600)))
+            .build()
+            .unwrap();
+
+        let mut request = HttpRequest::empty();
+        request
+            .set_uri("https://s3.amazonaws.com/bucket/key")
+            .unwrap();
+        SigV4Signer::new()
+            .sign_http_request(
+                &mut request,
+                &identity,
+                AuthSchemeEndpointConfig::empty(),
+                &runtime_components,
+                &cfg,
+            )
+            .unwrap();
+
+        assert_eq!(
+            Some("STREAMING-AWS4-HMAC-SHA256-PAYLOAD"),
+            request.headers().get("x-amz-content-sha256")
+        );
+        assert!(deferred.take().is_some(), "a chunk signer was sent");
+    }
 }
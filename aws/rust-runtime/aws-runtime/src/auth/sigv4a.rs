@@ -8,7 +8,9 @@ use crate::auth::{
     SigV4OperationSigningConfig, SigV4SigningError,
 };
 use aws_credential_types::Credentials;
-use aws_sigv4::http_request::{sign, SignableBody, SignableRequest, SigningSettings};
+use aws_sigv4::http_request::{
+    sign, SignableBody, SignableRequest, SigningParams, SigningSettings,
+};
 use aws_sigv4::sign::v4a;
 use aws_smithy_runtime_api::box_error::BoxError;
 use aws_smithy_runtime_api::client::auth::{
@@ -60,18 +62,24 @@ impl AuthScheme for SigV4aAuthScheme {
 }
 
 /// SigV4a HTTP request signer.
+///
+/// SigV4a signing keys are expensive to derive, so the signer caches them between requests.
 #[derive(Debug, Default)]
 #[non_exhaustive]
-pub struct SigV4aSigner;
+pub struct SigV4aSigner {
+    signing_key_cache: v4a::SigningKeyCache,
+}
 
 impl SigV4aSigner {
     /// Creates a new signer instance.
     pub fn new() -> Self {
-        Self
+        Default::default()
     }
 
-    fn settings(operation_config: &SigV4OperationSigningConfig) -> SigningSettings {
-        super::settings(operation_config)
+    fn settings(&self, operation_config: &SigV4OperationSigningConfig) -> SigningSettings {
+        let mut settings = super::settings(operation_config);
+        settings.sigv4a_signing_key_cache = Some(self.signing_key_cache.clone());
+        settings
     }
 
     fn signing_params<'a>(
@@ -174,9 +182,9 @@ impl Sign for SigV4aSigner {
             return Err(SigV4SigningError::WrongIdentityType(identity.clone()).into());
         }
 
-        let settings = Self::settings(&operation_config);
-        let signing_params =
-            Self::signing_params(settings, identity, &operation_config, request_time)?;
+        let settings = self.settings(&operation_config);
+        let signing_params: SigningParams<'_> =
+            Self::signing_params(settings, identity, &operation_config, request_time)?.into();
 
         let (signing_instructions, _signature) = {
             // A body that is already in memory can be signed directly. A body that is not in memory
@@ -202,10 +210,13 @@ impl Sign for SigV4aSigner {
                 request.headers().iter(),
                 signable_body,
             )?;
-            sign(signable_request, &signing_params.into())?
+            sign(signable_request, &signing_params)?
         }
         .into_parts();
 
+        // If this request has an aws-chunked body with chunk signatures, set up the chunk signer
+        super::send_chunk_signer(config_bag, &_signature, &signing_params)?;
+
         apply_signing_instructions(signing_instructions, request)?;
         Ok(())
     }
@@ -268,6 +279,25 @@ mod tests {
         assert!(logs_contain(EXPIRATION_WARNING));
     }
 
+    #[test]
+    fn settings_share_the_signing_key_cache() {
+        let signer = SigV4aSigner::new();
+        let operation_config = SigV4OperationSigningConfig::default();
+        let first = signer.settings(&operation_config);
+        let second = signer.settings(&operation_config);
+        assert!(first.sigv4a_signing_key_cache.is_some());
+        assert_eq!(
+            first.sigv4a_signing_key_cache,
+            second.sigv4a_signing_key_cache
+        );
+        assert_ne!(
+            first.sigv4a_signing_key_cache,
+            SigV4aSigner::new()
+                .settings(&operation_config)
+                .sigv4a_signing_key_cache
+        );
+    }
+
     #[test]
     fn endpoint_config_overrides_region_and_service() {
         let mut layer = Layer::new("test");
@@ -45,15 +45,98 @@
 //!     .expect("signing should succeed")
 //!     .into_parts();
 //! ```
+//!
+//! # Example: Verifying event stream messages
+//!
+//! Servers verify the received messages in order with a [`MessageVerifier`], starting with the
+//! signature and time of the initial request.
+//!
+//! ```rust
+//! use aws_sigv4::event_stream::MessageVerifier;
+//! # use aws_credential_types::Credentials;
+//! # use aws_smithy_types::event_stream::Message;
+//! # use std::time::SystemTime;
+//! # fn example(received: Vec<Message>) -> Result<(), aws_sigv4::event_stream::VerificationError> {
+//! # let identity = Credentials::new(
+//! #     "AKIDEXAMPLE",
+//! #     "wJalrXUtnFEMI/K7MDENG+bPxRfiCYEXAMPLEKEY",
+//! #     None,
+//! #     None,
+//! #     "hardcoded-credentials"
+//! # ).into();
+//! # let request_signature = "example298fc1c149afbf4c8996fb92427ae41e4649b934ca495991b7852b855";
+//! # let request_time = SystemTime::now();
+//!
+//! let mut verifier = MessageVerifier::new(
+//!     request_signature,
+//!     request_time,
+//!     identity,
+//!     "us-east-1",
+//!     "exampleservice",
+//! );
+//! for signed in received {
+//!     match verifier.verify(&signed)? {
+//!         Some(message) => { /* handle the message */ }
+//!         // The signed empty message ends the stream
+//!         None => break,
+//!     }
+//! }
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! # Example: Presigning a WebSocket upgrade URL
+//!
+//! Event streams sent over a WebSocket are authorized with a presigned upgrade URL instead of a
+//! signed HTTP request. The signature of the URL is the `last_signature` of the first message.
+//!
+//! ```rust
+//! # #[cfg(feature = "sign-http")]
+//! # fn example() {
+//! use aws_sigv4::event_stream::presign_websocket_url;
+//! # use aws_credential_types::Credentials;
+//! # use aws_sigv4::sign::v4;
+//! use std::time::{Duration, SystemTime};
+//!
+//! # let identity = Credentials::new(
+//! #     "AKIDEXAMPLE",
+//! #     "wJalrXUtnFEMI/K7MDENG+bPxRfiCYEXAMPLEKEY",
+//! #     None,
+//! #     None,
+//! #     "hardcoded-credentials"
+//! # ).into();
+//! let params = v4::SigningParams::builder()
+//!     .identity(&identity)
+//!     .region("us-east-1")
+//!     .name("transcribe")
+//!     .time(SystemTime::now())
+//!     .settings(())
+//!     .build()
+//!     .unwrap();
+//!
+//! let (url, last_signature) = presign_websocket_url(
+//!     "wss://transcribestreaming.us-east-1.amazonaws.com:8443/stream-transcription-websocket",
+//!     Duration::from_secs(300),
+//!     &params,
+//! )
+//! .expect("presigning should succeed")
+//! .into_parts();
+//! # }
+//! ```
 
 use crate::date_time::{format_date, format_date_time, truncate_subsecs};
 use crate::http_request::SigningError;
 use crate::sign::v4::{calculate_signature, generate_signing_key, sha256_hex_string};
 use crate::SigningOutput;
 use aws_credential_types::Credentials;
-use aws_smithy_eventstream::frame::{write_headers_to, write_message_to};
+use aws_smithy_eventstream::frame::{
+    read_message_from, write_headers_to, write_message_to, VerifyMessage, VerifyMessageError,
+};
+use aws_smithy_runtime_api::client::identity::Identity;
 use aws_smithy_types::event_stream::{Header, HeaderValue, Message};
 use bytes::Bytes;
+use std::error::Error;
+use std::fmt;
 use std::io::Write;
 use std::time::SystemTime;
 
@@ -109,6 +192,47 @@ pub fn sign_message<'a>(
     sign_payload(Some(message_payload), last_signature, params)
 }
 
+/// Presigns the URL of a WebSocket upgrade request that starts an event stream.
+///
+/// The returned URL is valid for `expires_in`, and the returned signature is the
+/// `last_signature` of the first message sent over the WebSocket with [`sign_message`].
+#[cfg(feature = "sign-http")]
+pub fn presign_websocket_url(
+    url: &str,
+    expires_in: std::time::Duration,
+    params: &SigningParams<'_>,
+) -> Result<SigningOutput<String>, SigningError> {
+    use crate::http_request::{
+        sign, PayloadChecksumKind, SignableBody, SignableRequest, SignatureLocation,
+        SigningSettings,
+    };
+    use aws_smithy_http::query_writer::QueryWriter;
+
+    let settings = SigningSettings {
+        signature_location: SignatureLocation::QueryParams,
+        expires_in: Some(expires_in),
+        payload_checksum_kind: PayloadChecksumKind::NoHeader,
+        ..Default::default()
+    };
+    let params = crate::sign::v4::SigningParams {
+        identity: params.identity,
+        region: params.region,
+        name: params.name,
+        time: params.time,
+        settings,
+    }
+    .into();
+
+    let request = SignableRequest::new("GET", url, std::iter::empty(), SignableBody::Bytes(&[]))?;
+    let (instructions, signature) = sign(request, &params)?.into_parts();
+    let mut query = QueryWriter::new_from_string(url)
+        .expect("unreachable: the URL was validated by SignableRequest");
+    for (name, value) in instructions.params() {
+        query.insert(name, value);
+    }
+    Ok(SigningOutput::new(query.build_uri().to_string(), signature))
+}
+
 /// Returns a signed empty message
 ///
 /// Empty signed event stream messages differ from normal signed event stream
@@ -157,9 +281,183 @@ fn sign_payload<'a>(
     ))
 }
 
+/// Verifies the signatures of event stream messages signed with [`sign_message`].
+///
+/// Servers use this to authenticate the messages of an event stream whose initial request was
+/// signed with SigV4. Messages must be verified in the order they were received, since each
+/// signature incorporates the signature of the previous message, starting with the signature of
+/// the initial request (the `Signature` in its `Authorization` header).
+#[derive(Debug)]
+pub struct MessageVerifier {
+    last_signature: String,
+    last_date: SystemTime,
+    identity: Identity,
+    region: String,
+    name: String,
+}
+
+impl MessageVerifier {
+    /// Creates a new `MessageVerifier` for an event stream whose initial request was signed with
+    /// `seed_signature` at `seed_time`, by the given `identity` for the given `region` and
+    /// service `name`.
+    pub fn new(
+        seed_signature: impl Into<String>,
+        seed_time: SystemTime,
+        identity: Identity,
+        region: impl Into<String>,
+        name: impl Into<String>,
+    ) -> Self {
+        Self {
+            last_signature: seed_signature.into(),
+            last_date: truncate_subsecs(seed_time),
+            identity,
+            region: region.into(),
+            name: name.into(),
+        }
+    }
+
+    /// Verifies the next signed message, and returns the message that it wraps.
+    ///
+    /// Returns `Ok(None)` if `message` is the signed empty message that ends the stream. Returns
+    /// an error if the signature doesn't match, or if the message is dated before the previous
+    /// message.
+    pub fn verify(&mut self, message: &Message) -> Result<Option<Message>, VerificationError> {
+        let signature = match header(message, ":chunk-signature")? {
+            HeaderValue::ByteArray(signature) => hex::encode(signature),
+            _ => return Err(VerificationErrorKind::InvalidHeader(":chunk-signature").into()),
+        };
+        let date = match header(message, ":date")? {
+            HeaderValue::Timestamp(date) => SystemTime::try_from(*date)
+                .map_err(|_| VerificationErrorKind::InvalidHeader(":date"))?,
+            _ => return Err(VerificationErrorKind::InvalidHeader(":date").into()),
+        };
+        if date < self.last_date {
+            return Err(VerificationErrorKind::ReplayedDate.into());
+        }
+
+        let creds = self
+            .identity
+            .data::<Credentials>()
+            .ok_or(VerificationErrorKind::UnsupportedIdentityType)?;
+        let params = SigningParams {
+            identity: &self.identity,
+            region: &self.region,
+            name: &self.name,
+            time: date,
+            settings: (),
+        };
+        let signing_key =
+            generate_signing_key(creds.secret_access_key(), date, params.region, params.name);
+        let string_to_sign =
+            calculate_string_to_sign(message.payload(), &self.last_signature, date, &params);
+        let expected_signature = calculate_signature(signing_key, &string_to_sign);
+        if !constant_time_eq(expected_signature.as_bytes(), signature.as_bytes()) {
+            return Err(VerificationErrorKind::SignatureMismatch.into());
+        }
+
+        self.last_signature = signature;
+        self.last_date = date;
+        if message.payload().is_empty() {
+            Ok(None)
+        } else {
+            read_message_from(&message.payload()[..])
+                .map(Some)
+                .map_err(|err| VerificationErrorKind::InvalidPayload(err).into())
+        }
+    }
+}
+
+impl VerifyMessage for MessageVerifier {
+    fn verify(&mut self, message: &Message) -> Result<Option<Message>, VerifyMessageError> {
+        Ok(MessageVerifier::verify(self, message)?)
+    }
+}
+
+fn header<'a>(
+    message: &'a Message,
+    name: &'static str,
+) -> Result<&'a HeaderValue, VerificationError> {
+    message
+        .headers()
+        .iter()
+        .find(|header| header.name().as_str() == name)
+        .map(|header| header.value())
+        .ok_or_else(|| VerificationErrorKind::MissingHeader(name).into())
+}
+
+// Compares the signatures without exiting early, so that the comparison doesn't leak how much of
+// a forged signature was correct.
+fn constant_time_eq(a: &[u8], b: &[u8]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).fold(0, |acc, (a, b)| acc | (a ^ b)) == 0
+}
+
+#[derive(Debug)]
+enum VerificationErrorKind {
+    MissingHeader(&'static str),
+    InvalidHeader(&'static str),
+    ReplayedDate,
+    SignatureMismatch,
+    UnsupportedIdentityType,
+    InvalidPayload(aws_smithy_eventstream::error::Error),
+}
+
+/// An event stream message failed signature verification.
+#[derive(Debug)]
+pub struct VerificationError {
+    kind: VerificationErrorKind,
+}
+
+impl VerificationError {
+    /// Returns `true` if the message signature didn't match the expected signature.
+    pub fn is_signature_mismatch(&self) -> bool {
+        matches!(self.kind, VerificationErrorKind::SignatureMismatch)
+    }
+
+    /// Returns `true` if the message was dated before the previous message.
+    pub fn is_replayed_date(&self) -> bool {
+        matches!(self.kind, VerificationErrorKind::ReplayedDate)
+    }
+}
+
+impl From<VerificationErrorKind> for VerificationError {
+    fn from(kind: VerificationErrorKind) -> Self {
+        Self { kind }
+    }
+}
+
+impl fmt::Display for VerificationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use VerificationErrorKind::*;
+        match &self.kind {
+            MissingHeader(name) => write!(f, "the signed message is missing the `{name}` header"),
+            InvalidHeader(name) => write!(f, "the signed message has an invalid `{name}` header"),
+            ReplayedDate => write!(
+                f,
+                "the signed message is dated before the previous message, and may have been replayed"
+            ),
+            SignatureMismatch => write!(f, "the message signature didn't match"),
+            UnsupportedIdentityType => {
+                write!(f, "only credential identities can verify message signatures")
+            }
+            InvalidPayload(_) => write!(f, "the signed message payload isn't a valid message"),
+        }
+    }
+}
+
+impl Error for VerificationError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match &self.kind {
+            VerificationErrorKind::InvalidPayload(source) => Some(source),
+            _ => None,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use crate::event_stream::{calculate_string_to_sign, sign_message, SigningParams};
+    use crate::event_stream::{
+        calculate_string_to_sign, sign_empty_message, sign_message, MessageVerifier, SigningParams,
+    };
     use crate::sign::v4::sha256_hex_string;
     use aws_credential_types::Credentials;
     use aws_smithy_eventstream::frame::write_message_to;
@@ -238,4 +536,162 @@ mod tests {
             panic!("expected timestamp for :date header");
         }
     }
+
+    #[cfg(feature = "sign-http")]
+    #[test]
+    fn presign_websocket_url() {
+        let params = SigningParams {
+            identity: &Credentials::for_tests().into(),
+            region: "us-east-1",
+            name: "transcribe",
+            time: (UNIX_EPOCH + Duration::new(123_456_789_u64, 1234u32)),
+            settings: (),
+        };
+
+        let (url, signature) = super::presign_websocket_url(
+            "wss://transcribestreaming.us-east-1.amazonaws.com:8443/stream-transcription-websocket?language-code=en-US",
+            Duration::from_secs(300),
+            &params,
+        )
+        .unwrap()
+        .into_parts();
+        let expected = format!(
+            "wss://transcribestreaming.us-east-1.amazonaws.com:8443/stream-transcription-websocket\
+            ?language-code=en-US\
+            &X-Amz-Algorithm=AWS4-HMAC-SHA256\
+            &X-Amz-Credential=ANOTREAL%2F19731129%2Fus-east-1%2Ftranscribe%2Faws4_request\
+            &X-Amz-Date=19731129T213309Z\
+            &X-Amz-Expires=300\
+            &X-Amz-SignedHeaders=host\
+            &X-Amz-Signature={signature}"
+        );
+        assert_eq!(expected, url);
+        assert_eq!(64, signature.len());
+
+        // The message chain starts with the signature of the URL
+        let message = Message::new(&b"audio"[..]);
+        assert!(sign_message(&message, &signature, &params).is_ok());
+    }
+
+    fn verifier(seed_signature: &str, seed_time: std::time::SystemTime) -> MessageVerifier {
+        MessageVerifier::new(
+            seed_signature,
+            seed_time,
+            Credentials::for_tests().into(),
+            "us-east-1",
+            "testservice",
+        )
+    }
+
+    #[test]
+    fn verify_signed_messages() {
+        let identity = Credentials::for_tests().into();
+        let seed_time = UNIX_EPOCH + Duration::new(123_456_789_u64, 1234u32);
+        let mut verifier = verifier("seed", seed_time);
+
+        let mut last_signature = "seed".to_string();
+        for (i, payload) in ["one", "two", "three"].into_iter().enumerate() {
+            let params = SigningParams {
+                identity: &identity,
+                region: "us-east-1",
+                name: "testservice",
+                time: seed_time + Duration::from_millis(600 * i as u64),
+                settings: (),
+            };
+            let message = Message::new(payload.as_bytes().to_vec());
+            let (signed, signature) = sign_message(&message, &last_signature, &params)
+                .unwrap()
+                .into_parts();
+            last_signature = signature;
+            assert_eq!(message, verifier.verify(&signed).unwrap().unwrap());
+        }
+
+        let params = SigningParams {
+            identity: &identity,
+            region: "us-east-1",
+            name: "testservice",
+            time: seed_time + Duration::from_secs(2),
+            settings: (),
+        };
+        let (signed, _) = sign_empty_message(&last_signature, &params)
+            .unwrap()
+            .into_parts();
+        assert_eq!(None, verifier.verify(&signed).unwrap());
+    }
+
+    #[test]
+    fn verify_rejects_invalid_messages() {
+        let identity = Credentials::for_tests().into();
+        let seed_time = UNIX_EPOCH + Duration::new(123_456_789_u64, 0);
+        let params = SigningParams {
+            identity: &identity,
+            region: "us-east-1",
+            name: "testservice",
+            time: seed_time,
+            settings: (),
+        };
+        let message = Message::new(&b"payload"[..]);
+        let (signed, _) = sign_message(&message, "seed", &params)
+            .unwrap()
+            .into_parts();
+
+        // Signed with a different seed
+        let err = verifier("other seed", seed_time)
+            .verify(&signed)
+            .unwrap_err();
+        assert!(err.is_signature_mismatch(), "{err}");
+
+        // A tampered payload
+        let mut tampered_payload = Vec::new();
+        write_message_to(&Message::new(&b"tampered"[..]), &mut tampered_payload).unwrap();
+        let tampered = Message::new(tampered_payload);
+        let tampered = signed.headers().iter().fold(tampered, |message, header| {
+            message.add_header(header.clone())
+        });
+        let err = verifier("seed", seed_time).verify(&tampered).unwrap_err();
+        assert!(err.is_signature_mismatch(), "{err}");
+
+        // A message replayed after a later message
+        let mut verifier = verifier("seed", seed_time);
+        verifier.verify(&signed).unwrap();
+        let later = SigningParams {
+            time: seed_time + Duration::from_secs(5),
+            ..params
+        };
+        let (next, next_signature) =
+            sign_message(&message, verifier.last_signature.as_str(), &later)
+                .unwrap()
+                .into_parts();
+        verifier.verify(&next).unwrap();
+        let (replayed, _) = sign_message(&message, &next_signature, &params)
+            .unwrap()
+            .into_parts();
+        let err = verifier.verify(&replayed).unwrap_err();
+        assert!(err.is_replayed_date(), "{err}");
+
+        // Unsigned messages
+        let err = verifier.verify(&message).unwrap_err();
+        assert_eq!(
+            "the signed message is missing the `:chunk-signature` header",
+            err.to_string()
+        );
+    }
+
+    #[test]
+    fn verify_rejects_messages_dated_before_the_initial_request() {
+        let identity = Credentials::for_tests().into();
+        let seed_time = UNIX_EPOCH + Duration::new(123_456_789_u64, 0);
+        let params = SigningParams {
+            identity: &identity,
+            region: "us-east-1",
+            name: "testservice",
+            time: seed_time - Duration::from_secs(1),
+            settings: (),
+        };
+        let (signed, _) = sign_message(&Message::new(&b"payload"[..]), "seed", &params)
+            .unwrap()
+            .into_parts();
+        let err = verifier("seed", seed_time).verify(&signed).unwrap_err();
+        assert!(err.is_replayed_date(), "{err}");
+    }
 }
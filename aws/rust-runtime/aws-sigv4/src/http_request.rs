@@ -56,6 +56,8 @@
 //! ```
 
 mod canonical_request;
+mod chunked;
+mod debug;
 mod error;
 mod settings;
 mod sign;
@@ -70,6 +72,8 @@ use crate::sign::v4;
 use crate::sign::v4a;
 use crate::SignatureVersion;
 use aws_credential_types::Credentials;
+pub use chunked::ChunkSigner;
+pub use debug::{LineDifference, SigningDebugOutput, SigningDiff};
 pub use error::SigningError;
 pub use settings::{
     PayloadChecksumKind, PercentEncodingMode, SessionTokenMode, SignatureLocation, SigningSettings,
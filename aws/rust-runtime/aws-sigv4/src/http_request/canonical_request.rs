@@ -48,6 +48,13 @@ pub(crate) const HMAC_256: &str = "AWS4-HMAC-SHA256";
 
 const UNSIGNED_PAYLOAD: &str = "UNSIGNED-PAYLOAD";
 const STREAMING_UNSIGNED_PAYLOAD_TRAILER: &str = "STREAMING-UNSIGNED-PAYLOAD-TRAILER";
+const STREAMING_SIGNED_PAYLOAD: &str = "STREAMING-AWS4-HMAC-SHA256-PAYLOAD";
+const STREAMING_SIGNED_PAYLOAD_TRAILER: &str = "STREAMING-AWS4-HMAC-SHA256-PAYLOAD-TRAILER";
+#[cfg(feature = "sigv4a")]
+const STREAMING_SIGNED_PAYLOAD_V4A: &str = "STREAMING-AWS4-ECDSA-P256-SHA256-PAYLOAD";
+#[cfg(feature = "sigv4a")]
+const STREAMING_SIGNED_PAYLOAD_TRAILER_V4A: &str =
+    "STREAMING-AWS4-ECDSA-P256-SHA256-PAYLOAD-TRAILER";
 
 #[derive(Debug, PartialEq)]
 pub(crate) struct HeaderValues<'a> {
@@ -153,7 +160,7 @@ impl<'a> CanonicalRequest<'a> {
             PercentEncodingMode::Double => Cow::Owned(percent_encode_path(&path)),
             PercentEncodingMode::Single => path,
         };
-        let payload_hash = Self::payload_hash(req.body());
+        let payload_hash = Self::payload_hash(req.body(), params.signature_version());
 
         let date_time = format_date_time(*params.time());
         let (signed_headers, canonical_headers) =
@@ -305,7 +312,10 @@ impl<'a> CanonicalRequest<'a> {
         Ok((signed_headers, canonical_headers))
     }
 
-    fn payload_hash<'b>(body: &'b SignableBody<'b>) -> Cow<'b, str> {
+    fn payload_hash<'b>(
+        body: &'b SignableBody<'b>,
+        signature_version: SignatureVersion,
+    ) -> Cow<'b, str> {
         // Payload hash computation
         //
         // Based on the input body, set the payload_hash of the canonical request:
@@ -315,6 +325,7 @@ impl<'a> CanonicalRequest<'a> {
         // - use `UnsignedPayload`
         // - use `UnsignedPayload` for streaming requests
         // - use `StreamingUnsignedPayloadTrailer` for streaming requests with trailers
+        // - use the streaming signed payload values for aws-chunked bodies with chunk signatures
         match body {
             SignableBody::Bytes(data) => Cow::Owned(sha256_hex_string(data)),
             SignableBody::Precomputed(digest) => Cow::Borrowed(digest.as_str()),
@@ -322,6 +333,16 @@ impl<'a> CanonicalRequest<'a> {
             SignableBody::StreamingUnsignedPayloadTrailer => {
                 Cow::Borrowed(STREAMING_UNSIGNED_PAYLOAD_TRAILER)
             }
+            SignableBody::StreamingSignedPayload => match signature_version {
+                #[cfg(feature = "sigv4a")]
+                SignatureVersion::V4a => Cow::Borrowed(STREAMING_SIGNED_PAYLOAD_V4A),
+                _ => Cow::Borrowed(STREAMING_SIGNED_PAYLOAD),
+            },
+            SignableBody::StreamingSignedPayloadTrailer => match signature_version {
+                #[cfg(feature = "sigv4a")]
+                SignatureVersion::V4a => Cow::Borrowed(STREAMING_SIGNED_PAYLOAD_TRAILER_V4A),
+                _ => Cow::Borrowed(STREAMING_SIGNED_PAYLOAD_TRAILER),
+            },
         }
     }
 
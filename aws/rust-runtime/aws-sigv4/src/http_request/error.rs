@@ -7,11 +7,14 @@ use http0::header::{InvalidHeaderName, InvalidHeaderValue};
 use http0::uri::InvalidUri;
 use std::error::Error;
 use std::fmt;
+use std::time::Duration;
 
 #[derive(Debug)]
 enum SigningErrorKind {
     FailedToCreateCanonicalRequest { source: CanonicalRequestError },
     UnsupportedIdentityType,
+    ExpiresInTooLong { max: Duration },
+    InvalidExpiration { reason: &'static str },
 }
 
 /// Error signing request
@@ -26,6 +29,18 @@ impl SigningError {
             kind: SigningErrorKind::UnsupportedIdentityType,
         }
     }
+
+    pub(crate) fn expires_in_too_long(max: Duration) -> Self {
+        Self {
+            kind: SigningErrorKind::ExpiresInTooLong { max },
+        }
+    }
+
+    pub(crate) fn invalid_expiration(reason: &'static str) -> Self {
+        Self {
+            kind: SigningErrorKind::InvalidExpiration { reason },
+        }
+    }
 }
 
 impl fmt::Display for SigningError {
@@ -37,6 +52,12 @@ impl fmt::Display for SigningError {
             SigningErrorKind::UnsupportedIdentityType => {
                 write!(f, "only 'AWS credentials' are supported for signing")
             }
+            SigningErrorKind::ExpiresInTooLong { max } => {
+                write!(f, "presigned requests can't expire in more than {max:?}")
+            }
+            SigningErrorKind::InvalidExpiration { reason } => {
+                write!(f, "invalid expiration: {reason}")
+            }
         }
     }
 }
@@ -46,6 +67,8 @@ impl Error for SigningError {
         match &self.kind {
             SigningErrorKind::FailedToCreateCanonicalRequest { source } => Some(source),
             SigningErrorKind::UnsupportedIdentityType => None,
+            SigningErrorKind::ExpiresInTooLong { .. } => None,
+            SigningErrorKind::InvalidExpiration { .. } => None,
         }
     }
 }
@@ -41,6 +41,15 @@ pub struct SigningSettings {
     /// Some services require an alternative session token header or query param instead of
     /// `x-amz-security-token` or `X-Amz-Security-Token`.
     pub session_token_name_override: Option<&'static str>,
+
+    /// Return the canonical request and string to sign with the signing instructions, to debug
+    /// signature mismatches. See [`SigningDebugOutput`](crate::http_request::SigningDebugOutput).
+    pub capture_debug_output: bool,
+
+    /// Cache of Sigv4a signing keys to reuse between requests. When this isn't set, the Sigv4a
+    /// signing key is derived every time a request is signed.
+    #[cfg(feature = "sigv4a")]
+    pub sigv4a_signing_key_cache: Option<crate::sign::v4a::SigningKeyCache>,
 }
 
 /// HTTP payload checksum type
@@ -138,6 +147,30 @@ impl Default for SigningSettings {
             uri_path_normalization_mode: UriPathNormalizationMode::Enabled,
             session_token_mode: SessionTokenMode::Include,
             session_token_name_override: None,
+            capture_debug_output: false,
+            #[cfg(feature = "sigv4a")]
+            sigv4a_signing_key_cache: None,
+        }
+    }
+}
+
+impl SigningSettings {
+    /// Returns the Sigv4a signing key for the given credentials, from the cache if one is set.
+    #[cfg(feature = "sigv4a")]
+    pub(crate) fn sigv4a_signing_key(
+        &self,
+        creds: &aws_credential_types::Credentials,
+    ) -> zeroize::Zeroizing<Vec<u8>> {
+        match &self.sigv4a_signing_key_cache {
+            Some(cache) => cache.get_or_generate(creds.access_key_id(), creds.secret_access_key()),
+            None => zeroize::Zeroizing::new(
+                crate::sign::v4a::generate_signing_key(
+                    creds.access_key_id(),
+                    creds.secret_access_key(),
+                )
+                .as_ref()
+                .to_vec(),
+            ),
         }
     }
 }
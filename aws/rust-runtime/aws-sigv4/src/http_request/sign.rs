@@ -3,6 +3,7 @@
  * SPDX-License-Identifier: Apache-2.0
  */
 
+use super::debug::SigningDebugOutput;
 use super::error::SigningError;
 use super::{PayloadChecksumKind, SignatureLocation};
 use crate::http_request::canonical_request::header;
@@ -91,6 +92,16 @@ pub enum SignableBody<'a> {
 
     /// Set when a streaming body has checksum trailers.
     StreamingUnsignedPayloadTrailer,
+
+    /// Set when a streaming body is `aws-chunked` encoded with a signature for each chunk.
+    ///
+    /// The chunks are signed with a [`ChunkSigner`](crate::http_request::ChunkSigner) that's
+    /// seeded with the signature of the request.
+    StreamingSignedPayload,
+
+    /// Set when a streaming body is `aws-chunked` encoded with a signature for each chunk, and
+    /// has signed trailers.
+    StreamingSignedPayloadTrailer,
 }
 
 /// Instructions for applying a signature to an HTTP request.
@@ -98,6 +109,7 @@ pub enum SignableBody<'a> {
 pub struct SigningInstructions {
     headers: Vec<Header>,
     params: Vec<(&'static str, Cow<'static, str>)>,
+    debug_output: Option<SigningDebugOutput>,
 }
 
 /// Header representation for use in [`SigningInstructions`]
@@ -139,8 +151,16 @@ impl Header {
 }
 
 impl SigningInstructions {
-    fn new(headers: Vec<Header>, params: Vec<(&'static str, Cow<'static, str>)>) -> Self {
-        Self { headers, params }
+    fn new(
+        headers: Vec<Header>,
+        params: Vec<(&'static str, Cow<'static, str>)>,
+        debug_output: Option<SigningDebugOutput>,
+    ) -> Self {
+        Self {
+            headers,
+            params,
+            debug_output,
+        }
     }
 
     /// Returns the headers and query params that should be applied to this request
@@ -160,6 +180,13 @@ impl SigningInstructions {
         self.params.as_slice()
     }
 
+    /// Returns what was signed, if
+    /// [`SigningSettings::capture_debug_output`](crate::http_request::SigningSettings::capture_debug_output)
+    /// is enabled.
+    pub fn debug_output(&self) -> Option<&SigningDebugOutput> {
+        self.debug_output.as_ref()
+    }
+
     #[cfg(any(feature = "http0-compat", test))]
     /// Applies the instructions to the given `request`.
     pub fn apply_to_request_http0x<B>(self, request: &mut http0::Request<B>) {
@@ -217,17 +244,17 @@ pub fn sign<'a>(
     tracing::trace!(request = ?request, params = ?params, "signing request");
     match params.settings().signature_location {
         SignatureLocation::Headers => {
-            let (signing_headers, signature) =
-                calculate_signing_headers(&request, params)?.into_parts();
+            let (signing_headers, signature, debug_output) =
+                calculate_signing_headers(&request, params)?;
             Ok(SigningOutput::new(
-                SigningInstructions::new(signing_headers, vec![]),
+                SigningInstructions::new(signing_headers, vec![], debug_output),
                 signature,
             ))
         }
         SignatureLocation::QueryParams => {
-            let (params, signature) = calculate_signing_params(&request, params)?;
+            let (params, signature, debug_output) = calculate_signing_params(&request, params)?;
             Ok(SigningOutput::new(
-                SigningInstructions::new(vec![], params),
+                SigningInstructions::new(vec![], params, debug_output),
                 signature,
             ))
         }
@@ -236,13 +263,30 @@ pub fn sign<'a>(
 
 type CalculatedParams = Vec<(&'static str, Cow<'static, str>)>;
 
+fn debug_output(
+    params: &SigningParams<'_>,
+    creq: &CanonicalRequest<'_>,
+    canonical_request: String,
+    string_to_sign: String,
+) -> Option<SigningDebugOutput> {
+    params.settings().capture_debug_output.then(|| {
+        SigningDebugOutput::new(
+            canonical_request,
+            string_to_sign,
+            creq.values.signed_headers().as_str(),
+            params.settings().session_token_name_override,
+        )
+    })
+}
+
 fn calculate_signing_params<'a>(
     request: &'a SignableRequest<'a>,
     params: &'a SigningParams<'a>,
-) -> Result<(CalculatedParams, String), SigningError> {
+) -> Result<(CalculatedParams, String, Option<SigningDebugOutput>), SigningError> {
     let creds = params.credentials()?;
     let creq = CanonicalRequest::from(request, params)?;
-    let encoded_creq = &v4::sha256_hex_string(creq.to_string().as_bytes());
+    let canonical_request = creq.to_string();
+    let encoded_creq = &v4::sha256_hex_string(canonical_request.as_bytes());
 
     let (signature, string_to_sign) = match params {
         SigningParams::V4(params) => {
@@ -264,13 +308,13 @@ fn calculate_signing_params<'a>(
                 StringToSign::new_v4a(params.time, params.region_set, params.name, encoded_creq)
                     .to_string();
 
-            let secret_key =
-                v4a::generate_signing_key(creds.access_key_id(), creds.secret_access_key());
+            let secret_key = params.settings.sigv4a_signing_key(creds);
             let signature = v4a::calculate_signature(&secret_key, string_to_sign.as_bytes());
             (signature, string_to_sign)
         }
     };
     tracing::trace!(canonical_request = %creq, string_to_sign = %string_to_sign, "calculated signing parameters");
+    let debug_output = debug_output(params, &creq, canonical_request, string_to_sign);
 
     let values = creq.values.into_query_params().expect("signing with query");
     let mut signing_params = vec![
@@ -305,7 +349,7 @@ fn calculate_signing_params<'a>(
         ));
     }
 
-    Ok((signing_params, signature))
+    Ok((signing_params, signature, debug_output))
 }
 
 /// Calculates the signature headers that need to get added to the given `request`.
@@ -317,15 +361,17 @@ fn calculate_signing_params<'a>(
 fn calculate_signing_headers<'a>(
     request: &'a SignableRequest<'a>,
     params: &'a SigningParams<'a>,
-) -> Result<SigningOutput<Vec<Header>>, SigningError> {
+) -> Result<(Vec<Header>, String, Option<SigningDebugOutput>), SigningError> {
     let creds = params.credentials()?;
 
     // Step 1: https://docs.aws.amazon.com/en_pv/general/latest/gr/sigv4-create-canonical-request.html.
     let creq = CanonicalRequest::from(request, params)?;
+    let canonical_request = creq.to_string();
     // Step 2: https://docs.aws.amazon.com/en_pv/general/latest/gr/sigv4-create-string-to-sign.html.
-    let encoded_creq = v4::sha256_hex_string(creq.to_string().as_bytes());
+    let encoded_creq = v4::sha256_hex_string(canonical_request.as_bytes());
     tracing::trace!(canonical_request = %creq);
     let mut headers = vec![];
+    let string_to_sign;
 
     let signature = match params {
         SigningParams::V4(params) => {
@@ -343,7 +389,8 @@ fn calculate_signing_headers<'a>(
                 params.region,
                 params.name,
             );
-            let signature = v4::calculate_signature(signing_key, sts.to_string().as_bytes());
+            string_to_sign = sts.to_string();
+            let signature = v4::calculate_signature(signing_key, string_to_sign.as_bytes());
 
             // Step 4: https://docs.aws.amazon.com/en_pv/general/latest/gr/sigv4-add-signature-to-request.html
             let values = creq.values.as_headers().expect("signing with headers");
@@ -390,9 +437,9 @@ fn calculate_signing_headers<'a>(
                 encoded_creq.as_str(),
             );
 
-            let signing_key =
-                v4a::generate_signing_key(creds.access_key_id(), creds.secret_access_key());
-            let signature = v4a::calculate_signature(&signing_key, sts.to_string().as_bytes());
+            let signing_key = params.settings.sigv4a_signing_key(creds);
+            string_to_sign = sts.to_string();
+            let signature = v4a::calculate_signature(&signing_key, string_to_sign.as_bytes());
 
             let values = creq.values.as_headers().expect("signing with headers");
             add_header(&mut headers, header::X_AMZ_DATE, &values.date_time, false);
@@ -435,7 +482,8 @@ fn calculate_signing_headers<'a>(
         }
     };
 
-    Ok(SigningOutput::new(headers, signature))
+    let debug_output = debug_output(params, &creq, canonical_request, string_to_sign);
+    Ok((headers, signature, debug_output))
 }
 
 fn add_header(map: &mut Vec<Header>, key: &'static str, value: &str, sensitive: bool) {
@@ -789,6 +837,59 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_sign_with_debug_output() {
+        let test = "get-vanilla-query-order-key-case";
+        let identity = &Credentials::for_tests().into();
+        for capture_debug_output in [false, true] {
+            for signature_location in [SignatureLocation::Headers, SignatureLocation::QueryParams] {
+                let settings = SigningSettings {
+                    signature_location,
+                    expires_in: Some(Duration::from_secs(35)),
+                    capture_debug_output,
+                    ..Default::default()
+                };
+                let params = v4::SigningParams {
+                    identity,
+                    region: "us-east-1",
+                    name: "service",
+                    time: parse_date_time("20150830T123600Z").unwrap(),
+                    settings,
+                }
+                .into();
+
+                let original = test::v4::test_request(test);
+                let signable = SignableRequest::from(&original);
+                let out = sign(signable, &params).unwrap();
+                let debug_output = out.output.debug_output();
+                if !capture_debug_output {
+                    assert!(debug_output.is_none());
+                    continue;
+                }
+                let debug_output = debug_output.expect("debug output was captured");
+                if signature_location == SignatureLocation::Headers {
+                    assert_eq!(
+                        test::v4::test_canonical_request(test),
+                        debug_output.canonical_request()
+                    );
+                    assert_eq!(test::v4::test_sts(test), debug_output.string_to_sign());
+                    assert_eq!(
+                        vec!["host", "x-amz-date"],
+                        debug_output.signed_headers().collect::<Vec<_>>()
+                    );
+                } else {
+                    assert!(debug_output
+                        .canonical_request()
+                        .contains("X-Amz-Expires=35"));
+                }
+                // The string to sign ends with the hash of the canonical request
+                assert!(debug_output.string_to_sign().ends_with(
+                    &crate::sign::v4::sha256_hex_string(debug_output.canonical_request())
+                ));
+            }
+        }
+    }
+
     #[test]
     fn test_sign_url_escape() {
         let test = "double-encode-path";
@@ -1059,7 +1160,7 @@ mod tests {
         let mut headers = vec![];
         add_header(&mut headers, "some-header", "foo", false);
         add_header(&mut headers, "some-other-header", "bar", false);
-        let instructions = SigningInstructions::new(headers, vec![]);
+        let instructions = SigningInstructions::new(headers, vec![], None);
 
         let mut request = http0::Request::builder()
             .uri("https://some-endpoint.some-region.amazonaws.com")
@@ -1079,7 +1180,7 @@ mod tests {
             ("some-param", Cow::Borrowed("f&o?o")),
             ("some-other-param?", Cow::Borrowed("bar")),
         ];
-        let instructions = SigningInstructions::new(vec![], params);
+        let instructions = SigningInstructions::new(vec![], params, None);
 
         let mut request = http0::Request::builder()
             .uri("https://some-endpoint.some-region.amazonaws.com/some/path")
@@ -1100,7 +1201,7 @@ mod tests {
             ("some-param", Cow::Borrowed("f&o?o")),
             ("some-other-param?", Cow::Borrowed("bar")),
         ];
-        let instructions = SigningInstructions::new(vec![], params);
+        let instructions = SigningInstructions::new(vec![], params, None);
 
         let mut request = http::Request::builder()
             .uri("https://some-endpoint.some-region.amazonaws.com/some/path")
@@ -0,0 +1,776 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! A [tower] layer that signs HTTP requests.
+//!
+//! This makes it possible to call AWS endpoints that aren't modeled by an SDK, such as
+//! OpenSearch, API Gateway with IAM authorization, or Neptune, with any HTTP client that's built
+//! on `tower`. Requests from the `http` 1.x crate are supported with the `http1` feature, and
+//! requests from the `http` 0.2 crate with the `http0-compat` feature.
+//!
+//! Credentials are loaded from a [`ProvideCredentials`] implementation, and cached in an identity
+//! cache, which is the lazy cache from `aws-smithy-runtime` unless another cache is set with
+//! [`Builder::identity_cache`].
+//!
+//! # Example
+//!
+//! ```rust
+//! # #[cfg(feature = "http1")]
+//! # async fn example() -> Result<(), aws_smithy_runtime_api::box_error::BoxError> {
+//! use aws_credential_types::Credentials;
+//! use aws_sigv4::layer::SigningLayer;
+//! use bytes::Bytes;
+//! use http_body_util::Full;
+//! use tower::{service_fn, ServiceBuilder, ServiceExt};
+//!
+//! # let credentials_provider = Credentials::for_tests();
+//! let layer = SigningLayer::builder()
+//!     .credentials_provider(credentials_provider)
+//!     .region("us-east-1")
+//!     .name("es")
+//!     .build()?;
+//!
+//! let client = ServiceBuilder::new()
+//!     .layer(layer)
+//!     .service(service_fn(|request: http::Request<Full<Bytes>>| async move {
+//!         assert!(request.headers().contains_key("authorization"));
+//!         Ok::<_, std::convert::Infallible>(http::Response::new(()))
+//!     }));
+//!
+//! let request = http::Request::post("https://search-domain.us-east-1.es.amazonaws.com/_search")
+//!     .body(Full::new(Bytes::from(r#"{"query":{"match_all":{}}}"#)))?;
+//! client.oneshot(request).await?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [tower]: https://docs.rs/tower
+
+use crate::http_request::{
+    sign, SignableBody, SignableRequest, SigningInstructions, SigningParams, SigningSettings,
+};
+use crate::sign::v4;
+#[cfg(feature = "sigv4a")]
+use crate::sign::v4a;
+use crate::SignatureVersion;
+use aws_credential_types::provider::{ProvideCredentials, SharedCredentialsProvider};
+use aws_smithy_async::rt::sleep::{default_async_sleep, AsyncSleep, SharedAsyncSleep};
+use aws_smithy_async::time::{SharedTimeSource, TimeSource};
+use aws_smithy_runtime::client::auth::no_auth::{NoAuthScheme, NO_AUTH_SCHEME_ID};
+use aws_smithy_runtime::client::identity::no_auth::NoAuthIdentityResolver;
+use aws_smithy_runtime::client::identity::IdentityCache;
+use aws_smithy_runtime::client::orchestrator::endpoints::StaticUriEndpointResolver;
+use aws_smithy_runtime::client::retries::strategy::NeverRetryStrategy;
+use aws_smithy_runtime_api::box_error::BoxError;
+use aws_smithy_runtime_api::client::auth::static_resolver::StaticAuthSchemeOptionResolver;
+use aws_smithy_runtime_api::client::identity::{
+    ResolveCachedIdentity, SharedIdentityCache, SharedIdentityResolver,
+};
+use aws_smithy_runtime_api::client::runtime_components::{
+    RuntimeComponents, RuntimeComponentsBuilder,
+};
+use aws_smithy_runtime_api::shared::IntoShared;
+use aws_smithy_types::config_bag::ConfigBag;
+use std::error::Error;
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use tower_layer::Layer;
+use tower_service::Service;
+
+/// How the body of a request is signed
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum PayloadSigning {
+    /// Buffer the body in memory, and sign its SHA-256 hash.
+    ///
+    /// The request is sent with the buffered body, which is converted with `From<Bytes>`.
+    #[default]
+    Buffered,
+
+    /// Stream the body without buffering it, and sign it as `UNSIGNED-PAYLOAD`.
+    Unsigned,
+}
+
+/// An error that occurs when building a [`SigningLayer`]
+#[derive(Debug)]
+pub struct BuildError {
+    reason: &'static str,
+}
+
+impl BuildError {
+    fn new(reason: &'static str) -> Self {
+        Self { reason }
+    }
+}
+
+impl fmt::Display for BuildError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.reason)
+    }
+}
+
+impl Error for BuildError {}
+
+/// Builder for a [`SigningLayer`]
+#[derive(Debug, Default)]
+pub struct Builder {
+    credentials_provider: Option<SharedCredentialsProvider>,
+    region: Option<String>,
+    name: Option<String>,
+    signature_version: Option<SignatureVersion>,
+    settings: Option<SigningSettings>,
+    payload_signing: Option<PayloadSigning>,
+    time_source: Option<SharedTimeSource>,
+    sleep_impl: Option<SharedAsyncSleep>,
+    identity_cache: Option<SharedIdentityCache>,
+}
+
+impl Builder {
+    /// Sets the provider of the credentials to sign with (required)
+    pub fn credentials_provider(
+        mut self,
+        credentials_provider: impl ProvideCredentials + 'static,
+    ) -> Self {
+        self.credentials_provider = Some(SharedCredentialsProvider::new(credentials_provider));
+        self
+    }
+
+    /// Sets the provider of the credentials to sign with (required)
+    pub fn set_credentials_provider(
+        &mut self,
+        credentials_provider: Option<SharedCredentialsProvider>,
+    ) -> &mut Self {
+        self.credentials_provider = credentials_provider;
+        self
+    }
+
+    /// Sets the region to sign for, or the region set when signing with SigV4a (required)
+    pub fn region(mut self, region: impl Into<String>) -> Self {
+        self.region = Some(region.into());
+        self
+    }
+
+    /// Sets the region to sign for, or the region set when signing with SigV4a (required)
+    pub fn set_region(&mut self, region: Option<String>) -> &mut Self {
+        self.region = region;
+        self
+    }
+
+    /// Sets the name of the service to sign for (required)
+    pub fn name(mut self, name: impl Into<String>) -> Self {
+        self.name = Some(name.into());
+        self
+    }
+
+    /// Sets the name of the service to sign for (required)
+    pub fn set_name(&mut self, name: Option<String>) -> &mut Self {
+        self.name = name;
+        self
+    }
+
+    /// Sets the signing algorithm to use. Defaults to [`SignatureVersion::V4`].
+    ///
+    /// Signing with [`SignatureVersion::V4a`] requires the `sigv4a` feature.
+    pub fn signature_version(mut self, signature_version: SignatureVersion) -> Self {
+        self.signature_version = Some(signature_version);
+        self
+    }
+
+    /// Sets the signing algorithm to use. Defaults to [`SignatureVersion::V4`].
+    ///
+    /// Signing with [`SignatureVersion::V4a`] requires the `sigv4a` feature.
+    pub fn set_signature_version(
+        &mut self,
+        signature_version: Option<SignatureVersion>,
+    ) -> &mut Self {
+        self.signature_version = signature_version;
+        self
+    }
+
+    /// Sets the signing settings. Defaults to [`SigningSettings::default`].
+    pub fn settings(mut self, settings: SigningSettings) -> Self {
+        self.settings = Some(settings);
+        self
+    }
+
+    /// Sets the signing settings. Defaults to [`SigningSettings::default`].
+    pub fn set_settings(&mut self, settings: Option<SigningSettings>) -> &mut Self {
+        self.settings = settings;
+        self
+    }
+
+    /// Sets how the body of a request is signed. Defaults to [`PayloadSigning::Buffered`].
+    pub fn payload_signing(mut self, payload_signing: PayloadSigning) -> Self {
+        self.payload_signing = Some(payload_signing);
+        self
+    }
+
+    /// Sets how the body of a request is signed. Defaults to [`PayloadSigning::Buffered`].
+    pub fn set_payload_signing(&mut self, payload_signing: Option<PayloadSigning>) -> &mut Self {
+        self.payload_signing = payload_signing;
+        self
+    }
+
+    /// Sets the source of the signing time. Defaults to the system clock.
+    pub fn time_source(mut self, time_source: impl TimeSource + 'static) -> Self {
+        self.time_source = Some(SharedTimeSource::new(time_source));
+        self
+    }
+
+    /// Sets the source of the signing time. Defaults to the system clock.
+    pub fn set_time_source(&mut self, time_source: Option<SharedTimeSource>) -> &mut Self {
+        self.time_source = time_source;
+        self
+    }
+
+    /// Sets the sleep implementation used to time out loading credentials. Defaults to the Tokio
+    /// sleep implementation.
+    pub fn sleep_impl(mut self, sleep_impl: impl AsyncSleep + 'static) -> Self {
+        self.sleep_impl = Some(sleep_impl.into_shared());
+        self
+    }
+
+    /// Sets the sleep implementation used to time out loading credentials. Defaults to the Tokio
+    /// sleep implementation.
+    pub fn set_sleep_impl(&mut self, sleep_impl: Option<SharedAsyncSleep>) -> &mut Self {
+        self.sleep_impl = sleep_impl;
+        self
+    }
+
+    /// Sets the cache for the credentials. Defaults to [`IdentityCache::lazy`].
+    ///
+    /// Sharing the identity cache of an SDK client shares its cached credentials, when the same
+    /// credentials provider is used.
+    pub fn identity_cache(mut self, identity_cache: impl ResolveCachedIdentity + 'static) -> Self {
+        self.identity_cache = Some(identity_cache.into_shared());
+        self
+    }
+
+    /// Sets the cache for the credentials. Defaults to [`IdentityCache::lazy`].
+    ///
+    /// Sharing the identity cache of an SDK client shares its cached credentials, when the same
+    /// credentials provider is used.
+    pub fn set_identity_cache(&mut self, identity_cache: Option<SharedIdentityCache>) -> &mut Self {
+        self.identity_cache = identity_cache;
+        self
+    }
+
+    /// Builds a [`SigningLayer`]. Will yield a [`BuildError`] if a required argument was not
+    /// given, or if the signature version isn't supported.
+    pub fn build(self) -> Result<SigningLayer, BuildError> {
+        let signature_version = self.signature_version.unwrap_or(SignatureVersion::V4);
+        #[cfg(not(feature = "sigv4a"))]
+        if signature_version == SignatureVersion::V4a {
+            return Err(BuildError::new(
+                "signing with SigV4a requires the `sigv4a` feature",
+            ));
+        }
+        let credentials_provider = self
+            .credentials_provider
+            .ok_or_else(|| BuildError::new("credentials provider is required"))?;
+        let time_source = self.time_source.unwrap_or_default();
+        let sleep_impl = self
+            .sleep_impl
+            .or_else(default_async_sleep)
+            .ok_or_else(|| BuildError::new("sleep implementation is required"))?;
+        let identity_cache = self
+            .identity_cache
+            .unwrap_or_else(|| IdentityCache::lazy().build());
+        Ok(SigningLayer {
+            config: Arc::new(SigningConfig {
+                identity_resolver: SharedIdentityResolver::new(credentials_provider),
+                runtime_components: runtime_components(
+                    identity_cache,
+                    time_source.clone(),
+                    sleep_impl,
+                ),
+                region: self
+                    .region
+                    .ok_or_else(|| BuildError::new("region is required"))?,
+                name: self
+                    .name
+                    .ok_or_else(|| BuildError::new("name is required"))?,
+                signature_version,
+                settings: self.settings.unwrap_or_default(),
+                payload_signing: self.payload_signing.unwrap_or_default(),
+                time_source,
+            }),
+        })
+    }
+}
+
+/// Returns the runtime components that identities are resolved from `identity_cache` with.
+///
+/// Identity caches are designed to run within the orchestrator, but only use its time source and
+/// sleep implementation, so the other required components are placeholders.
+fn runtime_components(
+    identity_cache: SharedIdentityCache,
+    time_source: SharedTimeSource,
+    sleep_impl: SharedAsyncSleep,
+) -> RuntimeComponents {
+    RuntimeComponentsBuilder::new("SigningLayer")
+        .with_identity_cache(Some(identity_cache))
+        .with_time_source(Some(time_source))
+        .with_sleep_impl(Some(sleep_impl))
+        .with_auth_scheme_option_resolver(Some(StaticAuthSchemeOptionResolver::new(vec![
+            NO_AUTH_SCHEME_ID,
+        ])))
+        .with_auth_scheme(NoAuthScheme::new())
+        .with_identity_resolver(NO_AUTH_SCHEME_ID, NoAuthIdentityResolver::new())
+        .with_endpoint_resolver(Some(StaticUriEndpointResolver::uri("")))
+        .with_retry_strategy(Some(NeverRetryStrategy::new()))
+        .build()
+        .expect("all required runtime components are set")
+}
+
+#[derive(Debug)]
+struct SigningConfig {
+    identity_resolver: SharedIdentityResolver,
+    runtime_components: RuntimeComponents,
+    region: String,
+    name: String,
+    signature_version: SignatureVersion,
+    settings: SigningSettings,
+    payload_signing: PayloadSigning,
+    time_source: SharedTimeSource,
+}
+
+impl SigningConfig {
+    async fn sign(
+        &self,
+        method: &str,
+        uri: &str,
+        headers: Vec<(&str, &str)>,
+        payload: Option<&[u8]>,
+    ) -> Result<SigningInstructions, BoxError> {
+        let identity = self
+            .runtime_components
+            .identity_cache()
+            .resolve_cached_identity(
+                self.identity_resolver.clone(),
+                &self.runtime_components,
+                &ConfigBag::base(),
+            )
+            .await?;
+        let time = self.time_source.now();
+        let params: SigningParams<'_> = match self.signature_version {
+            #[cfg(feature = "sigv4a")]
+            SignatureVersion::V4a => v4a::SigningParams::builder()
+                .identity(&identity)
+                .region_set(&self.region)
+                .name(&self.name)
+                .time(time)
+                .settings(self.settings.clone())
+                .build()?
+                .into(),
+            _ => v4::SigningParams::builder()
+                .identity(&identity)
+                .region(&self.region)
+                .name(&self.name)
+                .time(time)
+                .settings(self.settings.clone())
+                .build()?
+                .into(),
+        };
+        let body = payload
+            .map(SignableBody::Bytes)
+            .unwrap_or(SignableBody::UnsignedPayload);
+        let request = SignableRequest::new(method, uri, headers.into_iter(), body)?;
+        let (instructions, _signature) = sign(request, &params)?.into_parts();
+        Ok(instructions)
+    }
+}
+
+/// A [`Layer`] that signs requests with SigV4 or SigV4a.
+///
+/// See the [module documentation](self) for an example.
+#[derive(Clone, Debug)]
+pub struct SigningLayer {
+    config: Arc<SigningConfig>,
+}
+
+impl SigningLayer {
+    /// Returns a builder that can create a new `SigningLayer`.
+    pub fn builder() -> Builder {
+        Builder::default()
+    }
+}
+
+impl<S> Layer<S> for SigningLayer {
+    type Service = SigningService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        SigningService {
+            inner,
+            config: self.config.clone(),
+        }
+    }
+}
+
+/// A [`Service`] that signs requests before sending them with the inner service.
+///
+/// The service is created by a [`SigningLayer`].
+#[derive(Clone, Debug)]
+pub struct SigningService<S> {
+    inner: S,
+    config: Arc<SigningConfig>,
+}
+
+impl<S> SigningService<S> {
+    /// Returns a reference to the inner service.
+    pub fn get_ref(&self) -> &S {
+        &self.inner
+    }
+
+    /// Consumes the service, returning the inner service.
+    pub fn into_inner(self) -> S {
+        self.inner
+    }
+
+    // Takes the service that was driven to readiness, and leaves a clone in its place
+    fn take_ready(&mut self) -> S
+    where
+        S: Clone,
+    {
+        let clone = self.inner.clone();
+        std::mem::replace(&mut self.inner, clone)
+    }
+}
+
+/// The future returned by a [`SigningService`]
+pub type SigningFuture<T> = Pin<Box<dyn Future<Output = Result<T, BoxError>> + Send>>;
+
+fn header_values<'a, V: 'a>(
+    headers: impl Iterator<Item = (&'a str, &'a V)>,
+    to_str: impl Fn(&'a V) -> Result<&'a str, BoxError>,
+) -> Result<Vec<(&'a str, &'a str)>, BoxError> {
+    headers
+        .map(|(name, value)| Ok((name, to_str(value)?)))
+        .collect()
+}
+
+#[cfg(feature = "http1")]
+impl<S, B> Service<http::Request<B>> for SigningService<S>
+where
+    S: Service<http::Request<B>> + Clone + Send + 'static,
+    S::Future: Send,
+    S::Error: Into<BoxError>,
+    B: http_body_1_0::Body + From<bytes::Bytes> + Send + 'static,
+    B::Data: Send,
+    B::Error: Into<BoxError>,
+{
+    type Response = S::Response;
+    type Error = BoxError;
+    type Future = SigningFuture<S::Response>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx).map_err(Into::into)
+    }
+
+    fn call(&mut self, request: http::Request<B>) -> Self::Future {
+        use http_body_util::BodyExt;
+
+        let mut inner = self.take_ready();
+        let config = self.config.clone();
+        Box::pin(async move {
+            let (parts, body) = request.into_parts();
+            let (body, payload) = match config.payload_signing {
+                PayloadSigning::Unsigned => (body, None),
+                _ => {
+                    let payload = body.collect().await.map_err(Into::into)?.to_bytes();
+                    (B::from(payload.clone()), Some(payload))
+                }
+            };
+            let mut request = http::Request::from_parts(parts, body);
+
+            let instructions = {
+                let headers = header_values(
+                    request.headers().iter().map(|(k, v)| (k.as_str(), v)),
+                    |value| value.to_str().map_err(Into::into),
+                )?;
+                config
+                    .sign(
+                        request.method().as_str(),
+                        &request.uri().to_string(),
+                        headers,
+                        payload.as_deref(),
+                    )
+                    .await?
+            };
+            instructions.apply_to_request_http1x(&mut request);
+            inner.call(request).await.map_err(Into::into)
+        })
+    }
+}
+
+#[cfg(feature = "http0-compat")]
+impl<S, B> Service<http0::Request<B>> for SigningService<S>
+where
+    S: Service<http0::Request<B>> + Clone + Send + 'static,
+    S::Future: Send,
+    S::Error: Into<BoxError>,
+    B: http_body_0_4::Body + From<bytes::Bytes> + Send + 'static,
+    B::Data: Send,
+    B::Error: Into<BoxError>,
+{
+    type Response = S::Response;
+    type Error = BoxError;
+    type Future = SigningFuture<S::Response>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx).map_err(Into::into)
+    }
+
+    fn call(&mut self, request: http0::Request<B>) -> Self::Future {
+        use bytes::{Buf, BufMut};
+
+        let mut inner = self.take_ready();
+        let config = self.config.clone();
+        Box::pin(async move {
+            let (parts, body) = request.into_parts();
+            let (body, payload) = match config.payload_signing {
+                PayloadSigning::Unsigned => (body, None),
+                _ => {
+                    let mut body = Box::pin(body);
+                    let mut payload = bytes::BytesMut::new();
+                    while let Some(data) = http_body_0_4::Body::data(&mut body).await {
+                        let mut data = data.map_err(Into::into)?;
+                        payload.put(data.copy_to_bytes(data.remaining()));
+                    }
+                    let payload = payload.freeze();
+                    (B::from(payload.clone()), Some(payload))
+                }
+            };
+            let mut request = http0::Request::from_parts(parts, body);
+
+            let instructions = {
+                let headers = header_values(
+                    request.headers().iter().map(|(k, v)| (k.as_str(), v)),
+                    |value| value.to_str().map_err(Into::into),
+                )?;
+                config
+                    .sign(
+                        request.method().as_str(),
+                        &request.uri().to_string(),
+                        headers,
+                        payload.as_deref(),
+                    )
+                    .await?
+            };
+            instructions.apply_to_request_http0x(&mut request);
+            inner.call(request).await.map_err(Into::into)
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{PayloadSigning, SigningLayer};
+    use crate::http_request::{sign, SignableBody, SignableRequest, SigningSettings};
+    use crate::sign::v4;
+    use aws_credential_types::provider::{future, ProvideCredentials};
+    use aws_credential_types::Credentials;
+    use aws_smithy_async::time::StaticTimeSource;
+    use aws_smithy_runtime::client::identity::IdentityCache;
+    use aws_smithy_runtime_api::client::identity::Identity;
+    use bytes::Bytes;
+    use http_body_util::{BodyExt, Full};
+    use std::convert::Infallible;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use std::sync::Arc;
+    use std::time::{Duration, SystemTime};
+    use tower::{service_fn, Layer, ServiceExt};
+
+    const NOW: u64 = 1369353600;
+
+    #[derive(Debug, Default)]
+    struct CountingProvider {
+        calls: Arc<AtomicUsize>,
+        expires_in: Option<Duration>,
+    }
+
+    impl ProvideCredentials for CountingProvider {
+        fn provide_credentials<'a>(&'a self) -> future::ProvideCredentials<'a>
+        where
+            Self: 'a,
+        {
+            self.calls.fetch_add(1, Ordering::SeqCst);
+            let expiry = self
+                .expires_in
+                .map(|expires_in| SystemTime::UNIX_EPOCH + Duration::from_secs(NOW) + expires_in);
+            future::ProvideCredentials::ready(Ok(credentials(expiry)))
+        }
+    }
+
+    fn credentials(expiry: Option<SystemTime>) -> Credentials {
+        Credentials::new(
+            "ANOTREAL",
+            "notrealrnrELgWzOk3IfjzDKtFBhDby",
+            None,
+            expiry,
+            "test",
+        )
+    }
+
+    fn layer(payload_signing: PayloadSigning, provider: CountingProvider) -> SigningLayer {
+        SigningLayer::builder()
+            .credentials_provider(provider)
+            .region("us-east-1")
+            .name("execute-api")
+            .payload_signing(payload_signing)
+            .time_source(StaticTimeSource::from_secs(NOW))
+            .build()
+            .unwrap()
+    }
+
+    fn expected_authorization(body: SignableBody<'_>) -> String {
+        let identity: Identity = credentials(None).into();
+        let params = v4::SigningParams::builder()
+            .identity(&identity)
+            .region("us-east-1")
+            .name("execute-api")
+            .time(SystemTime::UNIX_EPOCH + Duration::from_secs(NOW))
+            .settings(SigningSettings::default())
+            .build()
+            .unwrap()
+            .into();
+        let request = SignableRequest::new(
+            "POST",
+            "https://api.example.com/prod/items",
+            [("content-type", "application/json")].into_iter(),
+            body,
+        )
+        .unwrap();
+        let (instructions, _) = sign(request, &params).unwrap().into_parts();
+        let mut request = http::Request::new(());
+        instructions.apply_to_request_http1x(&mut request);
+        request.headers()["authorization"]
+            .to_str()
+            .unwrap()
+            .to_owned()
+    }
+
+    fn request() -> http::Request<Full<Bytes>> {
+        http::Request::post("https://api.example.com/prod/items")
+            .header("content-type", "application/json")
+            .body(Full::new(Bytes::from_static(br#"{"name":"item"}"#)))
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn signs_buffered_body() {
+        let service = layer(PayloadSigning::Buffered, Default::default()).layer(service_fn(
+            |request: http::Request<Full<Bytes>>| async move { Ok::<_, Infallible>(request) },
+        ));
+        let signed = service.oneshot(request()).await.unwrap();
+
+        assert_eq!(
+            expected_authorization(SignableBody::Bytes(br#"{"name":"item"}"#)),
+            signed.headers()["authorization"]
+        );
+        assert_eq!("20130524T000000Z", signed.headers()["x-amz-date"]);
+        let body = signed.into_body().collect().await.unwrap().to_bytes();
+        assert_eq!(&br#"{"name":"item"}"#[..], &body[..]);
+    }
+
+    #[tokio::test]
+    async fn signs_unsigned_payload() {
+        let service = layer(PayloadSigning::Unsigned, Default::default()).layer(service_fn(
+            |request: http::Request<Full<Bytes>>| async move { Ok::<_, Infallible>(request) },
+        ));
+        let signed = service.oneshot(request()).await.unwrap();
+
+        assert_eq!(
+            expected_authorization(SignableBody::UnsignedPayload),
+            signed.headers()["authorization"]
+        );
+    }
+
+    #[cfg(feature = "http0-compat")]
+    #[tokio::test]
+    async fn signs_http0_requests() {
+        let service = layer(PayloadSigning::Buffered, Default::default()).layer(service_fn(
+            |request: http0::Request<http_body_0_4::Full<Bytes>>| async move {
+                Ok::<_, Infallible>(request)
+            },
+        ));
+        let request = http0::Request::post("https://api.example.com/prod/items")
+            .header("content-type", "application/json")
+            .body(http_body_0_4::Full::new(Bytes::from_static(
+                br#"{"name":"item"}"#,
+            )))
+            .unwrap();
+        let signed = service.oneshot(request).await.unwrap();
+
+        assert_eq!(
+            expected_authorization(SignableBody::Bytes(br#"{"name":"item"}"#)),
+            signed.headers()["authorization"]
+        );
+    }
+
+    #[tokio::test]
+    async fn caches_credentials_until_they_expire() {
+        let send = |provider: CountingProvider| async move {
+            let service = layer(PayloadSigning::Buffered, provider).layer(service_fn(
+                |_request: http::Request<Full<Bytes>>| async { Ok::<_, Infallible>(()) },
+            ));
+            for _ in 0..3 {
+                service.clone().oneshot(request()).await.unwrap();
+            }
+        };
+
+        let calls = Arc::new(AtomicUsize::new(0));
+        send(CountingProvider {
+            calls: calls.clone(),
+            expires_in: Some(Duration::from_secs(3600)),
+        })
+        .await;
+        assert_eq!(1, calls.load(Ordering::SeqCst));
+
+        // Credentials that expire within the buffer time are reloaded for every request
+        let calls = Arc::new(AtomicUsize::new(0));
+        send(CountingProvider {
+            calls: calls.clone(),
+            expires_in: Some(Duration::from_secs(5)),
+        })
+        .await;
+        assert_eq!(3, calls.load(Ordering::SeqCst));
+    }
+
+    #[tokio::test]
+    async fn uses_the_given_identity_cache() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let layer = SigningLayer::builder()
+            .credentials_provider(CountingProvider {
+                calls: calls.clone(),
+                expires_in: Some(Duration::from_secs(3600)),
+            })
+            .region("us-east-1")
+            .name("execute-api")
+            .identity_cache(IdentityCache::no_cache())
+            .build()
+            .unwrap();
+        let service = layer.layer(service_fn(|_request: http::Request<Full<Bytes>>| async {
+            Ok::<_, Infallible>(())
+        }));
+        for _ in 0..3 {
+            service.clone().oneshot(request()).await.unwrap();
+        }
+        assert_eq!(3, calls.load(Ordering::SeqCst));
+    }
+
+    #[test]
+    fn build_requires_region() {
+        let err = SigningLayer::builder()
+            .credentials_provider(Credentials::for_tests())
+            .name("execute-api")
+            .build()
+            .unwrap_err();
+        assert_eq!("region is required", err.to_string());
+    }
+}
@@ -30,6 +30,12 @@ pub mod event_stream;
 #[cfg(feature = "sign-http")]
 pub mod http_request;
 
+#[cfg(feature = "sign-http")]
+pub mod presign;
+
+#[cfg(all(feature = "tower", any(feature = "http1", feature = "http0-compat")))]
+pub mod layer;
+
 /// The version of the signing algorithm to use
 #[derive(Debug, Eq, PartialEq, Copy, Clone)]
 #[non_exhaustive]
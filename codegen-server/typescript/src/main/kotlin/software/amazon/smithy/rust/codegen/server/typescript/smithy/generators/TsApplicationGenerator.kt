@@ -44,6 +44,7 @@ class TsApplicationGenerator(
     private val codegenScope =
         arrayOf(
             "SmithyServer" to ServerCargoDependency.smithyHttpServer(runtimeConfig).toType(),
+            "SmithyTs" to TsServerCargoDependency.smithyHttpServerTs(runtimeConfig).toType(),
             "napi" to TsServerCargoDependency.Napi.toType(),
             "napi_derive" to TsServerCargoDependency.NapiDerive.toType(),
             "tokio" to TsServerCargoDependency.Tokio.toType(),
@@ -77,10 +78,7 @@ class TsApplicationGenerator(
                 val fnName = operationName.toSnakeCase()
                 rustTemplate(
                     """
-                    pub(crate) $fnName: #{napi}::threadsafe_function::ThreadsafeFunction<
-                        $input,
-                        #{napi}::threadsafe_function::ErrorStrategy::Fatal
-                    >,
+                    pub(crate) $fnName: #{SmithyTs}::TsHandler<$input>,
                     """,
                     *codegenScope,
                 )
@@ -123,7 +121,7 @@ class TsApplicationGenerator(
     private fun renderAppCreate(writer: RustWriter) {
         Attribute("napi(constructor)").render(writer)
         writer.rustBlockTemplate(
-            """pub fn create(ts_handlers: TsHandlers) -> #{napi}::Result<Self>""",
+            """pub fn create(env: #{napi}::Env, ts_handlers: TsHandlers) -> #{napi}::Result<Self>""",
             *codegenScope,
         ) {
             operations.map { operation ->
@@ -132,9 +130,7 @@ class TsApplicationGenerator(
                 val fnName = operationName.toSnakeCase()
                 rustTemplate(
                     """
-                    let $fnName: #{napi}::threadsafe_function::ThreadsafeFunction<
-                        $input, #{napi}::threadsafe_function::ErrorStrategy::Fatal
-                    > = ts_handlers.$fnName.create_threadsafe_function(0, |ctx| Ok(vec![ctx.value]))?;
+                    let $fnName: #{SmithyTs}::TsHandler<$input> = #{SmithyTs}::TsHandler::new(&env, &ts_handlers.$fnName)?;
                     """,
                     *codegenScope,
                 )
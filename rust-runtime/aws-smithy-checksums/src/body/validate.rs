@@ -7,6 +7,8 @@
 //! error if it doesn't match.
 
 use crate::http::HttpChecksum;
+use crate::multipart::CompositeChecksum;
+use crate::ChecksumAlgorithm;
 
 use aws_smithy_types::body::SdkBody;
 
@@ -27,6 +29,48 @@ pin_project! {
         inner: InnerBody,
         checksum: Option<Box<dyn HttpChecksum>>,
         precalculated_checksum: Bytes,
+        composite: Option<CompositeState>,
+    }
+}
+
+// The state of a body being validated against a composite checksum
+struct CompositeState {
+    algorithm: ChecksumAlgorithm,
+    part_size: u64,
+    // The number of bytes of the current part that have been read
+    part_bytes_read: u64,
+    part_checksums: Vec<Bytes>,
+    expected_part_count: usize,
+}
+
+impl CompositeState {
+    // Update the checksum of the current part, starting a new part every `part_size` bytes
+    fn update(&mut self, checksum: &mut Box<dyn HttpChecksum>, mut data: &[u8]) {
+        while !data.is_empty() {
+            let remaining = (self.part_size - self.part_bytes_read) as usize;
+            let (part, rest) = data.split_at(remaining.min(data.len()));
+            checksum.update(part);
+            self.part_bytes_read += part.len() as u64;
+            if self.part_bytes_read == self.part_size {
+                self.finish_part(checksum);
+            }
+            data = rest;
+        }
+    }
+
+    fn finish_part(&mut self, checksum: &mut Box<dyn HttpChecksum>) {
+        let part_checksum = std::mem::replace(checksum, self.algorithm.into_impl());
+        self.part_checksums.push(part_checksum.finalize());
+        self.part_bytes_read = 0;
+    }
+
+    // Finish the final part and calculate the composite checksum
+    fn finalize(mut self, mut checksum: Box<dyn HttpChecksum>) -> CompositeChecksum {
+        // An empty body is a single empty part
+        if self.part_bytes_read > 0 || self.part_checksums.is_empty() {
+            self.finish_part(&mut checksum);
+        }
+        CompositeChecksum::from_part_checksums(self.algorithm, &self.part_checksums)
     }
 }
 
@@ -42,6 +86,35 @@ impl ChecksumBody<SdkBody> {
             inner: body,
             checksum: Some(checksum),
             precalculated_checksum,
+            composite: None,
+        }
+    }
+
+    /// Given an `SdkBody`, the `ChecksumAlgorithm` of its parts, the size in bytes of every part
+    /// except the last, and an expected [`CompositeChecksum`], create a new
+    /// `ChecksumBody<SdkBody>` that validates the body against the composite checksum.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `part_size` is zero.
+    pub fn new_composite(
+        body: SdkBody,
+        algorithm: ChecksumAlgorithm,
+        part_size: u64,
+        expected: CompositeChecksum,
+    ) -> Self {
+        assert!(part_size > 0, "part_size must be greater than zero");
+        Self {
+            inner: body,
+            checksum: Some(algorithm.into_impl()),
+            precalculated_checksum: expected.checksum().clone(),
+            composite: Some(CompositeState {
+                algorithm,
+                part_size,
+                part_bytes_read: 0,
+                part_checksums: Vec::new(),
+                expected_part_count: expected.part_count(),
+            }),
         }
     }
 
@@ -67,7 +140,10 @@ impl ChecksumBody<SdkBody> {
                     }
                 };
 
-                checksum.update(&data);
+                match this.composite.as_mut() {
+                    Some(composite) => composite.update(checksum, &data),
+                    None => checksum.update(&data),
+                }
                 Poll::Ready(Some(Ok(data)))
             }
             // Once the inner body has stopped returning data, check the checksum
@@ -83,6 +159,24 @@ impl ChecksumBody<SdkBody> {
                     }
                 };
 
+                if let Some(composite) = this.composite.take() {
+                    let expected_part_count = composite.expected_part_count;
+                    let actual = composite.finalize(checksum);
+                    return if *this.precalculated_checksum == actual.checksum()
+                        && expected_part_count == actual.part_count()
+                    {
+                        Poll::Ready(None)
+                    } else {
+                        Poll::Ready(Some(Err(Box::new(Error::CompositeChecksumMismatch {
+                            expected: CompositeChecksum::new(
+                                this.precalculated_checksum.clone(),
+                                expected_part_count,
+                            ),
+                            actual,
+                        }))))
+                    };
+                }
+
                 let actual_checksum = checksum.finalize();
                 if *this.precalculated_checksum == actual_checksum {
                     Poll::Ready(None)
@@ -107,6 +201,11 @@ pub enum Error {
     /// The actual checksum didn't match the expected checksum. The checksummed data has been
     /// altered since the expected checksum was calculated.
     ChecksumMismatch { expected: Bytes, actual: Bytes },
+    /// The actual composite checksum or part count didn't match the expected composite checksum.
+    CompositeChecksumMismatch {
+        expected: CompositeChecksum,
+        actual: CompositeChecksum,
+    },
 }
 
 impl Display for Error {
@@ -118,6 +217,14 @@ impl Display for Error {
                 hex::encode(expected),
                 hex::encode(actual)
             ),
+            Error::CompositeChecksumMismatch { expected, actual } => write!(
+                f,
+                "body composite checksum mismatch. expected body composite checksum to be {} of {} parts but it was {} of {} parts",
+                hex::encode(expected.checksum()),
+                expected.part_count(),
+                hex::encode(actual.checksum()),
+                actual.part_count()
+            ),
         }
     }
 }
@@ -154,6 +261,7 @@ impl http_body::Body for ChecksumBody<SdkBody> {
 #[cfg(test)]
 mod tests {
     use crate::body::validate::{ChecksumBody, Error};
+    use crate::multipart::CompositeChecksum;
     use crate::ChecksumAlgorithm;
     use aws_smithy_types::body::SdkBody;
     use bytes::{Buf, Bytes};
@@ -187,6 +295,7 @@ mod tests {
                             assert_eq!(expected, &non_matching_checksum);
                             assert_eq!(actual, &actual_checksum);
                         }
+                        e => panic!("unexpected error: {e}"),
                     }
 
                     return;
@@ -218,4 +327,71 @@ mod tests {
         // Verify data is complete and unaltered
         assert_eq!(input_text, output_text);
     }
+
+    async fn read_composite(
+        input: &'static str,
+        part_size: u64,
+        expected: CompositeChecksum,
+    ) -> Result<(), Error> {
+        let mut body = ChecksumBody::new_composite(
+            SdkBody::from(input),
+            ChecksumAlgorithm::Crc32,
+            part_size,
+            expected,
+        );
+        while let Some(data) = body.data().await {
+            if let Err(e) = data {
+                return Err(*e.downcast::<Error>().unwrap());
+            }
+        }
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_composite_checksum_validated_body() {
+        let input_text = "This is some test text for an SdkBody";
+        let parts = input_text.as_bytes().chunks(10).map(|part| {
+            let checksum = crc32fast::hash(part);
+            checksum.to_be_bytes()
+        });
+        let expected = CompositeChecksum::from_part_checksums(ChecksumAlgorithm::Crc32, parts);
+        assert_eq!(4, expected.part_count());
+
+        read_composite(input_text, 10, expected.clone())
+            .await
+            .expect("checksum matches");
+
+        // The same data split into different parts has a different composite checksum
+        let err = read_composite(input_text, 20, expected.clone())
+            .await
+            .unwrap_err();
+        match err {
+            Error::CompositeChecksumMismatch {
+                expected: e,
+                actual,
+            } => {
+                assert_eq!(expected, e);
+                assert_eq!(2, actual.part_count());
+            }
+            e => panic!("unexpected error: {e}"),
+        }
+
+        // The part count is validated along with the checksum
+        let wrong_count = CompositeChecksum::new(expected.checksum().clone(), 3);
+        assert!(matches!(
+            read_composite(input_text, 10, wrong_count).await,
+            Err(Error::CompositeChecksumMismatch { .. })
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_composite_checksum_of_empty_body_is_one_empty_part() {
+        let expected = CompositeChecksum::from_part_checksums(
+            ChecksumAlgorithm::Crc32,
+            [crc32fast::hash(b"").to_be_bytes()],
+        );
+        read_composite("", 10, expected)
+            .await
+            .expect("checksum matches");
+    }
 }
@@ -0,0 +1,152 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! Combine the CRCs of two consecutive blocks of data into the CRC of both blocks.
+//!
+//! Given `crc(a)`, `crc(b)`, and the length of `b`, these functions return `crc(a + b)` without
+//! reading any data, in the style of zlib's `crc32_combine`. This is how the full object checksum
+//! of a multipart upload is calculated from the checksums of its parts.
+//!
+//! ```
+//! use aws_smithy_checksums::combine;
+//!
+//! let first = crc32fast::hash(b"hello ");
+//! let second = crc32fast::hash(b"world");
+//! assert_eq!(crc32fast::hash(b"hello world"), combine::crc32(first, second, 5));
+//! ```
+
+/// A reflected CRC with an initial value and final XOR of all ones.
+struct ReflectedCrc {
+    // The reflected polynomial, without its highest term
+    polynomial: u64,
+    // The highest bit of the CRC, which represents x^0 in reflected form
+    x0: u64,
+}
+
+const CRC_32: ReflectedCrc = ReflectedCrc {
+    polynomial: 0xEDB8_8320,
+    x0: 1 << 31,
+};
+const CRC_32_C: ReflectedCrc = ReflectedCrc {
+    polynomial: 0x82F6_3B78,
+    x0: 1 << 31,
+};
+const CRC_64_NVME: ReflectedCrc = ReflectedCrc {
+    polynomial: 0x9A6C_9329_AC4B_C9B5,
+    x0: 1 << 63,
+};
+
+impl ReflectedCrc {
+    /// Returns `a * b` modulo the polynomial.
+    fn multiply(&self, a: u64, mut b: u64) -> u64 {
+        let mut product = 0;
+        let mut term = self.x0;
+        while term != 0 {
+            if a & term != 0 {
+                product ^= b;
+            }
+            term >>= 1;
+            b = if b & 1 != 0 {
+                (b >> 1) ^ self.polynomial
+            } else {
+                b >> 1
+            };
+        }
+        product
+    }
+
+    /// Returns `x^(8 * bytes)` modulo the polynomial.
+    fn shift(&self, mut bytes: u64) -> u64 {
+        // x^8, the shift of a single byte
+        let mut square = self.x0 >> 8;
+        let mut result = self.x0;
+        while bytes != 0 {
+            if bytes & 1 != 0 {
+                result = self.multiply(result, square);
+            }
+            square = self.multiply(square, square);
+            bytes >>= 1;
+        }
+        result
+    }
+
+    fn combine(&self, crc1: u64, crc2: u64, len2: u64) -> u64 {
+        // The initial values and final XORs cancel out, since the CRC is linear
+        self.multiply(self.shift(len2), crc1) ^ crc2
+    }
+}
+
+/// Returns the CRC32 of two consecutive blocks of data, given the CRC32 of the first block
+/// (`crc1`), and the CRC32 (`crc2`) and length in bytes (`len2`) of the second block.
+pub fn crc32(crc1: u32, crc2: u32, len2: u64) -> u32 {
+    CRC_32.combine(crc1.into(), crc2.into(), len2) as u32
+}
+
+/// Returns the CRC32C of two consecutive blocks of data, given the CRC32C of the first block
+/// (`crc1`), and the CRC32C (`crc2`) and length in bytes (`len2`) of the second block.
+pub fn crc32c(crc1: u32, crc2: u32, len2: u64) -> u32 {
+    CRC_32_C.combine(crc1.into(), crc2.into(), len2) as u32
+}
+
+/// Returns the CRC64-NVME of two consecutive blocks of data, given the CRC64-NVME of the first
+/// block (`crc1`), and the CRC64-NVME (`crc2`) and length in bytes (`len2`) of the second block.
+pub fn crc64_nvme(crc1: u64, crc2: u64, len2: u64) -> u64 {
+    CRC_64_NVME.combine(crc1, crc2, len2)
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::ChecksumAlgorithm;
+
+    fn crc(algorithm: ChecksumAlgorithm, data: &[u8]) -> u64 {
+        let mut checksum = algorithm.into_impl();
+        checksum.update(data);
+        checksum
+            .finalize()
+            .iter()
+            .fold(0, |crc, byte| crc << 8 | u64::from(*byte))
+    }
+
+    fn combine(algorithm: ChecksumAlgorithm, crc1: u64, crc2: u64, len2: u64) -> u64 {
+        match algorithm {
+            ChecksumAlgorithm::Crc32 => super::crc32(crc1 as u32, crc2 as u32, len2).into(),
+            ChecksumAlgorithm::Crc32c => super::crc32c(crc1 as u32, crc2 as u32, len2).into(),
+            ChecksumAlgorithm::Crc64Nvme => super::crc64_nvme(crc1, crc2, len2),
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn combines_crcs() {
+        let data: Vec<u8> = (0..10_000u32).map(|i| (i * 31 % 251) as u8).collect();
+        for algorithm in [
+            ChecksumAlgorithm::Crc32,
+            ChecksumAlgorithm::Crc32c,
+            ChecksumAlgorithm::Crc64Nvme,
+        ] {
+            let expected = crc(algorithm, &data);
+            for split in [0, 1, 7, 8, 1000, 4097, 9999, 10_000] {
+                let (first, second) = data.split_at(split);
+                assert_eq!(
+                    expected,
+                    combine(
+                        algorithm,
+                        crc(algorithm, first),
+                        crc(algorithm, second),
+                        second.len() as u64
+                    ),
+                    "{algorithm:?} split at {split}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn combining_an_empty_block_is_a_no_op() {
+        assert_eq!(0x1234_5678, super::crc32(0x1234_5678, 0, 0));
+        assert_eq!(0x1234_5678, super::crc32c(0x1234_5678, 0, 0));
+        assert_eq!(0x1234_5678, super::crc64_nvme(0x1234_5678, 0, 0));
+    }
+}
@@ -3,6 +3,7 @@
  * SPDX-License-Identifier: Apache-2.0
  */
 
+use crate::ChecksumAlgorithm;
 use std::error::Error;
 use std::fmt;
 
@@ -29,10 +30,74 @@ impl fmt::Display for UnknownChecksumAlgorithmError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(
             f,
-            r#"unknown checksum algorithm "{}", please pass a known algorithm name ("crc32", "crc32c", "sha1", "sha256", "md5")"#,
+            r#"unknown checksum algorithm "{}", please pass a known algorithm name ("crc32", "crc32c", "crc64nvme", "sha1", "sha256", "md5")"#,
             self.checksum_algorithm
         )
     }
 }
 
 impl Error for UnknownChecksumAlgorithmError {}
+
+#[derive(Debug)]
+enum MultipartChecksumErrorKind {
+    NotCombinable(ChecksumAlgorithm),
+    InvalidPartChecksumLength {
+        algorithm: ChecksumAlgorithm,
+        length: usize,
+    },
+    InvalidCompositeChecksum(String),
+}
+
+/// A multipart checksum couldn't be calculated or parsed
+#[derive(Debug)]
+pub struct MultipartChecksumError {
+    kind: MultipartChecksumErrorKind,
+}
+
+impl MultipartChecksumError {
+    pub(crate) fn not_combinable(algorithm: ChecksumAlgorithm) -> Self {
+        Self {
+            kind: MultipartChecksumErrorKind::NotCombinable(algorithm),
+        }
+    }
+
+    pub(crate) fn invalid_part_checksum_length(
+        algorithm: ChecksumAlgorithm,
+        length: usize,
+    ) -> Self {
+        Self {
+            kind: MultipartChecksumErrorKind::InvalidPartChecksumLength { algorithm, length },
+        }
+    }
+
+    pub(crate) fn invalid_composite_checksum(value: impl Into<String>) -> Self {
+        Self {
+            kind: MultipartChecksumErrorKind::InvalidCompositeChecksum(value.into()),
+        }
+    }
+}
+
+impl fmt::Display for MultipartChecksumError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        use MultipartChecksumErrorKind::*;
+        match &self.kind {
+            NotCombinable(algorithm) => write!(
+                f,
+                "{} checksums can't be combined into a full object checksum, only CRCs can",
+                algorithm.as_str()
+            ),
+            InvalidPartChecksumLength { algorithm, length } => write!(
+                f,
+                "a {} part checksum must be {} bytes long but it was {length} bytes long",
+                algorithm.as_str(),
+                algorithm.checksum_size()
+            ),
+            InvalidCompositeChecksum(value) => write!(
+                f,
+                r#""{value}" is not a composite checksum, expected a base64 encoded checksum followed by "-<part count>""#
+            ),
+        }
+    }
+}
+
+impl Error for MultipartChecksumError {}
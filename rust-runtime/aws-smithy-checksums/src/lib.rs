@@ -21,21 +21,29 @@ use bytes::Bytes;
 use std::str::FromStr;
 
 pub mod body;
+pub mod combine;
 pub mod error;
 pub mod http;
+pub mod multipart;
 
 // Valid checksum algorithm names
 pub const CRC_32_NAME: &str = "crc32";
 pub const CRC_32_C_NAME: &str = "crc32c";
+pub const CRC_64_NVME_NAME: &str = "crc64nvme";
 pub const SHA_1_NAME: &str = "sha1";
 pub const SHA_256_NAME: &str = "sha256";
 pub const MD5_NAME: &str = "md5";
 
 /// We only support checksum calculation and validation for these checksum algorithms.
+///
+/// More algorithms may be supported in the future, so matching on this enum requires a wildcard
+/// arm. It was exhaustive before `Crc64Nvme` was added in 0.61.0.
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[non_exhaustive]
 pub enum ChecksumAlgorithm {
     Crc32,
     Crc32c,
+    Crc64Nvme,
     Md5,
     Sha1,
     Sha256,
@@ -47,6 +55,7 @@ impl FromStr for ChecksumAlgorithm {
     /// Create a new `ChecksumAlgorithm` from an algorithm name. Valid algorithm names are:
     /// - "crc32"
     /// - "crc32c"
+    /// - "crc64nvme"
     /// - "sha1"
     /// - "sha256"
     /// - "md5"
@@ -57,6 +66,8 @@ impl FromStr for ChecksumAlgorithm {
             Ok(Self::Crc32)
         } else if checksum_algorithm.eq_ignore_ascii_case(CRC_32_C_NAME) {
             Ok(Self::Crc32c)
+        } else if checksum_algorithm.eq_ignore_ascii_case(CRC_64_NVME_NAME) {
+            Ok(Self::Crc64Nvme)
         } else if checksum_algorithm.eq_ignore_ascii_case(SHA_1_NAME) {
             Ok(Self::Sha1)
         } else if checksum_algorithm.eq_ignore_ascii_case(SHA_256_NAME) {
@@ -75,6 +86,7 @@ impl ChecksumAlgorithm {
         match self {
             Self::Crc32 => Box::<Crc32>::default(),
             Self::Crc32c => Box::<Crc32c>::default(),
+            Self::Crc64Nvme => Box::<Crc64Nvme>::default(),
             Self::Md5 => Box::<Md5>::default(),
             Self::Sha1 => Box::<Sha1>::default(),
             Self::Sha256 => Box::<Sha256>::default(),
@@ -86,11 +98,26 @@ impl ChecksumAlgorithm {
         match self {
             Self::Crc32 => CRC_32_NAME,
             Self::Crc32c => CRC_32_C_NAME,
+            Self::Crc64Nvme => CRC_64_NVME_NAME,
             Self::Md5 => MD5_NAME,
             Self::Sha1 => SHA_1_NAME,
             Self::Sha256 => SHA_256_NAME,
         }
     }
+
+    /// Return `true` if this algorithm is a CRC.
+    ///
+    /// The CRCs of consecutive parts of an object can be [combined](crate::combine) into the CRC
+    /// of the whole object, so they support [full object checksums](crate::multipart) of
+    /// multipart uploads.
+    pub fn is_crc(&self) -> bool {
+        matches!(self, Self::Crc32 | Self::Crc32c | Self::Crc64Nvme)
+    }
+
+    // The size in bytes of a checksum calculated with this algorithm
+    pub(crate) fn checksum_size(&self) -> u64 {
+        Checksum::size(self.into_impl().as_ref())
+    }
 }
 
 /// Types implementing this trait can calculate checksums.
@@ -183,6 +210,38 @@ impl Checksum for Crc32c {
     }
 }
 
+#[derive(Default)]
+struct Crc64Nvme {
+    hasher: crc64fast_nvme::Digest,
+}
+
+impl Crc64Nvme {
+    fn update(&mut self, bytes: &[u8]) {
+        self.hasher.write(bytes);
+    }
+
+    fn finalize(self) -> Bytes {
+        Bytes::copy_from_slice(self.hasher.sum64().to_be_bytes().as_slice())
+    }
+
+    // Size of the checksum in bytes
+    fn size() -> u64 {
+        8
+    }
+}
+
+impl Checksum for Crc64Nvme {
+    fn update(&mut self, bytes: &[u8]) {
+        Self::update(self, bytes)
+    }
+    fn finalize(self: Box<Self>) -> Bytes {
+        Self::finalize(*self)
+    }
+    fn size(&self) -> u64 {
+        Self::size()
+    }
+}
+
 #[derive(Debug, Default)]
 struct Sha1 {
     hasher: sha1::Sha1,
@@ -293,10 +352,10 @@ impl Checksum for Md5 {
 mod tests {
     use super::{
         http::{
-            CRC_32_C_HEADER_NAME, CRC_32_HEADER_NAME, MD5_HEADER_NAME, SHA_1_HEADER_NAME,
-            SHA_256_HEADER_NAME,
+            CRC_32_C_HEADER_NAME, CRC_32_HEADER_NAME, CRC_64_NVME_HEADER_NAME, MD5_HEADER_NAME,
+            SHA_1_HEADER_NAME, SHA_256_HEADER_NAME,
         },
-        Crc32, Crc32c, Md5, Sha1, Sha256,
+        Crc32, Crc32c, Crc64Nvme, Md5, Sha1, Sha256,
     };
 
     use crate::http::HttpChecksum;
@@ -349,6 +408,30 @@ mod tests {
         assert_eq!(decoded_checksum, expected_checksum);
     }
 
+    #[test]
+    fn test_crc64nvme_checksum() {
+        let mut checksum = Crc64Nvme::default();
+        checksum.update(TEST_DATA.as_bytes());
+        let checksum_result = Box::new(checksum).headers();
+        let encoded_checksum = checksum_result.get(CRC_64_NVME_HEADER_NAME).unwrap();
+        let decoded_checksum = base64_encoded_checksum_to_hex_string(encoded_checksum);
+
+        let expected_checksum = "0xAECAF3AF9C98A855";
+
+        assert_eq!(decoded_checksum, expected_checksum);
+    }
+
+    #[test]
+    fn test_crc64nvme_check_value() {
+        // The standard check value for CRC-64/NVME
+        let mut checksum = Crc64Nvme::default();
+        checksum.update(b"123456789");
+        assert_eq!(
+            0xAE8B14860A799888u64.to_be_bytes().as_slice(),
+            &checksum.finalize()[..]
+        );
+    }
+
     #[test]
     fn test_sha1_checksum() {
         let mut checksum = Sha1::default();
@@ -389,6 +472,24 @@ mod tests {
         assert_eq!(decoded_checksum, expected_checksum);
     }
 
+    #[test]
+    fn test_checksum_algorithm_names_round_trip() {
+        for algorithm in [
+            ChecksumAlgorithm::Crc32,
+            ChecksumAlgorithm::Crc32c,
+            ChecksumAlgorithm::Crc64Nvme,
+            ChecksumAlgorithm::Md5,
+            ChecksumAlgorithm::Sha1,
+            ChecksumAlgorithm::Sha256,
+        ] {
+            assert_eq!(algorithm, algorithm.as_str().parse().unwrap());
+        }
+        assert_eq!(
+            ChecksumAlgorithm::Crc64Nvme,
+            "CRC64NVME".parse::<ChecksumAlgorithm>().unwrap()
+        );
+    }
+
     #[test]
     fn test_checksum_algorithm_returns_error_for_unknown() {
         let error = "some invalid checksum algorithm"
@@ -0,0 +1,252 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! Checksums of objects uploaded in multiple parts.
+//!
+//! There are two ways to checksum an object from the checksums of its parts:
+//! - A **full object checksum** is the checksum of the whole object, exactly as if it had been
+//!   uploaded in one part. It can only be calculated for [CRCs](crate::ChecksumAlgorithm::is_crc),
+//!   by [combining](crate::combine) the part checksums with [`full_object_checksum`].
+//! - A **composite checksum** is the checksum of the concatenated part checksums, and is written
+//!   as the base64 encoded checksum followed by `-<part count>`, e.g. `ZAUo/w==-3`. It can be
+//!   calculated for any algorithm with [`CompositeChecksum::from_part_checksums`], and the body of
+//!   an object can be validated against it with
+//!   [`ChecksumBody::new_composite`](crate::body::validate::ChecksumBody::new_composite).
+
+use crate::error::MultipartChecksumError;
+use crate::{combine, ChecksumAlgorithm};
+use aws_smithy_types::base64;
+use bytes::Bytes;
+use std::fmt;
+use std::str::FromStr;
+
+/// Calculate the full object checksum of an object from the checksums of its parts.
+///
+/// `parts` yields the checksum and the length in bytes of each part, in order. Returns an error
+/// if `algorithm` isn't a CRC or if a part checksum isn't the size of an `algorithm` checksum.
+///
+/// ```
+/// use aws_smithy_checksums::multipart::full_object_checksum;
+/// use aws_smithy_checksums::ChecksumAlgorithm;
+///
+/// let parts = [
+///     (crc32fast::hash(b"hello ").to_be_bytes(), 6),
+///     (crc32fast::hash(b"world").to_be_bytes(), 5),
+/// ];
+/// let checksum = full_object_checksum(ChecksumAlgorithm::Crc32, parts).unwrap();
+/// assert_eq!(&crc32fast::hash(b"hello world").to_be_bytes()[..], &checksum[..]);
+/// ```
+pub fn full_object_checksum<C: AsRef<[u8]>>(
+    algorithm: ChecksumAlgorithm,
+    parts: impl IntoIterator<Item = (C, u64)>,
+) -> Result<Bytes, MultipartChecksumError> {
+    let combine: fn(u64, u64, u64) -> u64 = match algorithm {
+        ChecksumAlgorithm::Crc32 => {
+            |crc1, crc2, len2| combine::crc32(crc1 as u32, crc2 as u32, len2).into()
+        }
+        ChecksumAlgorithm::Crc32c => {
+            |crc1, crc2, len2| combine::crc32c(crc1 as u32, crc2 as u32, len2).into()
+        }
+        ChecksumAlgorithm::Crc64Nvme => combine::crc64_nvme,
+        _ => return Err(MultipartChecksumError::not_combinable(algorithm)),
+    };
+    let size = algorithm.checksum_size() as usize;
+
+    // The CRC of no data is zero for all the supported CRCs
+    let mut crc = 0;
+    for (checksum, length) in parts {
+        let checksum = checksum.as_ref();
+        if checksum.len() != size {
+            return Err(MultipartChecksumError::invalid_part_checksum_length(
+                algorithm,
+                checksum.len(),
+            ));
+        }
+        let part_crc = checksum
+            .iter()
+            .fold(0, |crc, byte| crc << 8 | u64::from(*byte));
+        crc = combine(crc, part_crc, length);
+    }
+
+    Ok(Bytes::copy_from_slice(&crc.to_be_bytes()[8 - size..]))
+}
+
+/// The checksum of the concatenated checksums of the parts of an object.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CompositeChecksum {
+    checksum: Bytes,
+    part_count: usize,
+}
+
+impl CompositeChecksum {
+    /// Create a new `CompositeChecksum` from a checksum of part checksums and the number of parts.
+    pub fn new(checksum: impl Into<Bytes>, part_count: usize) -> Self {
+        Self {
+            checksum: checksum.into(),
+            part_count,
+        }
+    }
+
+    /// Calculate the composite checksum of an object from the checksums of its parts, in order.
+    ///
+    /// ```
+    /// use aws_smithy_checksums::multipart::CompositeChecksum;
+    /// use aws_smithy_checksums::ChecksumAlgorithm;
+    ///
+    /// let parts = [
+    ///     crc32fast::hash(b"hello ").to_be_bytes(),
+    ///     crc32fast::hash(b"world").to_be_bytes(),
+    /// ];
+    /// let checksum = CompositeChecksum::from_part_checksums(ChecksumAlgorithm::Crc32, parts);
+    /// assert_eq!(2, checksum.part_count());
+    /// assert_eq!("1Fu2mQ==-2", checksum.to_string());
+    /// ```
+    pub fn from_part_checksums<C: AsRef<[u8]>>(
+        algorithm: ChecksumAlgorithm,
+        part_checksums: impl IntoIterator<Item = C>,
+    ) -> Self {
+        let mut checksum = algorithm.into_impl();
+        let mut part_count = 0;
+        for part_checksum in part_checksums {
+            checksum.update(part_checksum.as_ref());
+            part_count += 1;
+        }
+        Self::new(checksum.finalize(), part_count)
+    }
+
+    /// The checksum of the part checksums
+    pub fn checksum(&self) -> &Bytes {
+        &self.checksum
+    }
+
+    /// The number of parts
+    pub fn part_count(&self) -> usize {
+        self.part_count
+    }
+}
+
+impl fmt::Display for CompositeChecksum {
+    /// Format this checksum as a header value, e.g. `ZAUo/w==-3`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}-{}", base64::encode(&self.checksum), self.part_count)
+    }
+}
+
+impl FromStr for CompositeChecksum {
+    type Err = MultipartChecksumError;
+
+    /// Parse a composite checksum header value, e.g. `ZAUo/w==-3`.
+    fn from_str(value: &str) -> Result<Self, Self::Err> {
+        let invalid = || MultipartChecksumError::invalid_composite_checksum(value);
+        let (checksum, part_count) = value.rsplit_once('-').ok_or_else(invalid)?;
+        let part_count = part_count
+            .parse()
+            .ok()
+            .filter(|count| *count > 0)
+            .ok_or_else(invalid)?;
+        let checksum = base64::decode(checksum).map_err(|_| invalid())?;
+        Ok(Self::new(checksum, part_count))
+    }
+}
+
+/// Return `true` if a checksum header value is a composite checksum, i.e. has a `-<part count>`
+/// suffix.
+pub fn is_composite_checksum(value: &str) -> bool {
+    value.parse::<CompositeChecksum>().is_ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{full_object_checksum, is_composite_checksum, CompositeChecksum};
+    use crate::ChecksumAlgorithm;
+    use bytes::Bytes;
+
+    fn checksum(algorithm: ChecksumAlgorithm, data: &[u8]) -> Bytes {
+        let mut checksum = algorithm.into_impl();
+        checksum.update(data);
+        checksum.finalize()
+    }
+
+    #[test]
+    fn full_object_checksum_matches_checksum_of_whole_object() {
+        let data: Vec<u8> = (0..5000u32).map(|i| (i % 253) as u8).collect();
+        for algorithm in [
+            ChecksumAlgorithm::Crc32,
+            ChecksumAlgorithm::Crc32c,
+            ChecksumAlgorithm::Crc64Nvme,
+        ] {
+            let parts = data
+                .chunks(1024)
+                .map(|part| (checksum(algorithm, part), part.len() as u64));
+            assert_eq!(
+                checksum(algorithm, &data),
+                full_object_checksum(algorithm, parts).unwrap(),
+                "{algorithm:?}"
+            );
+        }
+    }
+
+    #[test]
+    fn full_object_checksum_of_no_parts_is_checksum_of_nothing() {
+        let parts: [(Bytes, u64); 0] = [];
+        assert_eq!(
+            checksum(ChecksumAlgorithm::Crc64Nvme, b""),
+            full_object_checksum(ChecksumAlgorithm::Crc64Nvme, parts).unwrap()
+        );
+    }
+
+    #[test]
+    fn full_object_checksum_errors() {
+        let err = full_object_checksum(ChecksumAlgorithm::Sha256, [(&[0u8; 32][..], 1)])
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("sha256 checksums can't be combined"), "{err}");
+
+        let err = full_object_checksum(ChecksumAlgorithm::Crc32, [(&[0u8; 8][..], 1)])
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("must be 4 bytes long but it was 8"), "{err}");
+    }
+
+    #[test]
+    fn composite_checksum_is_checksum_of_part_checksums() {
+        let parts = [b"first part".as_slice(), b"second part", b"third part"];
+        for algorithm in [ChecksumAlgorithm::Crc32c, ChecksumAlgorithm::Sha256] {
+            let part_checksums: Vec<Bytes> = parts.iter().map(|p| checksum(algorithm, p)).collect();
+            let composite = CompositeChecksum::from_part_checksums(algorithm, &part_checksums);
+            assert_eq!(3, composite.part_count());
+            assert_eq!(
+                &checksum(algorithm, &part_checksums.concat()),
+                composite.checksum()
+            );
+        }
+    }
+
+    #[test]
+    fn composite_checksum_header_value_round_trip() {
+        let composite = CompositeChecksum::new(vec![0x64, 0x05, 0x28, 0xff], 3);
+        assert_eq!("ZAUo/w==-3", composite.to_string());
+        assert_eq!(composite, "ZAUo/w==-3".parse().unwrap());
+    }
+
+    #[test]
+    fn parse_invalid_composite_checksums() {
+        for value in [
+            "ZAUo/w==",
+            "ZAUo/w==-",
+            "ZAUo/w==-0",
+            "ZAUo/w==-x",
+            "ZAU!/w==-3",
+        ] {
+            assert!(!is_composite_checksum(value), "{value}");
+        }
+        let err = "ZAUo/w==".parse::<CompositeChecksum>().unwrap_err();
+        assert_eq!(
+            r#""ZAUo/w==" is not a composite checksum, expected a base64 encoded checksum followed by "-<part count>""#,
+            err.to_string()
+        );
+        assert!(is_composite_checksum("ZAUo/w==-10000"));
+    }
+}
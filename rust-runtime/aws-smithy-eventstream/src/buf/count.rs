@@ -5,7 +5,7 @@
 
 //! A [`Buf`] implementation that counts bytes read.
 
-use bytes::Buf;
+use bytes::{Buf, Bytes};
 
 /// A [`Buf`] implementation that counts bytes read.
 pub(crate) struct CountBuf<'a, B>
@@ -47,12 +47,18 @@ where
         self.count += cnt;
         self.buffer.advance(cnt);
     }
+
+    // Delegate so that an underlying `Bytes` can be sliced instead of copied
+    fn copy_to_bytes(&mut self, len: usize) -> Bytes {
+        self.count += len;
+        self.buffer.copy_to_bytes(len)
+    }
 }
 
 #[cfg(test)]
 mod tests {
     use super::CountBuf;
-    use bytes::Buf;
+    use bytes::{Buf, Bytes};
 
     #[test]
     fn count_no_data_read() {
@@ -86,4 +92,13 @@ mod tests {
         assert_eq!(10, buf.get_i16());
         assert_eq!(6, buf.into_count());
     }
+
+    #[test]
+    fn count_copy_to_bytes() {
+        let mut data = Bytes::from_static(&[0, 0, 0, 5, 0, 10u8]);
+        let mut buf = CountBuf::new(&mut data);
+        assert_eq!(&[0, 0, 0, 5][..], &buf.copy_to_bytes(4)[..]);
+        assert_eq!(10, buf.get_i16());
+        assert_eq!(6, buf.into_count());
+    }
 }
@@ -6,7 +6,7 @@
 //! Utilities for calculating CRC-32 while reading from a [`Buf`] or writing to a [`BufMut`].
 
 use bytes::buf::UninitSlice;
-use bytes::{Buf, BufMut};
+use bytes::{Buf, BufMut, Bytes};
 use crc32fast::Hasher;
 
 /// Implementation of [`Buf`] that calculates a CRC-32 checksum of the data
@@ -54,12 +54,19 @@ where
         self.crc.update(&chunk[0..cnt]);
         self.buffer.advance(cnt);
     }
+
+    // Delegate so that an underlying `Bytes` can be sliced instead of copied
+    fn copy_to_bytes(&mut self, len: usize) -> Bytes {
+        let bytes = self.buffer.copy_to_bytes(len);
+        self.crc.update(&bytes);
+        bytes
+    }
 }
 
 #[cfg(test)]
 mod crc_buf_tests {
     use super::CrcBuf;
-    use bytes::Buf;
+    use bytes::{Buf, Bytes};
 
     #[test]
     fn crc_no_data_read() {
@@ -93,6 +100,20 @@ mod crc_buf_tests {
         assert_eq!(10, buf.get_i16());
         assert_eq!(0x57DC8A56, buf.into_crc());
     }
+
+    #[test]
+    fn copy_to_bytes_slices_bytes() {
+        static DATA: [u8; 6] = [0, 0, 0, 5, 0, 10u8];
+        let mut data = Bytes::from_static(&DATA);
+        let mut buf = CrcBuf::new(&mut data);
+        let copied = buf.copy_to_bytes(6);
+        assert_eq!(
+            DATA.as_ptr(),
+            copied.as_ptr(),
+            "should slice rather than copy"
+        );
+        assert_eq!(0x57DC8A56, buf.into_crc());
+    }
 }
 
 /// Implementation of [`BufMut`] that calculates a CRC-32 checksum of the data
@@ -10,6 +10,7 @@ use std::fmt;
 #[derive(Debug)]
 pub(crate) enum ErrorKind {
     HeadersTooLong,
+    HeadersExceedMaxSize(u32, u32),
     HeaderValueTooLong,
     InvalidHeaderNameLength,
     InvalidHeaderValue,
@@ -18,6 +19,7 @@ pub(crate) enum ErrorKind {
     InvalidMessageLength,
     InvalidUtf8String,
     MessageChecksumMismatch(u32, u32),
+    MessageExceedsMaxSize(u32, u32),
     MessageTooLong,
     PayloadTooLong,
     PreludeChecksumMismatch(u32, u32),
@@ -65,6 +67,16 @@ impl Error {
                 | Marshalling(_)
         )
     }
+
+    /// Returns true if a received message was rejected for exceeding the maximum message size or
+    /// maximum headers size of the [`MessageFrameDecoder`](crate::frame::MessageFrameDecoder)
+    pub fn is_max_size_exceeded(&self) -> bool {
+        use ErrorKind::*;
+        matches!(
+            self.kind,
+            MessageExceedsMaxSize(..) | HeadersExceedMaxSize(..)
+        )
+    }
 }
 
 impl From<ErrorKind> for Error {
@@ -80,6 +92,11 @@ impl fmt::Display for Error {
         use ErrorKind::*;
         match &self.kind {
             HeadersTooLong => write!(f, "headers too long to fit in event stream frame"),
+            HeadersExceedMaxSize(size, max) => write!(
+                f,
+                "message headers length of {} bytes exceeds the maximum of {} bytes",
+                size, max
+            ),
             HeaderValueTooLong => write!(f, "header value too long to fit in event stream frame"),
             InvalidHeaderNameLength => write!(f, "invalid header name length"),
             InvalidHeaderValue => write!(f, "invalid header value"),
@@ -92,6 +109,11 @@ impl fmt::Display for Error {
                 "message checksum 0x{:X} didn't match expected checksum 0x{:X}",
                 actual, expected
             ),
+            MessageExceedsMaxSize(size, max) => write!(
+                f,
+                "message length of {} bytes exceeds the maximum of {} bytes",
+                size, max
+            ),
             MessageTooLong => write!(f, "message too long to fit in event stream frame"),
             PayloadTooLong => write!(f, "message payload too long to fit in event stream frame"),
             PreludeChecksumMismatch(expected, actual) => write!(
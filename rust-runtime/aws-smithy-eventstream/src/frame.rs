@@ -49,6 +49,21 @@ pub trait SignMessage: fmt::Debug {
     fn sign_empty(&mut self) -> Option<Result<Message, SignMessageError>>;
 }
 
+/// An error returned by [`VerifyMessage::verify`] when a message fails verification, e.g.
+/// because its signature doesn't match.
+pub type VerifyMessageError = Box<dyn StdError + Send + Sync + 'static>;
+
+/// Verifies the signatures of received Event Stream messages.
+///
+/// This is the receiving counterpart of [`SignMessage`]: messages are verified in the order they
+/// were received, since each signature incorporates the signature of the previous message.
+pub trait VerifyMessage: fmt::Debug {
+    /// Verifies a signed message and returns the message that it wraps.
+    ///
+    /// Returns `Ok(None)` if `message` is the signed empty message that ends the stream.
+    fn verify(&mut self, message: &Message) -> Result<Option<Message>, VerifyMessageError>;
+}
+
 /// A sender that gets placed in the request config to wire up an event stream signer after signing.
 #[derive(Debug)]
 #[non_exhaustive]
@@ -616,12 +631,37 @@ pub enum DecodedFrame {
     Complete(Message),
 }
 
+/// Default maximum length of a message frame, including its prelude and checksums (16 MiB)
+pub const DEFAULT_MAX_MESSAGE_SIZE: u32 = 16 * 1024 * 1024;
+
+/// Default maximum length of the headers of a message frame (128 KiB)
+pub const DEFAULT_MAX_HEADERS_SIZE: u32 = 128 * 1024;
+
 /// Streaming decoder for decoding a [`Message`] from a stream.
+///
+/// The length of each frame is checked against the decoder's maximum message and headers sizes
+/// as soon as its prelude is read, so that a peer can't make a receiver buffer an arbitrarily
+/// large frame. Header values and payloads are sliced out of the buffer without copying when
+/// they're contiguous in a buffer of [`Bytes`](bytes::Bytes), e.g. when a frame lies within a
+/// single chunk of a `SegmentedBuf<Bytes>`.
 #[non_exhaustive]
-#[derive(Default, Debug)]
+#[derive(Debug)]
 pub struct MessageFrameDecoder {
     prelude: [u8; PRELUDE_LENGTH_BYTES_USIZE],
     prelude_read: bool,
+    max_message_size: u32,
+    max_headers_size: u32,
+}
+
+impl Default for MessageFrameDecoder {
+    fn default() -> Self {
+        Self {
+            prelude: [0u8; PRELUDE_LENGTH_BYTES_USIZE],
+            prelude_read: false,
+            max_message_size: DEFAULT_MAX_MESSAGE_SIZE,
+            max_headers_size: DEFAULT_MAX_HEADERS_SIZE,
+        }
+    }
 }
 
 impl MessageFrameDecoder {
@@ -630,6 +670,30 @@ impl MessageFrameDecoder {
         Default::default()
     }
 
+    /// Sets the maximum length in bytes of a message frame, including its prelude and checksums.
+    ///
+    /// Defaults to [`DEFAULT_MAX_MESSAGE_SIZE`].
+    pub fn set_max_message_size(&mut self, max_message_size: u32) {
+        self.max_message_size = max_message_size;
+    }
+
+    /// Returns the maximum length in bytes of a message frame.
+    pub fn max_message_size(&self) -> u32 {
+        self.max_message_size
+    }
+
+    /// Sets the maximum length in bytes of the headers of a message frame.
+    ///
+    /// Defaults to [`DEFAULT_MAX_HEADERS_SIZE`].
+    pub fn set_max_headers_size(&mut self, max_headers_size: u32) {
+        self.max_headers_size = max_headers_size;
+    }
+
+    /// Returns the maximum length in bytes of the headers of a message frame.
+    pub fn max_headers_size(&self) -> u32 {
+        self.max_headers_size
+    }
+
     /// Determines if the `buffer` has enough data in it to read a full frame.
     /// Returns `Ok(None)` if there's not enough data, or `Some(remaining)` where
     /// `remaining` is the number of bytes after the prelude that belong to the
@@ -650,6 +714,24 @@ impl MessageFrameDecoder {
         Ok(None)
     }
 
+    /// Validates the prelude checksum and checks the frame lengths against the limits.
+    fn check_prelude(&self) -> Result<(), Error> {
+        let mut prelude = &self.prelude[..];
+        let (total_len, header_len) = (prelude.get_u32(), prelude.get_u32());
+        let expected_crc = crc32fast::hash(&self.prelude[..2 * size_of::<u32>()]);
+        let prelude_crc = prelude.get_u32();
+        if expected_crc != prelude_crc {
+            return Err(ErrorKind::PreludeChecksumMismatch(expected_crc, prelude_crc).into());
+        }
+        if total_len > self.max_message_size {
+            return Err(ErrorKind::MessageExceedsMaxSize(total_len, self.max_message_size).into());
+        }
+        if header_len > self.max_headers_size {
+            return Err(ErrorKind::HeadersExceedMaxSize(header_len, self.max_headers_size).into());
+        }
+        Ok(())
+    }
+
     /// Resets the decoder.
     fn reset(&mut self) {
         self.prelude_read = false;
@@ -664,10 +746,17 @@ impl MessageFrameDecoder {
     /// position. The state from the reading of the prelude is stored in the decoder so that
     /// the next call will be able to decode the entire message, even though the prelude
     /// is no longer available in the `Buf`.
+    ///
+    /// Returns an error as soon as the prelude has been read if the frame exceeds the maximum
+    /// message or headers size.
     pub fn decode_frame<B: Buf>(&mut self, mut buffer: B) -> Result<DecodedFrame, Error> {
         if !self.prelude_read && buffer.remaining() >= PRELUDE_LENGTH_BYTES_USIZE {
             buffer.copy_to_slice(&mut self.prelude);
             self.prelude_read = true;
+            if let Err(err) = self.check_prelude() {
+                self.reset();
+                return Err(err);
+            }
         }
 
         if let Some(remaining_len) = self.remaining_bytes_if_frame_available(&buffer)? {
@@ -684,6 +773,7 @@ impl MessageFrameDecoder {
 #[cfg(test)]
 mod message_frame_decoder_tests {
     use super::{DecodedFrame, MessageFrameDecoder};
+    use crate::error::ErrorKind;
     use crate::frame::read_message_from;
     use bytes::Bytes;
     use bytes_utils::SegmentedBuf;
@@ -748,6 +838,74 @@ mod message_frame_decoder_tests {
             multiple_streaming_messages_chunk_size(chunk_size);
         }
     }
+
+    #[test]
+    fn payload_within_one_chunk_is_not_copied() {
+        let message = include_bytes!("../test_data/valid_with_all_headers_and_payload");
+        let expected = read_message_from(&mut Bytes::from_static(message)).unwrap();
+        let payload_offset = message.len() - 4 - expected.payload().len();
+
+        let mut decoder = MessageFrameDecoder::new();
+        let mut segmented = SegmentedBuf::new();
+        segmented.push(Bytes::from_static(message));
+        match decoder.decode_frame(&mut segmented).unwrap() {
+            DecodedFrame::Incomplete => panic!("frame should be complete"),
+            DecodedFrame::Complete(actual) => {
+                assert_eq!(expected, actual);
+                assert_eq!(
+                    message[payload_offset..].as_ptr(),
+                    actual.payload().as_ptr(),
+                    "the payload should be a slice of the chunk"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn message_exceeding_max_size_is_rejected_after_prelude() {
+        let message = include_bytes!("../test_data/valid_with_all_headers_and_payload");
+
+        let mut decoder = MessageFrameDecoder::new();
+        decoder.set_max_message_size(message.len() as u32 - 1);
+        let err = decoder.decode_frame(&mut &message[..12]).unwrap_err();
+        assert!(err.is_max_size_exceeded());
+        assert!(matches!(
+            err.kind(),
+            ErrorKind::MessageExceedsMaxSize(size, _) if *size as usize == message.len()
+        ));
+
+        let mut decoder = MessageFrameDecoder::new();
+        decoder.set_max_message_size(message.len() as u32);
+        assert!(matches!(
+            decoder.decode_frame(&mut &message[..]).unwrap(),
+            DecodedFrame::Complete(_)
+        ));
+    }
+
+    #[test]
+    fn headers_exceeding_max_size_are_rejected_after_prelude() {
+        let message = include_bytes!("../test_data/valid_with_all_headers_and_payload");
+
+        let mut decoder = MessageFrameDecoder::new();
+        decoder.set_max_headers_size(1);
+        let err = decoder.decode_frame(&mut &message[..12]).unwrap_err();
+        assert!(err.is_max_size_exceeded());
+        assert!(matches!(err.kind(), ErrorKind::HeadersExceedMaxSize(_, 1)));
+        assert!(err.to_string().contains("exceeds the maximum of 1 bytes"));
+    }
+
+    #[test]
+    fn corrupt_prelude_is_rejected_before_size_check() {
+        let mut message =
+            include_bytes!("../test_data/valid_with_all_headers_and_payload").to_vec();
+        // Claim a huge message length
+        message[0] = 0xff;
+
+        let mut decoder = MessageFrameDecoder::new();
+        let err = decoder.decode_frame(&mut &message[..]).unwrap_err();
+        assert!(!err.is_max_size_exceeded());
+        assert!(matches!(err.kind(), ErrorKind::PreludeChecksumMismatch(..)));
+    }
 }
 
 #[cfg(test)]
@@ -0,0 +1,227 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! Python wrappers for event streams.
+//!
+//! Operations with `@streaming` unions receive events through an [EventStreamReceiver], exposed
+//! to Python as an async iterator of modeled events, and send events through an
+//! [EventStreamSender], created from a Python async iterable such as an async generator.
+//!
+//! Modeled event stream errors cross the language boundary as Python exceptions: an error
+//! message received from the client is raised from `__anext__()`, and an exception raised by an
+//! outbound async generator is sent to the client as an error event, terminating the stream.
+
+use std::{
+    convert::Infallible,
+    fmt,
+    pin::Pin,
+    sync::{Arc, PoisonError},
+    task::{Context, Poll},
+};
+
+use aws_smithy_http::event_stream::Receiver;
+use aws_smithy_runtime_api::client::result::SdkError;
+use aws_smithy_types::{error::display::DisplayErrorContext, event_stream::RawMessage};
+use futures::{stream::BoxStream, Stream, StreamExt};
+use pyo3::{
+    exceptions::{PyRuntimeError, PyStopAsyncIteration},
+    prelude::*,
+};
+use tokio::sync::Mutex;
+
+use crate::types::stream_from_async_iter;
+
+/// Python Wrapper for [aws_smithy_http::event_stream::Receiver].
+///
+/// The receiver is exposed to Python as an async iterator yielding modeled events:
+///
+/// ```python
+///     async for event in input.events:
+///         print(event)
+/// ```
+///
+/// A modeled error sent by the client is raised as a Python exception and terminates the stream,
+/// while the end of the stream raises `StopAsyncIteration` as usual.
+///
+/// Cancelling a pending `__anext__()`, for example because the task awaiting it has been
+/// cancelled, or calling `aclose()` drops the underlying [Receiver], together with its
+/// `MessageFrameDecoder` and the request body, and ends the iteration.
+///
+/// The original Rust [Receiver] is type-erased and wrapped inside a `Arc<Mutex>` to allow the type
+/// to be [Clone] (required by PyO3) and to allow internal mutability, required to receive the next
+/// event.
+#[pyclass]
+#[derive(Clone)]
+pub struct EventStreamReceiver(Arc<Mutex<Option<BoxStream<'static, PyResult<PyObject>>>>>);
+
+impl fmt::Debug for EventStreamReceiver {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("EventStreamReceiver")
+            .finish_non_exhaustive()
+    }
+}
+
+impl EventStreamReceiver {
+    /// Wrap `receiver` so that its events can be consumed from Python.
+    ///
+    /// Events are converted into Python objects with [IntoPy], modeled errors are raised as the
+    /// Python exception they convert into.
+    pub fn new<T, E>(receiver: Receiver<T, E>) -> Self
+    where
+        T: IntoPy<PyObject> + 'static,
+        E: Into<PyErr> + Send + 'static,
+    {
+        let events = futures::stream::unfold(Some(receiver), |receiver| async move {
+            let mut receiver = receiver?;
+            match receiver.recv().await {
+                Ok(Some(event)) => {
+                    Some((Ok(Python::with_gil(|py| event.into_py(py))), Some(receiver)))
+                }
+                Ok(None) => None,
+                // The receiver is terminated after an error, stop polling it.
+                Err(err) => Some((Err(receiver_error(err)), None)),
+            }
+        });
+        Self(Arc::new(Mutex::new(Some(events.boxed()))))
+    }
+}
+
+/// Convert an error returned by a [Receiver] into a Python exception.
+fn receiver_error<E: Into<PyErr>>(err: SdkError<E, RawMessage>) -> PyErr {
+    match err {
+        SdkError::ServiceError(context) => context.into_err().into(),
+        // No other variant carries a modeled error, erase its type to render the error chain.
+        err => {
+            let err = err.map_service_error(|_| -> Infallible {
+                unreachable!("service errors are handled above")
+            });
+            PyRuntimeError::new_err(DisplayErrorContext(&err).to_string())
+        }
+    }
+}
+
+#[pymethods]
+impl EventStreamReceiver {
+    /// Allow to asyncronously iterate over the events.
+    ///
+    /// More info: `<https://docs.python.org/3/reference/datamodel.html#object.__aiter__.>`
+    pub fn __aiter__(slf: PyRef<Self>) -> PyRef<Self> {
+        slf
+    }
+
+    /// Return an awaitable resulting in the next event or raise a StopAsyncIteration
+    /// exception when the stream is over.
+    ///
+    /// The stream is taken out of the wrapper while waiting for the next event and it is put back
+    /// only once an event is received: if the awaitable is cancelled, the stream is dropped and
+    /// the iteration ends.
+    ///
+    /// See [ByteStream::__anext__](crate::types::ByteStream::__anext__) for why the return type
+    /// is wrapped in an `Option`.
+    pub fn __anext__(slf: PyRefMut<Self>) -> PyResult<Option<PyObject>> {
+        let events = slf.0.clone();
+        let fut = pyo3_asyncio::tokio::future_into_py(slf.py(), async move {
+            let mut guard = events.lock().await;
+            let Some(mut stream) = guard.take() else {
+                return Err(PyStopAsyncIteration::new_err("stream exhausted"));
+            };
+            match stream.next().await {
+                Some(Ok(event)) => {
+                    *guard = Some(stream);
+                    Ok(event)
+                }
+                Some(Err(err)) => Err(err),
+                None => Err(PyStopAsyncIteration::new_err("stream exhausted")),
+            }
+        })?;
+        Ok(Some(fut.into()))
+    }
+
+    /// Stop receiving events, dropping the underlying stream.
+    ///
+    /// :rtype typing.Awaitable[None]:
+    pub fn aclose<'p>(&self, py: Python<'p>) -> PyResult<&'p PyAny> {
+        let events = self.0.clone();
+        pyo3_asyncio::tokio::future_into_py(py, async move {
+            events.lock().await.take();
+            Ok(())
+        })
+    }
+}
+
+/// Python Wrapper for [aws_smithy_http::event_stream::EventStreamSender].
+///
+/// It can be extracted from any Python async iterable, usually an async generator, yielding
+/// objects that can be extracted into the modeled event `T`:
+///
+/// ```python
+///     async def events():
+///         yield Message(text="hello")
+///         raise ThrottlingError(message="slow down")
+///
+///     return ChatOutput(events=events())
+/// ```
+///
+/// The iterable is consumed lazily on the event loop it is extracted from, one event at a time.
+/// An exception raised by the iterable, including a failure to extract an event, is converted
+/// into the modeled error `E` and terminates the stream.
+pub struct EventStreamSender<T, E>(aws_smithy_http::event_stream::EventStreamSender<T, E>);
+
+impl<T, E> fmt::Debug for EventStreamSender<T, E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("EventStreamSender").field(&self.0).finish()
+    }
+}
+
+impl<T, E> EventStreamSender<T, E> {
+    /// Consumes the wrapper and returns the Rust [EventStreamSender](aws_smithy_http::event_stream::EventStreamSender).
+    pub fn into_inner(self) -> aws_smithy_http::event_stream::EventStreamSender<T, E> {
+        self.0
+    }
+}
+
+impl<T, E> From<EventStreamSender<T, E>>
+    for aws_smithy_http::event_stream::EventStreamSender<T, E>
+{
+    fn from(sender: EventStreamSender<T, E>) -> Self {
+        sender.0
+    }
+}
+
+impl<T, E> FromPyObject<'_> for EventStreamSender<T, E>
+where
+    T: for<'a> FromPyObject<'a> + Send + 'static,
+    E: From<PyErr> + Send + 'static,
+{
+    fn extract(obj: &PyAny) -> PyResult<Self> {
+        let py = obj.py();
+        let aiter = obj.call_method0("__aiter__")?.into_py(py);
+        let locals = pyo3_asyncio::tokio::get_current_locals(py)?;
+        let events = stream_from_async_iter(aiter, locals, |event| event.extract::<T>())
+            .map(|event| event.map_err(E::from))
+            .boxed();
+        Ok(Self(SyncStream(std::sync::Mutex::new(events)).into()))
+    }
+}
+
+/// [EventStreamSender](aws_smithy_http::event_stream::EventStreamSender) requires a `Sync`
+/// stream, while the futures awaiting Python are only `Send`.
+///
+/// The stream is only ever polled through a mutable reference, so the `Mutex` is never locked
+/// and only used to make the stream `Sync`.
+struct SyncStream<S>(std::sync::Mutex<S>);
+
+impl<S: Stream + Unpin> Stream for SyncStream<S> {
+    type Item = S::Item;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let stream = self
+            .get_mut()
+            .0
+            .get_mut()
+            .unwrap_or_else(PoisonError::into_inner);
+        stream.poll_next_unpin(cx)
+    }
+}
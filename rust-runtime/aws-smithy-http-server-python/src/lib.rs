@@ -19,6 +19,7 @@
 
 pub mod context;
 mod error;
+pub mod event_stream;
 pub mod lambda;
 pub mod logging;
 pub mod middleware;
@@ -144,6 +144,93 @@ async def handler(bytestream):
     Ok(())
 }
 
+#[pyo3_asyncio::tokio::test]
+async fn streaming_async_generator_into_rust() -> PyResult<()> {
+    let bytestream = Python::with_gil(|py| {
+        let module = PyModule::from_code(
+            py,
+            r#"
+async def body():
+    yield b"hello"
+    yield bytearray(b" ")
+    yield b"world"
+"#,
+            "",
+            "",
+        )?;
+        ByteStream::from_async_iter(py, module.getattr("body")?.call0()?)
+    })?;
+
+    let chunks = bytestream
+        .map(|chunk| chunk.unwrap())
+        .collect::<Vec<_>>()
+        .await;
+    assert_eq!(chunks, vec!["hello", " ", "world"]);
+
+    Ok(())
+}
+
+#[pyo3_asyncio::tokio::test]
+async fn streaming_async_generator_is_pulled_lazily() -> PyResult<()> {
+    let (module, mut bytestream) = Python::with_gil(|py| {
+        let module = PyModule::from_code(
+            py,
+            r#"
+produced = 0
+
+async def body():
+    global produced
+    for chunk in [b"a", b"b", b"c"]:
+        produced += 1
+        yield chunk
+"#,
+            "",
+            "",
+        )?;
+        let bytestream = ByteStream::from_async_iter(py, module.getattr("body")?.call0()?)?;
+        Ok::<_, PyErr>((module.to_object(py), bytestream))
+    })?;
+    let produced = || {
+        Python::with_gil(|py| {
+            module
+                .getattr(py, "produced")
+                .and_then(|produced| produced.extract::<usize>(py))
+        })
+    };
+
+    assert_eq!(produced()?, 0);
+    assert_eq!(bytestream.next().await.unwrap().unwrap(), "a");
+    assert_eq!(produced()?, 1);
+    assert_eq!(bytestream.next().await.unwrap().unwrap(), "b");
+    assert_eq!(produced()?, 2);
+
+    Ok(())
+}
+
+#[pyo3_asyncio::tokio::test]
+async fn streaming_async_generator_propagates_errors() -> PyResult<()> {
+    let mut bytestream = Python::with_gil(|py| {
+        let module = PyModule::from_code(
+            py,
+            r#"
+async def body():
+    yield b"hello"
+    yield "not bytes"
+"#,
+            "",
+            "",
+        )?;
+        ByteStream::from_async_iter(py, module.getattr("body")?.call0()?)
+    })?;
+
+    assert_eq!(bytestream.next().await.unwrap().unwrap(), "hello");
+    let err = bytestream.next().await.unwrap().unwrap_err();
+    assert!(format!("{err:?}").contains("expected 'bytes' or 'bytearray'"));
+    assert!(bytestream.next().await.is_none());
+
+    Ok(())
+}
+
 fn streaming_bytestream_from_vec(chunks: Vec<&'static str>) -> ByteStream {
     let stream = stream::iter(chunks.into_iter().map(Ok::<_, io::Error>));
     let body = Body::wrap_stream(stream);
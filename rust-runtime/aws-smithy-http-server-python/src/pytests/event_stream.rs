@@ -0,0 +1,303 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+use std::{fmt, io};
+
+use aws_smithy_eventstream::{
+    error::Error as EventStreamError,
+    frame::{
+        read_message_from, write_message_to, MarshallMessage, NoOpSigner, UnmarshallMessage,
+        UnmarshalledMessage,
+    },
+};
+use aws_smithy_types::{body::SdkBody, event_stream::Message};
+use bytes::Bytes;
+use futures::StreamExt;
+use futures_util::stream;
+use hyper::Body;
+use pyo3::{exceptions::PyValueError, prelude::*, py_run};
+
+use aws_smithy_http::event_stream::Receiver;
+use aws_smithy_http_server_python::event_stream::{EventStreamReceiver, EventStreamSender};
+
+#[pyo3_asyncio::tokio::test]
+fn receiving_events_on_python() -> PyResult<()> {
+    let receiver = receiver_from_body(body_from_vec(vec!["hello", "world"]));
+    Python::with_gil(|py| {
+        let receiver = receiver.into_py(py);
+        py_run!(
+            py,
+            receiver,
+            r#"
+import asyncio
+
+async def main(receiver):
+    events = []
+    async for event in receiver:
+        events.append(event)
+    assert events == ["hello", "world"]
+
+asyncio.run(main(receiver))
+"#
+        );
+        Ok(())
+    })
+}
+
+#[pyo3_asyncio::tokio::test]
+fn receiving_modeled_error_on_python() -> PyResult<()> {
+    let receiver = receiver_from_body(body_from_vec(vec!["hello", "error: boom", "world"]));
+    Python::with_gil(|py| {
+        let receiver = receiver.into_py(py);
+        py_run!(
+            py,
+            receiver,
+            r#"
+import asyncio
+
+async def main(receiver):
+    assert await receiver.__anext__() == "hello"
+    try:
+        await receiver.__anext__()
+        assert False, "modeled error should be raised"
+    except ValueError as err:
+        assert str(err) == "boom"
+
+    # The stream is terminated after an error.
+    try:
+        await receiver.__anext__()
+        assert False, "iteration should stop by now"
+    except StopAsyncIteration:
+        pass
+
+asyncio.run(main(receiver))
+"#
+        );
+        Ok(())
+    })
+}
+
+#[pyo3_asyncio::tokio::test]
+async fn cancelling_receiver_drops_the_body() -> PyResult<()> {
+    let (mut tx, body) = Body::channel();
+    tx.send_data(encode_message("hello")).await.unwrap();
+    let receiver = receiver_from_body(body);
+    Python::with_gil(|py| {
+        let module = PyModule::from_code(
+            py,
+            r#"
+import asyncio
+
+async def main(receiver):
+    assert await receiver.__anext__() == "hello"
+
+    # No more data is sent, so the next event is pending until cancelled.
+    task = asyncio.ensure_future(receiver.__anext__())
+    await asyncio.sleep(0.1)
+    task.cancel()
+    try:
+        await task
+        assert False, "task should be cancelled"
+    except asyncio.CancelledError:
+        pass
+
+    try:
+        await receiver.__anext__()
+        assert False, "iteration should stop after a cancellation"
+    except StopAsyncIteration:
+        pass
+"#,
+            "",
+            "",
+        )?;
+        let main = module.getattr("main")?.call1((receiver,))?;
+        pyo3_asyncio::tokio::into_future(main)
+    })?
+    .await?;
+
+    assert!(tx.send_data(encode_message("world")).await.is_err());
+    Ok(())
+}
+
+#[pyo3_asyncio::tokio::test]
+async fn closing_receiver_drops_the_body() -> PyResult<()> {
+    let (mut tx, body) = Body::channel();
+    let receiver = receiver_from_body(body);
+    Python::with_gil(|py| {
+        let module = PyModule::from_code(
+            py,
+            r#"
+import asyncio
+
+async def main(receiver):
+    await receiver.aclose()
+    try:
+        await receiver.__anext__()
+        assert False, "iteration should stop after closing"
+    except StopAsyncIteration:
+        pass
+"#,
+            "",
+            "",
+        )?;
+        let main = module.getattr("main")?.call1((receiver,))?;
+        pyo3_asyncio::tokio::into_future(main)
+    })?
+    .await?;
+
+    assert!(tx.send_data(encode_message("hello")).await.is_err());
+    Ok(())
+}
+
+#[pyo3_asyncio::tokio::test]
+async fn sending_events_from_python_async_generator() -> PyResult<()> {
+    let sender = Python::with_gil(|py| {
+        let module = PyModule::from_code(
+            py,
+            r#"
+async def events():
+    yield "hello"
+    yield "world"
+    raise ValueError("boom")
+"#,
+            "",
+            "",
+        )?;
+        module
+            .getattr("events")?
+            .call0()?
+            .extract::<EventStreamSender<String, TestError>>()
+    })?;
+
+    let messages = sender
+        .into_inner()
+        .into_body_stream(Marshaller, ErrorMarshaller, NoOpSigner {})
+        .map(|frame| {
+            let message = read_message_from(frame.unwrap()).unwrap();
+            String::from_utf8(message.payload().to_vec()).unwrap()
+        })
+        .collect::<Vec<_>>()
+        .await;
+    assert_eq!(messages, vec!["hello", "world", "error: boom"]);
+
+    Ok(())
+}
+
+#[pyo3_asyncio::tokio::test]
+async fn sending_unexpected_event_type_from_python() -> PyResult<()> {
+    let sender = Python::with_gil(|py| {
+        let module = PyModule::from_code(
+            py,
+            r#"
+async def events():
+    yield "hello"
+    yield 42
+    yield "unreachable"
+"#,
+            "",
+            "",
+        )?;
+        module
+            .getattr("events")?
+            .call0()?
+            .extract::<EventStreamSender<String, TestError>>()
+    })?;
+
+    let messages = sender
+        .into_inner()
+        .into_body_stream(Marshaller, ErrorMarshaller, NoOpSigner {})
+        .map(|frame| {
+            let message = read_message_from(frame.unwrap()).unwrap();
+            String::from_utf8(message.payload().to_vec()).unwrap()
+        })
+        .collect::<Vec<_>>()
+        .await;
+    assert_eq!(messages.len(), 2);
+    assert_eq!(messages[0], "hello");
+    assert!(messages[1].starts_with("error: "));
+
+    Ok(())
+}
+
+#[derive(Debug)]
+struct TestError(String);
+
+impl fmt::Display for TestError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for TestError {}
+
+impl From<TestError> for PyErr {
+    fn from(err: TestError) -> Self {
+        PyValueError::new_err(err.0)
+    }
+}
+
+impl From<PyErr> for TestError {
+    fn from(err: PyErr) -> Self {
+        Python::with_gil(|py| TestError(err.value(py).to_string()))
+    }
+}
+
+#[derive(Debug)]
+struct Unmarshaller;
+
+impl UnmarshallMessage for Unmarshaller {
+    type Output = String;
+    type Error = TestError;
+
+    fn unmarshall(
+        &self,
+        message: &Message,
+    ) -> Result<UnmarshalledMessage<Self::Output, Self::Error>, EventStreamError> {
+        let payload = String::from_utf8(message.payload().to_vec()).unwrap();
+        Ok(match payload.strip_prefix("error: ") {
+            Some(err) => UnmarshalledMessage::Error(TestError(err.into())),
+            None => UnmarshalledMessage::Event(payload),
+        })
+    }
+}
+
+#[derive(Debug)]
+struct Marshaller;
+
+impl MarshallMessage for Marshaller {
+    type Input = String;
+
+    fn marshall(&self, input: Self::Input) -> Result<Message, EventStreamError> {
+        Ok(Message::new(input))
+    }
+}
+
+#[derive(Debug)]
+struct ErrorMarshaller;
+
+impl MarshallMessage for ErrorMarshaller {
+    type Input = TestError;
+
+    fn marshall(&self, input: Self::Input) -> Result<Message, EventStreamError> {
+        Ok(Message::new(format!("error: {}", input.0)))
+    }
+}
+
+fn encode_message(payload: &str) -> Bytes {
+    let mut buffer = Vec::new();
+    write_message_to(&Message::new(payload.to_owned()), &mut buffer).unwrap();
+    buffer.into()
+}
+
+fn body_from_vec(payloads: Vec<&'static str>) -> Body {
+    let frames = payloads
+        .into_iter()
+        .map(|payload| Ok::<_, io::Error>(encode_message(payload)));
+    Body::wrap_stream(stream::iter(frames))
+}
+
+fn receiver_from_body(body: Body) -> EventStreamReceiver {
+    EventStreamReceiver::new(Receiver::new(Unmarshaller, SdkBody::from_body_0_4(body)))
+}
@@ -9,3 +9,4 @@ async fn main() -> pyo3::PyResult<()> {
 }
 
 mod bytestream;
+mod event_stream;
@@ -29,7 +29,9 @@ use pyo3::{
     exceptions::{PyRuntimeError, PyStopAsyncIteration, PyTypeError},
     iter::IterNextOutput,
     prelude::*,
+    types::{PyByteArray, PyBytes},
 };
+use pyo3_asyncio::TaskLocals;
 use tokio::{runtime::Handle, sync::Mutex};
 
 use crate::PyError;
@@ -332,6 +334,20 @@ impl Deref for DateTime {
 /// effectively maintaining the asyncronous behavior that Rust exposes, while the sync one is blocking the Tokio runtime to be able
 /// to await one chunk at a time.
 ///
+/// ### Example of streaming a response from an async generator:
+///
+/// ```python
+///     async def body():
+///         async for chunk in upstream:
+///             yield chunk
+///
+///     return GetObjectOutput(body=ByteStream.from_async_iter(body()))
+/// ```
+///
+/// A `ByteStream` created from an async iterable is pulled lazily: the next chunk is requested from Python only when
+/// the consumer (for example Hyper writing the response to the socket) asks for it, so slow clients apply backpressure
+/// to the Python generator and at most one chunk is buffered at a time.
+///
 /// The original Rust [ByteStream](aws_smithy_types::byte_stream::ByteStream) is wrapped inside a `Arc<Mutex>` to allow the type to be
 /// [Clone] (required by PyO3) and to allow internal mutability, required to fetch the next chunk of data.
 ///
@@ -366,6 +382,58 @@ async fn yield_data_chunk(
         .map_err(|e| PyRuntimeError::new_err(e.to_string()))
 }
 
+/// Build a pull-based stream out of a Python async iterator, converting every yielded item
+/// with `convert`.
+///
+/// Every poll of the returned stream awaits a single `__anext__()` on the Python event loop
+/// identified by `locals`, so no item is requested before the previous one has been consumed.
+pub(crate) fn stream_from_async_iter<T, F>(
+    aiter: PyObject,
+    locals: TaskLocals,
+    convert: F,
+) -> impl futures::stream::Stream<Item = PyResult<T>> + Send + 'static
+where
+    T: Send + 'static,
+    F: Fn(&PyAny) -> PyResult<T> + Send + 'static,
+{
+    futures::stream::unfold(Some((aiter, locals, convert)), |state| async move {
+        let (aiter, locals, convert) = state?;
+        let next = Python::with_gil(|py| {
+            let awaitable = aiter.call_method0(py, "__anext__")?;
+            pyo3_asyncio::into_future_with_locals(&locals, awaitable.as_ref(py))
+        });
+        let item = match next {
+            Ok(next) => next.await,
+            Err(err) => Err(err),
+        };
+        let item = Python::with_gil(|py| match item {
+            Ok(item) => convert(item.as_ref(py)).map(Some),
+            Err(err) if err.is_instance_of::<PyStopAsyncIteration>(py) => Ok(None),
+            Err(err) => Err(err),
+        });
+        match item {
+            Ok(Some(item)) => Some((Ok(item), Some((aiter, locals, convert)))),
+            Ok(None) => None,
+            // Terminate the stream after reporting the error.
+            Err(err) => Some((Err(err), None)),
+        }
+    })
+}
+
+/// Convert a chunk yielded by Python into [Bytes].
+fn chunk_to_bytes(chunk: &PyAny) -> PyResult<Bytes> {
+    if let Ok(bytes) = chunk.downcast::<PyBytes>() {
+        Ok(Bytes::copy_from_slice(bytes.as_bytes()))
+    } else if let Ok(bytearray) = chunk.downcast::<PyByteArray>() {
+        Ok(Bytes::from(bytearray.to_vec()))
+    } else {
+        Err(PyTypeError::new_err(format!(
+            "async iterator yielded '{}', expected 'bytes' or 'bytearray'",
+            chunk.get_type().name()?
+        )))
+    }
+}
+
 impl ByteStream {
     /// Construct a new [`ByteStream`](aws_smithy_types::byte_stream::ByteStream) from a
     /// [`SdkBody`](aws_smithy_types::body::SdkBody).
@@ -430,6 +498,24 @@ impl ByteStream {
         })
     }
 
+    /// Create a new [ByteStream](aws_smithy_types::byte_stream::ByteStream) from a Python async
+    /// iterable, for example an async generator yielding `bytes`.
+    ///
+    /// The iterable is consumed lazily on the event loop this method is called from, one chunk
+    /// at a time, which allows to stream arbitrarily large payloads without buffering them.
+    ///
+    /// :param iterable typing.AsyncIterable[bytes]:
+    /// :rtype ByteStream:
+    #[staticmethod]
+    pub fn from_async_iter(py: Python, iterable: &PyAny) -> PyResult<Self> {
+        let aiter = iterable.call_method0("__aiter__")?.into_py(py);
+        let locals = pyo3_asyncio::tokio::get_current_locals(py)?;
+        let body = hyper::Body::wrap_stream(stream_from_async_iter(aiter, locals, chunk_to_bytes));
+        Ok(Self::new(aws_smithy_types::body::SdkBody::from_body_0_4(
+            body,
+        )))
+    }
+
     /// Allow to syncronously iterate over the stream.
     ///
     /// More info: `<https://docs.python.org/3/reference/datamodel.html#object.__iter__.>`
@@ -0,0 +1,136 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! Typescript error definition.
+
+use aws_smithy_http_server::{
+    body::{to_boxed, BoxBody},
+    protocol::{
+        aws_json_10::AwsJson1_0, aws_json_11::AwsJson1_1, rest_json_1::RestJson1, rest_xml::RestXml,
+    },
+    response::IntoResponse,
+};
+use aws_smithy_types::date_time::{ConversionError, DateTimeParseError};
+use thiserror::Error;
+
+/// Typescript error that implements foreign errors.
+#[derive(Error, Debug)]
+pub enum TsError {
+    /// Implements `From<aws_smithy_types::date_time::ConversionError>`.
+    #[error("DateTimeConversion: {0}")]
+    DateTimeConversion(#[from] ConversionError),
+    /// Implements `From<aws_smithy_types::date_time::DateTimeParseError>`.
+    #[error("DateTimeParse: {0}")]
+    DateTimeParse(#[from] DateTimeParseError),
+    /// Raised when a streaming body cannot be read.
+    #[error("ByteStream: {0}")]
+    ByteStream(#[from] aws_smithy_types::byte_stream::error::Error),
+}
+
+impl From<TsError> for napi::Error {
+    fn from(other: TsError) -> napi::Error {
+        napi::Error::from_reason(other.to_string())
+    }
+}
+
+/// Error raised by a Typescript middleware.
+///
+/// A middleware that throws (or returns a rejected `Promise`) is turned into this error, which
+/// implements protocol specific capabilities to build a [aws_smithy_http_server::response::Response]
+/// from it. Middlewares that want to control the status code should resolve to a
+/// [TsResponse](crate::TsResponse) instead.
+#[derive(Debug, Clone)]
+pub struct TsMiddlewareException {
+    message: String,
+    status_code: u16,
+}
+
+impl TsMiddlewareException {
+    /// Create a new [TsMiddlewareException].
+    pub fn new(message: String, status_code: Option<u16>) -> Self {
+        Self {
+            message,
+            status_code: status_code.unwrap_or(500),
+        }
+    }
+}
+
+impl From<napi::Error> for TsMiddlewareException {
+    fn from(other: napi::Error) -> Self {
+        Self::new(other.reason, None)
+    }
+}
+
+impl IntoResponse<RestJson1> for TsMiddlewareException {
+    fn into_response(self) -> http::Response<BoxBody> {
+        http::Response::builder()
+            .status(self.status_code)
+            .header("Content-Type", "application/json")
+            .header("X-Amzn-Errortype", "MiddlewareException")
+            .body(to_boxed(self.json_body()))
+            .expect("invalid HTTP response for `MiddlewareException`; please file a bug report under https://github.com/smithy-lang/smithy-rs/issues")
+    }
+}
+
+impl IntoResponse<RestXml> for TsMiddlewareException {
+    fn into_response(self) -> http::Response<BoxBody> {
+        http::Response::builder()
+            .status(self.status_code)
+            .header("Content-Type", "application/xml")
+            .body(to_boxed(self.xml_body()))
+            .expect("invalid HTTP response for `MiddlewareException`; please file a bug report under https://github.com/smithy-lang/smithy-rs/issues")
+    }
+}
+
+impl IntoResponse<AwsJson1_0> for TsMiddlewareException {
+    fn into_response(self) -> http::Response<BoxBody> {
+        http::Response::builder()
+            .status(self.status_code)
+            .header("Content-Type", "application/x-amz-json-1.0")
+            // See https://smithy.io/2.0/aws/protocols/aws-json-1_0-protocol.html#empty-body-serialization
+            .body(to_boxed(self.json_body()))
+            .expect("invalid HTTP response for `MiddlewareException`; please file a bug report under https://github.com/smithy-lang/smithy-rs/issues")
+    }
+}
+
+impl IntoResponse<AwsJson1_1> for TsMiddlewareException {
+    fn into_response(self) -> http::Response<BoxBody> {
+        http::Response::builder()
+            .status(self.status_code)
+            .header("Content-Type", "application/x-amz-json-1.1")
+            // See https://smithy.io/2.0/aws/protocols/aws-json-1_1-protocol.html#empty-body-serialization
+            .body(to_boxed(self.json_body()))
+            .expect("invalid HTTP response for `MiddlewareException`; please file a bug report under https://github.com/smithy-lang/smithy-rs/issues")
+    }
+}
+
+impl TsMiddlewareException {
+    /// Serialize the body into a JSON object.
+    fn json_body(&self) -> String {
+        let mut out = String::new();
+        let mut object = aws_smithy_json::serialize::JsonObjectWriter::new(&mut out);
+        object.key("message").string(self.message.as_str());
+        object.finish();
+        out
+    }
+
+    /// Serialize the body into a XML object.
+    fn xml_body(&self) -> String {
+        let mut out = String::new();
+        {
+            let mut writer = aws_smithy_xml::encode::XmlWriter::new(&mut out);
+            let root = writer
+                .start_el("Error")
+                .write_ns("http://s3.amazonaws.com/doc/2006-03-01/", None);
+            let mut scope = root.finish();
+            {
+                let mut inner_writer = scope.start_el("Message").finish();
+                inner_writer.data(self.message.as_ref());
+            }
+            scope.finish();
+        }
+        out
+    }
+}
@@ -6,3 +6,37 @@
 /* Automatically managed default lints */
 #![cfg_attr(docsrs, feature(doc_auto_cfg))]
 /* End of automatically managed default lints */
+#![allow(clippy::derive_partial_eq_without_eq)]
+#![cfg_attr(docsrs, feature(doc_cfg))]
+
+//! Rust/Typescript bindings, runtime and utilities.
+//!
+//! This crates implements all the generic code needed to start and manage
+//! a Smithy Rust HTTP server where the business logic is implemented in Typescript,
+//! leveraging [napi-rs].
+//!
+//! Node.js runs JavaScript on a single event loop: operation handlers and middlewares
+//! registered from Typescript are called through [napi::threadsafe_function::ThreadsafeFunction]s,
+//! which schedule the call on the event loop and hand the resulting `Promise` back to the
+//! [Tokio] runtime driving the [hyper] server.
+//!
+//! [napi-rs]: https://napi.rs/
+//! [Tokio]: https://tokio.rs/
+
+mod error;
+pub mod middleware;
+mod server;
+mod socket;
+pub mod tls;
+#[cfg(feature = "tstests")]
+mod tstests;
+pub mod types;
+
+#[doc(inline)]
+pub use error::{TsError, TsMiddlewareException};
+#[doc(inline)]
+pub use middleware::{TsMiddlewareHandler, TsMiddlewareLayer, TsRequest, TsResponse};
+#[doc(inline)]
+pub use server::{start_hyper_worker, TsHandler};
+#[doc(inline)]
+pub use socket::TsSocket;
@@ -0,0 +1,25 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+use thiserror::Error;
+
+/// Possible middleware errors that might arise.
+#[derive(Error, Debug)]
+pub enum TsMiddlewareError {
+    #[error("request is accessed after the middleware returned")]
+    RequestGone,
+    #[error("header `{0}` is not valid")]
+    InvalidHeader(String),
+    #[error("URI `{0}` cannot be parsed")]
+    InvalidUri(String),
+    #[error("response status code `{0}` is not valid")]
+    InvalidStatusCode(u16),
+}
+
+impl From<TsMiddlewareError> for napi::Error {
+    fn from(err: TsMiddlewareError) -> napi::Error {
+        napi::Error::from_reason(err.to_string())
+    }
+}
@@ -0,0 +1,77 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! Execute pure-Typescript middleware handler.
+
+use aws_smithy_http_server::body::{Body, BoxBody};
+use http::{Request, Response};
+use napi::{
+    bindgen_prelude::Promise,
+    threadsafe_function::{ErrorStrategy, ThreadsafeFunction},
+    Env, JsFunction,
+};
+use tower::{util::BoxService, BoxError, ServiceExt};
+
+use super::{TsMiddlewareError, TsRequest, TsResponse};
+use crate::{server::async_threadsafe_function, TsMiddlewareException};
+
+// TsNext represents the inner service Tower layer applied to.
+type TsNext = BoxService<Request<Body>, Response<BoxBody>, BoxError>;
+
+/// A Typescript middleware handler function representation.
+///
+/// The function is wrapped in a [ThreadsafeFunction] so it can be scheduled on the Node.js
+/// event loop from any thread of the Tokio runtime. A middleware that is not `async` is handled
+/// like an `async` one, a synchronous exception being turned into a rejected `Promise`.
+#[derive(Clone)]
+pub struct TsMiddlewareHandler {
+    pub name: String,
+    func: ThreadsafeFunction<TsRequest, ErrorStrategy::Fatal>,
+}
+
+impl std::fmt::Debug for TsMiddlewareHandler {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TsMiddlewareHandler")
+            .field("name", &self.name)
+            .finish_non_exhaustive()
+    }
+}
+
+impl TsMiddlewareHandler {
+    pub fn new(env: &Env, name: impl Into<String>, func: &JsFunction) -> napi::Result<Self> {
+        let func = async_threadsafe_function(env, func)?;
+        Ok(Self {
+            name: name.into(),
+            func,
+        })
+    }
+
+    // Calls pure-Typescript middleware handler with given `Request` and, unless the middleware
+    // short-circuits with its own `Response`, forwards the request to the next Tower service.
+    pub async fn call(
+        self,
+        req: Request<Body>,
+        next: TsNext,
+    ) -> Result<Response<BoxBody>, TsMiddlewareException> {
+        let (ts_req, body) = TsRequest::new(req);
+        let result = self
+            .func
+            .call_async::<Promise<Option<TsResponse>>>(ts_req.clone())
+            .await?
+            .await?;
+        let req = ts_req
+            .take_inner(body)
+            .ok_or(TsMiddlewareError::RequestGone)
+            .map_err(napi::Error::from)?;
+
+        match result {
+            Some(response) => Ok(response.into_response().map_err(napi::Error::from)?),
+            None => next
+                .oneshot(req)
+                .await
+                .map_err(|err| TsMiddlewareException::new(err.to_string(), None)),
+        }
+    }
+}
@@ -0,0 +1,117 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! Tower layer implementation of Typescript middleware handling.
+
+use std::{
+    convert::Infallible,
+    marker::PhantomData,
+    mem,
+    task::{Context, Poll},
+};
+
+use aws_smithy_http_server::{
+    body::{Body, BoxBody},
+    response::IntoResponse,
+};
+use futures::{future::BoxFuture, TryFutureExt};
+use http::{Request, Response};
+use tower::{util::BoxService, Layer, Service, ServiceExt};
+
+use super::TsMiddlewareHandler;
+use crate::TsMiddlewareException;
+
+/// Tower [Layer] implementation of Typescript middleware handling.
+///
+/// Middleware stored in the `handler` attribute will be executed inside an async Tower middleware.
+#[derive(Debug, Clone)]
+pub struct TsMiddlewareLayer<P> {
+    handler: TsMiddlewareHandler,
+    _protocol: PhantomData<P>,
+}
+
+impl<P> TsMiddlewareLayer<P> {
+    pub fn new(handler: TsMiddlewareHandler) -> Self {
+        Self {
+            handler,
+            _protocol: PhantomData,
+        }
+    }
+}
+
+impl<S, P> Layer<S> for TsMiddlewareLayer<P>
+where
+    TsMiddlewareException: IntoResponse<P>,
+{
+    type Service = TsMiddlewareService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        TsMiddlewareService::new(
+            inner,
+            self.handler.clone(),
+            TsMiddlewareException::into_response,
+        )
+    }
+}
+
+/// Tower [Service] wrapping the Typescript middleware [Layer].
+#[derive(Clone, Debug)]
+pub struct TsMiddlewareService<S> {
+    inner: S,
+    handler: TsMiddlewareHandler,
+    into_response: fn(TsMiddlewareException) -> http::Response<BoxBody>,
+}
+
+impl<S> TsMiddlewareService<S> {
+    pub fn new(
+        inner: S,
+        handler: TsMiddlewareHandler,
+        into_response: fn(TsMiddlewareException) -> http::Response<BoxBody>,
+    ) -> TsMiddlewareService<S> {
+        Self {
+            inner,
+            handler,
+            into_response,
+        }
+    }
+}
+
+impl<S> Service<Request<Body>> for TsMiddlewareService<S>
+where
+    S: Service<Request<Body>, Response = Response<BoxBody>, Error = Infallible>
+        + Clone
+        + Send
+        + 'static,
+    S::Future: Send,
+{
+    type Response = S::Response;
+    // We are making `Service` `Infallible` because we convert errors to responses via
+    // `TsMiddlewareException::into_response` which has `IntoResponse<Protocol>` bound,
+    // so we always return a protocol specific error response instead of erroring out.
+    type Error = Infallible;
+    type Future = BoxFuture<'static, Result<Self::Response, Self::Error>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, req: Request<Body>) -> Self::Future {
+        let inner = {
+            // https://docs.rs/tower/latest/tower/trait.Service.html#be-careful-when-cloning-inner-services
+            let clone = self.inner.clone();
+            mem::replace(&mut self.inner, clone)
+        };
+        let handler = self.handler.clone();
+        let handler_name = handler.name.clone();
+        let next = BoxService::new(inner.map_err(|err| err.into()));
+        let into_response = self.into_response;
+
+        Box::pin(handler.call(req, next).or_else(move |err| async move {
+            tracing::error!(error = ?err, handler_name, "middleware failed");
+            let response = (into_response)(err);
+            Ok(response)
+        }))
+    }
+}
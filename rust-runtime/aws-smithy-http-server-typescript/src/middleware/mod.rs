@@ -0,0 +1,42 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! Schedule pure-Typescript middlewares as [tower::Layer]s.
+//!
+//! A Typescript middleware is a function receiving a [TsRequest] and returning a `Promise`:
+//!
+//! ```typescript
+//! async function authMiddleware(request: Request): Promise<Response | undefined> {
+//!     if (request.getHeader("authorization") === null) {
+//!         return { statusCode: 401 };
+//!     }
+//!     request.setHeader("x-authenticated", "true");
+//! }
+//! ```
+//!
+//! If the `Promise` resolves to a [TsResponse] the request is short-circuited and the response
+//! is returned to the client; otherwise the (possibly modified) request is forwarded to the next
+//! [tower::Service]. A rejected `Promise` is turned into a [TsMiddlewareException](crate::TsMiddlewareException).
+//!
+//! # Moving data from Rust to Typescript and back
+//!
+//! Once a value is moved into the Javascript heap, Rust cannot get its ownership back.
+//! [TsRequest] therefore wraps the request head in an `Arc<Mutex<Option<_>>>`: Rust keeps a
+//! handle to it while Typescript runs and takes the head back once the middleware `Promise`
+//! settles. Accessing a [TsRequest] after its middleware returned fails with
+//! [TsMiddlewareError::RequestGone]. The request body never crosses the boundary, so
+//! streaming requests are not buffered by middlewares.
+
+mod error;
+mod handler;
+mod layer;
+mod request;
+mod response;
+
+pub use self::error::TsMiddlewareError;
+pub use self::handler::TsMiddlewareHandler;
+pub use self::layer::{TsMiddlewareLayer, TsMiddlewareService};
+pub use self::request::TsRequest;
+pub use self::response::TsResponse;
@@ -0,0 +1,143 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! Typescript-compatible middleware [http::Request] implementation.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use aws_smithy_http_server::body::Body;
+use http::{header::HeaderName, request::Parts, HeaderValue, Request};
+use napi_derive::napi;
+use parking_lot::Mutex;
+
+use super::TsMiddlewareError;
+
+/// Typescript-compatible [Request] object.
+///
+/// Only the request head is exposed to Typescript, the body stays on the Rust side.
+#[napi(js_name = "Request")]
+#[derive(Debug, Clone)]
+pub struct TsRequest {
+    parts: Arc<Mutex<Option<Parts>>>,
+}
+
+impl TsRequest {
+    /// Create a new Typescript-compatible [Request] structure from the Rust side.
+    ///
+    /// Returns the [TsRequest] to be handed to Typescript and the body, which has to be
+    /// given back to [TsRequest::take_inner].
+    pub fn new(request: Request<Body>) -> (Self, Body) {
+        let (parts, body) = request.into_parts();
+        (
+            Self {
+                parts: Arc::new(Mutex::new(Some(parts))),
+            },
+            body,
+        )
+    }
+
+    // Takes the request head back and reassembles the request with `body`.
+    // Any handle still owned by Typescript will fail with `RequestGone` afterwards.
+    pub fn take_inner(&self, body: Body) -> Option<Request<Body>> {
+        let parts = self.parts.lock().take()?;
+        Some(Request::from_parts(parts, body))
+    }
+
+    fn with_parts<T>(
+        &self,
+        f: impl FnOnce(&mut Parts) -> Result<T, TsMiddlewareError>,
+    ) -> napi::Result<T> {
+        let mut parts = self.parts.lock();
+        let parts = parts.as_mut().ok_or(TsMiddlewareError::RequestGone)?;
+        Ok(f(parts)?)
+    }
+}
+
+#[napi]
+impl TsRequest {
+    /// Return the HTTP method of this request.
+    #[napi(getter)]
+    pub fn method(&self) -> napi::Result<String> {
+        self.with_parts(|parts| Ok(parts.method.to_string()))
+    }
+
+    /// Return the URI of this request.
+    #[napi(getter)]
+    pub fn uri(&self) -> napi::Result<String> {
+        self.with_parts(|parts| Ok(parts.uri.to_string()))
+    }
+
+    /// Sets the URI of this request.
+    #[napi(setter, js_name = "uri")]
+    pub fn set_uri(&self, uri: String) -> napi::Result<()> {
+        self.with_parts(|parts| {
+            parts.uri = uri
+                .parse()
+                .map_err(|_| TsMiddlewareError::InvalidUri(uri.clone()))?;
+            Ok(())
+        })
+    }
+
+    /// Return the HTTP version of this request.
+    #[napi(getter)]
+    pub fn version(&self) -> napi::Result<String> {
+        self.with_parts(|parts| Ok(format!("{:?}", parts.version)))
+    }
+
+    /// Return the HTTP headers of this request.
+    ///
+    /// Headers with multiple values are joined with `,`; headers whose value is not valid
+    /// UTF-8 are skipped.
+    #[napi(getter)]
+    pub fn headers(&self) -> napi::Result<HashMap<String, String>> {
+        self.with_parts(|parts| {
+            let mut headers: HashMap<String, String> = HashMap::new();
+            for (name, value) in parts.headers.iter() {
+                if let Ok(value) = value.to_str() {
+                    headers
+                        .entry(name.to_string())
+                        .and_modify(|existing| {
+                            existing.push(',');
+                            existing.push_str(value);
+                        })
+                        .or_insert_with(|| value.to_owned());
+                }
+            }
+            Ok(headers)
+        })
+    }
+
+    /// Return the first value of the header `name`, or `null` if it is not set.
+    #[napi]
+    pub fn get_header(&self, name: String) -> napi::Result<Option<String>> {
+        self.with_parts(|parts| {
+            Ok(parts
+                .headers
+                .get(&name)
+                .and_then(|value| value.to_str().ok())
+                .map(str::to_owned))
+        })
+    }
+
+    /// Set the header `name` to `value`, replacing any existing value.
+    #[napi]
+    pub fn set_header(&self, name: String, value: String) -> napi::Result<()> {
+        self.with_parts(|parts| {
+            let header_name = HeaderName::from_bytes(name.as_bytes())
+                .map_err(|_| TsMiddlewareError::InvalidHeader(name.clone()))?;
+            let header_value = HeaderValue::from_str(&value)
+                .map_err(|_| TsMiddlewareError::InvalidHeader(name.clone()))?;
+            parts.headers.insert(header_name, header_value);
+            Ok(())
+        })
+    }
+
+    /// Remove the header `name`, returning `true` if it was set.
+    #[napi]
+    pub fn remove_header(&self, name: String) -> napi::Result<bool> {
+        self.with_parts(|parts| Ok(parts.headers.remove(&name).is_some()))
+    }
+}
@@ -0,0 +1,45 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! Typescript-compatible middleware [http::Response] implementation.
+
+use std::collections::HashMap;
+
+use aws_smithy_http_server::body::{to_boxed, BoxBody};
+use http::{header::HeaderName, HeaderValue, Response, StatusCode};
+use napi::bindgen_prelude::Buffer;
+use napi_derive::napi;
+
+use super::TsMiddlewareError;
+
+/// Typescript-compatible [Response] object.
+///
+/// A middleware returns this plain object to short-circuit the request:
+/// `{ statusCode: 403, headers: { "content-type": "text/plain" }, body: Buffer.from("denied") }`.
+#[napi(object, js_name = "Response")]
+pub struct TsResponse {
+    pub status_code: u16,
+    pub headers: Option<HashMap<String, String>>,
+    pub body: Option<Buffer>,
+}
+
+impl TsResponse {
+    /// Convert the Typescript response into an HTTP response.
+    pub fn into_response(self) -> Result<Response<BoxBody>, TsMiddlewareError> {
+        let status = StatusCode::from_u16(self.status_code)
+            .map_err(|_| TsMiddlewareError::InvalidStatusCode(self.status_code))?;
+        let body = self.body.map(Vec::<u8>::from).unwrap_or_default();
+        let mut response = Response::new(to_boxed(body));
+        *response.status_mut() = status;
+        for (name, value) in self.headers.unwrap_or_default() {
+            let header_name = HeaderName::from_bytes(name.as_bytes())
+                .map_err(|_| TsMiddlewareError::InvalidHeader(name.clone()))?;
+            let header_value = HeaderValue::from_str(&value)
+                .map_err(|_| TsMiddlewareError::InvalidHeader(name.clone()))?;
+            response.headers_mut().insert(header_name, header_value);
+        }
+        Ok(response)
+    }
+}
@@ -0,0 +1,191 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! Run the generated Smithy service on a shared socket, dispatching operations to Typescript.
+
+use std::convert::Infallible;
+use std::net::TcpListener as StdTcpListener;
+use std::sync::{mpsc, Arc};
+
+use aws_smithy_http_server::{
+    body::{Body, BoxBody},
+    routing::IntoMakeService,
+};
+use http::{Request, Response};
+use hyper::server::conn::AddrIncoming;
+use napi::{
+    bindgen_prelude::{FromNapiValue, Promise, ToNapiValue},
+    threadsafe_function::{ErrorStrategy, ThreadsafeFunction},
+    Env, JsFunction,
+};
+use socket2::Socket;
+use tokio::net::TcpListener;
+use tokio_rustls::TlsAcceptor;
+use tower::util::BoxCloneService;
+
+use crate::{
+    tls::{listener::Listener as TlsListener, TsTlsConfig},
+    TsSocket,
+};
+
+// A `BoxCloneService` with default `Request`, `Response` and `Error`.
+type Service = BoxCloneService<Request<Body>, Response<BoxBody>, Infallible>;
+
+/// A Typescript operation handler.
+///
+/// The Typescript business logic is an `async` function taking the operation input and
+/// resolving to the operation output. The function is wrapped in a [ThreadsafeFunction]:
+/// calling the handler from the Tokio runtime schedules the call on the Node.js event loop and
+/// the returned `Promise` is awaited back in Rust, so handlers never block the Hyper server.
+///
+/// Handlers that are not `async` are supported as well: their return value is awaited as a
+/// resolved `Promise` and a synchronous exception as a rejected one.
+pub struct TsHandler<I: 'static> {
+    func: ThreadsafeFunction<I, ErrorStrategy::Fatal>,
+}
+
+impl<I: 'static> Clone for TsHandler<I> {
+    fn clone(&self) -> Self {
+        Self {
+            func: self.func.clone(),
+        }
+    }
+}
+
+impl<I: 'static> std::fmt::Debug for TsHandler<I> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TsHandler").finish_non_exhaustive()
+    }
+}
+
+impl<I: ToNapiValue + 'static> TsHandler<I> {
+    /// Wrap the Typescript function `func` so that it can be called from any thread.
+    pub fn new(env: &Env, func: &JsFunction) -> napi::Result<Self> {
+        let func = async_threadsafe_function(env, func)?;
+        Ok(Self { func })
+    }
+
+    /// Call the Typescript handler on the Node.js event loop and await its result.
+    ///
+    /// A rejected `Promise` or an exception thrown by the handler is returned as an error, which
+    /// generated servers turn into an internal server error response.
+    pub async fn call<O>(&self, input: I) -> napi::Result<O>
+    where
+        O: FromNapiValue + Send + 'static,
+    {
+        self.func.call_async::<Promise<O>>(input).await?.await
+    }
+}
+
+/// Wrap the Typescript function `func` in a [ThreadsafeFunction] whose calls always return a
+/// `Promise`.
+///
+/// With [ErrorStrategy::Fatal], a call that throws or whose return value cannot be converted
+/// aborts the Node.js process. `func` is therefore first wrapped in an `async` Javascript
+/// function: a synchronous exception becomes a rejected `Promise` and a plain return value a
+/// resolved one, which are both handled when the `Promise` is awaited in Rust.
+pub(crate) fn async_threadsafe_function<T: ToNapiValue + 'static>(
+    env: &Env,
+    func: &JsFunction,
+) -> napi::Result<ThreadsafeFunction<T, ErrorStrategy::Fatal>> {
+    let wrapper: JsFunction = env.run_script("(func) => async (...args) => func(...args)")?;
+    let func = JsFunction::try_from(wrapper.call(None, &[func])?)?;
+    func.create_threadsafe_function(0, |ctx| Ok(vec![ctx.value]))
+}
+
+/// Start a Hyper server serving `service` on the shared `socket`.
+///
+/// The server is spawned on the Tokio runtime managed by `napi`, leaving the Node.js event loop
+/// free to run the Typescript handlers. Every Node.js process started with the [cluster] module
+/// can call this function with a clone of the same [TsSocket]: the kernel load balances
+/// connections across all the processes bound to the socket.
+///
+/// If `tls` is provided, connections are served over TLS and certificates are reloaded
+/// periodically according to [TsTlsConfig::reload_duration].
+///
+/// [cluster]: https://nodejs.org/api/cluster.html
+pub fn start_hyper_worker(
+    socket: &TsSocket,
+    service: Service,
+    tls: Option<TsTlsConfig>,
+) -> napi::Result<()> {
+    let raw_socket = socket
+        .get_socket()
+        .map_err(|e| napi::Error::from_reason(e.to_string()))?;
+    let tls = tls
+        .map(|config| {
+            config
+                .build()
+                .map(|_| config)
+                .map_err(|e| napi::Error::from_reason(format!("invalid tls config: {e}")))
+        })
+        .transpose()?;
+
+    napi::bindgen_prelude::spawn(async move {
+        let addr = addr_incoming_from_socket(raw_socket);
+
+        if let Some(config) = tls {
+            let (acceptor, acceptor_rx) = tls_config_reloader(config);
+            let listener = TlsListener::new(acceptor, addr, acceptor_rx);
+            let server = hyper::Server::builder(listener).serve(IntoMakeService::new(service));
+
+            tracing::trace!("started tls hyper server from shared socket");
+            // Run forever-ish...
+            if let Err(err) = server.await {
+                tracing::error!(error = ?err, "server error");
+            }
+        } else {
+            let server = hyper::Server::builder(addr).serve(IntoMakeService::new(service));
+
+            tracing::trace!("started hyper server from shared socket");
+            // Run forever-ish...
+            if let Err(err) = server.await {
+                tracing::error!(error = ?err, "server error");
+            }
+        }
+    });
+    Ok(())
+}
+
+fn addr_incoming_from_socket(socket: Socket) -> AddrIncoming {
+    let std_listener: StdTcpListener = socket.into();
+    // StdTcpListener::from_std doesn't set O_NONBLOCK
+    std_listener
+        .set_nonblocking(true)
+        .expect("unable to set `O_NONBLOCK=true` on `std::net::TcpListener`");
+    let listener = TcpListener::from_std(std_listener)
+        .expect("unable to create `tokio::net::TcpListener` from `std::net::TcpListener`");
+    AddrIncoming::from_listener(listener)
+        .expect("unable to create `AddrIncoming` from `TcpListener`")
+}
+
+// Builds `TlsAcceptor` from given `config` and also creates a background task
+// to reload certificates and returns a channel to receive new `TlsAcceptor`s.
+fn tls_config_reloader(config: TsTlsConfig) -> (TlsAcceptor, mpsc::Receiver<TlsAcceptor>) {
+    let reload_dur = config.reload_duration();
+    let (tx, rx) = mpsc::channel();
+    let acceptor = TlsAcceptor::from(Arc::new(config.build().expect("invalid tls config")));
+
+    tokio::spawn(async move {
+        tracing::trace!(dur = ?reload_dur, "starting timer to reload tls config");
+        loop {
+            tokio::time::sleep(reload_dur).await;
+            tracing::trace!("reloading tls config");
+            match config.build() {
+                Ok(config) => {
+                    let new_config = TlsAcceptor::from(Arc::new(config));
+                    // Note on expect: `tx.send` can only fail if the receiver is dropped,
+                    // it probably a bug if that happens
+                    tx.send(new_config).expect("could not send new tls config")
+                }
+                Err(err) => {
+                    tracing::error!(error = ?err, "could not reload tls config because it is invalid");
+                }
+            }
+        }
+    });
+
+    (acceptor, rx)
+}
@@ -0,0 +1,86 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! Socket implementation that can be shared between multiple Node.js processes.
+
+use napi_derive::napi;
+use socket2::{Domain, Protocol, Socket, Type};
+use std::net::SocketAddr;
+
+/// Socket implementation that can be shared between multiple Node.js processes.
+///
+/// Node.js runs the business logic on a single thread, often resulting in only one core
+/// used by the application. To work around this, Node.js applications usually use the
+/// [cluster] module to fork multiple worker processes. By creating a socket with
+/// SO_REUSEADDR and SO_REUSEPORT enabled, every worker can bind to the same address,
+/// allowing you to maximize performance and use all available computing capacity of the host.
+///
+/// [cluster]: https://nodejs.org/api/cluster.html
+#[napi]
+#[derive(Debug)]
+pub struct TsSocket {
+    pub(crate) inner: Socket,
+}
+
+#[napi]
+impl TsSocket {
+    /// Create a new UNIX `SharedSocket` from an address, port and backlog.
+    /// If not specified, the backlog defaults to 1024 connections.
+    #[napi(constructor)]
+    pub fn new(address: String, port: i32, backlog: Option<i32>) -> napi::Result<Self> {
+        Self::new_socket(address, port, backlog).map_err(to_napi_error)
+    }
+
+    /// Clone the inner socket allowing it to be shared between multiple
+    /// Node.js processes.
+    #[napi]
+    pub fn try_clone(&self) -> napi::Result<TsSocket> {
+        let copied = self.inner.try_clone().map_err(to_napi_error)?;
+        Ok(TsSocket { inner: copied })
+    }
+}
+
+impl TsSocket {
+    /// Create a new socket, reporting errors as [std::io::Error].
+    pub fn new_socket(
+        address: String,
+        port: i32,
+        backlog: Option<i32>,
+    ) -> Result<Self, std::io::Error> {
+        let address: SocketAddr = format!("{}:{}", address, port)
+            .parse()
+            .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidInput, e))?;
+        let (domain, ip_version) = TsSocket::socket_domain(address);
+        tracing::trace!(address = %address, ip_version, "shared socket listening");
+        let socket = Socket::new(domain, Type::STREAM, Some(Protocol::TCP))?;
+        // Set value for the `SO_REUSEPORT` and `SO_REUSEADDR` options on this socket.
+        // This indicates that further calls to `bind` may allow reuse of local
+        // addresses. For IPv4 sockets this means that a socket may bind even when
+        // there's a socket already listening on this port.
+        socket.set_reuse_port(true)?;
+        socket.set_reuse_address(true)?;
+        socket.bind(&address.into())?;
+        socket.listen(backlog.unwrap_or(1024))?;
+        Ok(TsSocket { inner: socket })
+    }
+
+    /// Get a cloned inner socket.
+    pub fn get_socket(&self) -> Result<Socket, std::io::Error> {
+        self.inner.try_clone()
+    }
+
+    /// Find the socket domain
+    fn socket_domain(address: SocketAddr) -> (Domain, &'static str) {
+        if address.is_ipv6() {
+            (Domain::IPV6, "6")
+        } else {
+            (Domain::IPV4, "4")
+        }
+    }
+}
+
+fn to_napi_error(err: std::io::Error) -> napi::Error {
+    napi::Error::from_reason(err.to_string())
+}
@@ -0,0 +1,37 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+const assert = require("node:assert");
+const { test } = require("node:test");
+
+const { callHandler } = require("./tstests.node");
+
+test("resolves to the output of the handler", async () => {
+    const output = await callHandler(async (input) => `hello ${input}`, "client");
+    assert.strictEqual(output, "hello client");
+});
+
+test("awaits handlers that are not async", async () => {
+    const output = await callHandler((input) => `hello ${input}`, "client");
+    assert.strictEqual(output, "hello client");
+});
+
+test("returns a rejected Promise as an error", async () => {
+    await assert.rejects(
+        callHandler(async () => {
+            throw new Error("rejected");
+        }, "client"),
+    );
+});
+
+test("returns a synchronous throw as an error instead of aborting", async () => {
+    await assert.rejects(
+        callHandler(() => {
+            throw new Error("thrown");
+        }, "client"),
+    );
+    // The process is still alive and serves the next call.
+    assert.strictEqual(await callHandler(async () => "ok", "client"), "ok");
+});
@@ -0,0 +1,56 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+const assert = require("node:assert");
+const { test } = require("node:test");
+
+const { callMiddlewares } = require("./tstests.node");
+
+test("runs middlewares in the order they are registered", async () => {
+    const calls = [];
+    const middleware = (name) => async (request) => {
+        calls.push(name);
+        request.setHeader("x-middleware", name);
+    };
+
+    const response = await callMiddlewares([middleware("first"), middleware("second")]);
+    assert.deepStrictEqual(calls, ["first", "second"]);
+    assert.deepStrictEqual(response, { status: 200, body: "handler" });
+});
+
+test("returning a response short-circuits the next middlewares", async () => {
+    const calls = [];
+    const response = await callMiddlewares([
+        async () => {
+            calls.push("first");
+            return { statusCode: 401, body: Buffer.from("denied") };
+        },
+        async () => {
+            calls.push("second");
+        },
+    ]);
+    assert.deepStrictEqual(calls, ["first"]);
+    assert.deepStrictEqual(response, { status: 401, body: "denied" });
+});
+
+test("converts a rejected Promise to a protocol specific response", async () => {
+    const response = await callMiddlewares([
+        async () => {
+            throw new Error("fail");
+        },
+    ]);
+    assert.strictEqual(response.status, 500);
+    assert.ok("message" in JSON.parse(response.body));
+});
+
+test("converts a synchronous throw to a protocol specific response", async () => {
+    const response = await callMiddlewares([
+        () => {
+            throw new Error("fail");
+        },
+    ]);
+    assert.strictEqual(response.status, 500);
+    assert.ok("message" in JSON.parse(response.body));
+});
@@ -0,0 +1,75 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! Native functions used by the Typescript tests in this directory.
+//!
+//! Node.js cannot be embedded in a Rust test binary, so the tests run the other way around:
+//! `npm test` builds this crate as an addon with the `tstests` feature and calls the functions
+//! below from Node.js, which drive handlers and middlewares like a generated server does.
+
+use std::convert::Infallible;
+
+use aws_smithy_http_server::{
+    body::{to_boxed, Body},
+    protocol::rest_json_1::RestJson1,
+};
+use http::{Request, Response};
+use napi::{Env, JsFunction, JsObject};
+use napi_derive::napi;
+use tower::{service_fn, util::BoxCloneService, Layer, ServiceExt};
+
+use crate::{TsHandler, TsMiddlewareHandler, TsMiddlewareLayer};
+
+/// Call `handler` with `input` through a [TsHandler].
+///
+/// Returns a `Promise` resolving to the handler output.
+#[napi]
+pub fn call_handler(env: Env, handler: JsFunction, input: String) -> napi::Result<JsObject> {
+    let handler = TsHandler::<String>::new(&env, &handler)?;
+    env.execute_tokio_future(
+        async move { handler.call::<String>(input).await },
+        |_, output| Ok(output),
+    )
+}
+
+/// Response received by the client of [call_middlewares].
+#[napi(object)]
+pub struct TestResponse {
+    pub status: u32,
+    pub body: String,
+}
+
+/// Send a request through `middlewares`, the first one being the outermost, to an operation
+/// responding with a `200` and `handler` as body.
+///
+/// Returns a `Promise` resolving to a [TestResponse].
+#[napi]
+pub fn call_middlewares(env: Env, middlewares: Vec<JsFunction>) -> napi::Result<JsObject> {
+    let mut service = BoxCloneService::new(service_fn(|_request: Request<Body>| async {
+        Ok::<_, Infallible>(Response::new(to_boxed("handler")))
+    }));
+    for (index, middleware) in middlewares.iter().enumerate().rev() {
+        let handler = TsMiddlewareHandler::new(&env, format!("middleware-{index}"), middleware)?;
+        service = BoxCloneService::new(TsMiddlewareLayer::<RestJson1>::new(handler).layer(service));
+    }
+
+    env.execute_tokio_future(
+        async move {
+            let response = service
+                .oneshot(Request::new(Body::empty()))
+                .await
+                .unwrap_or_else(|never| match never {});
+            let status = response.status().as_u16().into();
+            let body = hyper::body::to_bytes(response.into_body())
+                .await
+                .map_err(|err| napi::Error::from_reason(err.to_string()))?;
+            Ok(TestResponse {
+                status,
+                body: String::from_utf8_lossy(&body).into_owned(),
+            })
+        },
+        |_, response| Ok(response),
+    )
+}
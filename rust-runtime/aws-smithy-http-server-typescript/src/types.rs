@@ -0,0 +1,555 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! Typescript wrapped types from aws-smithy-types.
+//!
+//! ## Classes and plain values
+//! Types that carry behaviour on the Typescript side ([DateTime] and [ByteStream]) are exposed
+//! as `napi` classes. Types that have a natural Javascript representation are converted to and
+//! from plain values instead: a [Blob] is a Node.js `Buffer` and a [Document] is any JSON-like
+//! value.
+//!
+//! Generated structures are `#[napi(object)]`s, which require their members to be convertible
+//! by value. `napi` only implements `FromNapiValue` for references to classes, so the classes
+//! in this module also implement it by value by cloning the wrapped Rust type; this is cheap
+//! because [DateTime] is `Copy`-sized and [ByteStream] is reference counted.
+//!
+//! ## `Deref` hacks for Json serializer
+//! [aws_smithy_json::serialize::JsonValueWriter] expects references to the types
+//! from [aws_smithy_types] (for example [aws_smithy_json::serialize::JsonValueWriter::document()]
+//! expects `&aws_smithy_types::Document`). In order to make
+//! [aws_smithy_json::serialize::JsonValueWriter] happy, we implement `Deref` traits for
+//! Typescript types to their Rust counterparts.
+
+use std::{
+    collections::HashMap,
+    future::Future,
+    ops::Deref,
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll},
+};
+
+use bytes::Bytes;
+use napi::{
+    bindgen_prelude::{
+        BigInt, Buffer, FromNapiValue, Null, ToNapiValue, TypeName, ValidateNapiValue,
+    },
+    sys, ValueType,
+};
+use napi_derive::napi;
+use tokio::sync::Mutex;
+
+use crate::TsError;
+
+/// Typescript Wrapper for [aws_smithy_types::Blob].
+///
+/// On the Typescript side a `Blob` is a Node.js `Buffer`.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Blob(aws_smithy_types::Blob);
+
+impl Blob {
+    /// Creates a new blob from the given `input`.
+    pub fn new<T: Into<Vec<u8>>>(input: T) -> Self {
+        Self(aws_smithy_types::Blob::new(input))
+    }
+
+    /// Consumes the `Blob` and returns a `Vec<u8>` with its contents.
+    pub fn into_inner(self) -> Vec<u8> {
+        self.0.into_inner()
+    }
+}
+
+impl AsRef<[u8]> for Blob {
+    fn as_ref(&self) -> &[u8] {
+        self.0.as_ref()
+    }
+}
+
+impl From<aws_smithy_types::Blob> for Blob {
+    fn from(other: aws_smithy_types::Blob) -> Blob {
+        Blob(other)
+    }
+}
+
+impl From<Blob> for aws_smithy_types::Blob {
+    fn from(other: Blob) -> aws_smithy_types::Blob {
+        other.0
+    }
+}
+
+impl<'blob> From<&'blob Blob> for &'blob aws_smithy_types::Blob {
+    fn from(other: &'blob Blob) -> &'blob aws_smithy_types::Blob {
+        &other.0
+    }
+}
+
+impl TypeName for Blob {
+    fn type_name() -> &'static str {
+        Buffer::type_name()
+    }
+
+    fn value_type() -> ValueType {
+        Buffer::value_type()
+    }
+}
+
+impl ValidateNapiValue for Blob {
+    unsafe fn validate(
+        env: sys::napi_env,
+        napi_val: sys::napi_value,
+    ) -> napi::Result<sys::napi_value> {
+        Buffer::validate(env, napi_val)
+    }
+}
+
+impl FromNapiValue for Blob {
+    unsafe fn from_napi_value(env: sys::napi_env, napi_val: sys::napi_value) -> napi::Result<Self> {
+        let buffer = Buffer::from_napi_value(env, napi_val)?;
+        Ok(Self::new(Vec::<u8>::from(buffer)))
+    }
+}
+
+impl ToNapiValue for Blob {
+    unsafe fn to_napi_value(env: sys::napi_env, val: Self) -> napi::Result<sys::napi_value> {
+        Buffer::to_napi_value(env, Buffer::from(val.into_inner()))
+    }
+}
+
+/// Formats for representing a `DateTime` in the Smithy protocols.
+#[napi]
+#[derive(Debug, Eq, PartialEq)]
+pub enum Format {
+    /// RFC-3339 Date Time.
+    DateTime,
+    /// Date format used by the HTTP `Date` header, specified in RFC-7231.
+    HttpDate,
+    /// Number of seconds since the Unix epoch formatted as a floating point.
+    EpochSeconds,
+}
+
+impl From<Format> for aws_smithy_types::date_time::Format {
+    fn from(variant: Format) -> aws_smithy_types::date_time::Format {
+        match variant {
+            Format::DateTime => aws_smithy_types::date_time::Format::DateTime,
+            Format::HttpDate => aws_smithy_types::date_time::Format::HttpDate,
+            Format::EpochSeconds => aws_smithy_types::date_time::Format::EpochSeconds,
+        }
+    }
+}
+
+/// Typescript Wrapper for [aws_smithy_types::date_time::DateTime].
+///
+/// DateTime in time represented as seconds and sub-second nanos since
+/// the Unix epoch (January 1, 1970 at midnight UTC/GMT).
+#[napi]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct DateTime(aws_smithy_types::date_time::DateTime);
+
+impl DateTime {
+    /// Formats the `DateTime` to a string using the given `format`.
+    ///
+    /// Returns an error if the given `DateTime` cannot be represented by the desired format.
+    pub fn fmt(
+        &self,
+        format: aws_smithy_types::date_time::Format,
+    ) -> Result<String, aws_smithy_types::date_time::DateTimeFormatError> {
+        self.0.fmt(format)
+    }
+}
+
+#[napi]
+impl DateTime {
+    /// Creates a `DateTime` from a number of seconds since the Unix epoch.
+    #[napi(factory)]
+    pub fn from_secs(epoch_seconds: i64) -> Self {
+        Self(aws_smithy_types::date_time::DateTime::from_secs(
+            epoch_seconds,
+        ))
+    }
+
+    /// Creates a `DateTime` from a number of milliseconds since the Unix epoch.
+    #[napi(factory)]
+    pub fn from_millis(epoch_millis: i64) -> Self {
+        Self(aws_smithy_types::date_time::DateTime::from_millis(
+            epoch_millis,
+        ))
+    }
+
+    /// Creates a `DateTime` from a number of nanoseconds since the Unix epoch.
+    #[napi(factory)]
+    pub fn from_nanos(epoch_nanos: BigInt) -> napi::Result<Self> {
+        let (epoch_nanos, lossless) = epoch_nanos.get_i128();
+        if !lossless {
+            return Err(napi::Error::from_reason(
+                "epoch nanos cannot be represented as a 128-bit integer",
+            ));
+        }
+        Ok(Self(
+            aws_smithy_types::date_time::DateTime::from_nanos(epoch_nanos)
+                .map_err(TsError::DateTimeConversion)?,
+        ))
+    }
+
+    /// Creates a `DateTime` from a number of seconds and a fractional second since the Unix epoch.
+    #[napi(factory)]
+    pub fn from_fractional_secs(epoch_seconds: i64, fraction: f64) -> Self {
+        Self(aws_smithy_types::date_time::DateTime::from_fractional_secs(
+            epoch_seconds,
+            fraction,
+        ))
+    }
+
+    /// Creates a `DateTime` from a number of seconds and sub-second nanos since the Unix epoch.
+    #[napi(factory)]
+    pub fn from_secs_and_nanos(seconds: i64, subsecond_nanos: u32) -> Self {
+        Self(aws_smithy_types::date_time::DateTime::from_secs_and_nanos(
+            seconds,
+            subsecond_nanos,
+        ))
+    }
+
+    /// Creates a `DateTime` from an `f64` representing the number of seconds since the Unix epoch.
+    #[napi(factory)]
+    pub fn from_secs_f64(epoch_seconds: f64) -> Self {
+        Self(aws_smithy_types::date_time::DateTime::from_secs_f64(
+            epoch_seconds,
+        ))
+    }
+
+    /// Parses a `DateTime` from a string using the given `format`.
+    #[napi(factory)]
+    pub fn from_str(s: String, format: Format) -> napi::Result<Self> {
+        Ok(Self(
+            aws_smithy_types::date_time::DateTime::from_str(&s, format.into())
+                .map_err(TsError::DateTimeParse)?,
+        ))
+    }
+
+    /// Formats the `DateTime` to a string using the given `format`.
+    #[napi(js_name = "format")]
+    pub fn format(&self, format: Format) -> napi::Result<String> {
+        self.fmt(format.into())
+            .map_err(|e| napi::Error::from_reason(e.to_string()))
+    }
+
+    /// Returns the number of nanoseconds since the Unix epoch that this `DateTime` represents.
+    #[napi]
+    pub fn as_nanos(&self) -> BigInt {
+        BigInt::from(self.0.as_nanos())
+    }
+
+    /// Returns the `DateTime` value as an `f64` representing the seconds since the Unix epoch.
+    #[napi]
+    pub fn as_secs_f64(&self) -> f64 {
+        self.0.as_secs_f64()
+    }
+
+    /// Returns true if sub-second nanos is greater than zero.
+    #[napi]
+    pub fn has_subsec_nanos(&self) -> bool {
+        self.0.has_subsec_nanos()
+    }
+
+    /// Returns the epoch seconds component of the `DateTime`.
+    #[napi]
+    pub fn secs(&self) -> i64 {
+        self.0.secs()
+    }
+
+    /// Returns the sub-second nanos component of the `DateTime`.
+    #[napi]
+    pub fn subsec_nanos(&self) -> u32 {
+        self.0.subsec_nanos()
+    }
+
+    /// Converts the `DateTime` to the number of milliseconds since the Unix epoch.
+    #[napi]
+    pub fn to_millis(&self) -> napi::Result<i64> {
+        Ok(self.0.to_millis().map_err(TsError::DateTimeConversion)?)
+    }
+}
+
+impl From<aws_smithy_types::DateTime> for DateTime {
+    fn from(other: aws_smithy_types::DateTime) -> DateTime {
+        DateTime(other)
+    }
+}
+
+impl Deref for DateTime {
+    type Target = aws_smithy_types::DateTime;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl ValidateNapiValue for DateTime {
+    unsafe fn validate(
+        env: sys::napi_env,
+        napi_val: sys::napi_value,
+    ) -> napi::Result<sys::napi_value> {
+        <&DateTime>::validate(env, napi_val)
+    }
+}
+
+impl FromNapiValue for DateTime {
+    unsafe fn from_napi_value(env: sys::napi_env, napi_val: sys::napi_value) -> napi::Result<Self> {
+        <&DateTime>::from_napi_value(env, napi_val).cloned()
+    }
+}
+
+/// Typescript Wrapper for [aws_smithy_types::byte_stream::ByteStream].
+///
+/// ByteStream provides misuse-resistant primitives to make it easier to handle common patterns with streaming data.
+///
+/// On the Rust side, the Typescript implementation wraps the original [ByteStream](aws_smithy_types::byte_stream::ByteStream)
+/// in a clonable structure and implements the [Stream](futures::stream::Stream) trait for it to
+/// allow Rust to handle the type transparently.
+///
+/// On the Typescript side the stream is consumed by awaiting `next()` until it resolves to
+/// `null`, which makes it straightforward to wrap into an async iterator:
+///
+/// ```typescript
+/// async function* chunks(stream: ByteStream) {
+///     let chunk;
+///     while ((chunk = await stream.next()) !== null) {
+///         yield chunk;
+///     }
+/// }
+/// ```
+///
+/// The original Rust [ByteStream](aws_smithy_types::byte_stream::ByteStream) is wrapped inside a `Arc<Mutex>` to allow the type to be
+/// [Clone] (required to move it in and out of Javascript) and to allow internal mutability, required to fetch the next chunk of data.
+#[napi]
+#[derive(Debug, Clone)]
+pub struct ByteStream(Arc<Mutex<aws_smithy_types::byte_stream::ByteStream>>);
+
+impl futures::stream::Stream for ByteStream {
+    type Item = Result<Bytes, aws_smithy_types::byte_stream::error::Error>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let stream = self.0.lock();
+        tokio::pin!(stream);
+        match stream.poll(cx) {
+            Poll::Ready(mut stream) => Pin::new(&mut *stream).poll_next(cx),
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
+
+/// Return a new data chunk from the stream.
+async fn yield_data_chunk(
+    body: Arc<Mutex<aws_smithy_types::byte_stream::ByteStream>>,
+) -> napi::Result<Option<Bytes>> {
+    let mut stream = body.lock().await;
+    Ok(stream
+        .next()
+        .await
+        .transpose()
+        .map_err(TsError::ByteStream)?)
+}
+
+impl ByteStream {
+    /// Construct a new [`ByteStream`](aws_smithy_types::byte_stream::ByteStream) from a
+    /// [`SdkBody`](aws_smithy_types::body::SdkBody).
+    ///
+    /// This method is available only to Rust and it is required to comply with the
+    /// interface required by the code generator.
+    pub fn new(body: aws_smithy_types::body::SdkBody) -> Self {
+        Self(Arc::new(Mutex::new(
+            aws_smithy_types::byte_stream::ByteStream::new(body),
+        )))
+    }
+}
+
+impl Default for ByteStream {
+    fn default() -> Self {
+        Self::new(aws_smithy_types::body::SdkBody::from(""))
+    }
+}
+
+#[napi]
+impl ByteStream {
+    /// Create a new [ByteStream](aws_smithy_types::byte_stream::ByteStream) from a `Buffer`.
+    #[napi(constructor)]
+    pub fn from_buffer(input: Buffer) -> Self {
+        Self::new(aws_smithy_types::body::SdkBody::from(Vec::<u8>::from(
+            input,
+        )))
+    }
+
+    /// Create a new [ByteStream](aws_smithy_types::byte_stream::ByteStream) from a path.
+    ///
+    /// The file is read lazily, one chunk at a time, as the stream is consumed.
+    #[napi]
+    pub async fn from_path(path: String) -> napi::Result<ByteStream> {
+        let byte_stream = aws_smithy_types::byte_stream::ByteStream::from_path(path)
+            .await
+            .map_err(TsError::ByteStream)?;
+        Ok(Self(Arc::new(Mutex::new(byte_stream))))
+    }
+
+    /// Return the next chunk of the stream, or `null` once the stream is exhausted.
+    ///
+    /// To get the next value of the iterator, the `Arc` inner stream is cloned and the Rust call
+    /// to `next()` is converted into a Javascript `Promise`.
+    #[napi(js_name = "next", ts_return_type = "Promise<Buffer | null>")]
+    pub async fn next_chunk(&self) -> napi::Result<Option<Buffer>> {
+        let data = yield_data_chunk(self.0.clone()).await?;
+        Ok(data.map(|data| Buffer::from(data.to_vec())))
+    }
+}
+
+impl ValidateNapiValue for ByteStream {
+    unsafe fn validate(
+        env: sys::napi_env,
+        napi_val: sys::napi_value,
+    ) -> napi::Result<sys::napi_value> {
+        <&ByteStream>::validate(env, napi_val)
+    }
+}
+
+impl FromNapiValue for ByteStream {
+    unsafe fn from_napi_value(env: sys::napi_env, napi_val: sys::napi_value) -> napi::Result<Self> {
+        <&ByteStream>::from_napi_value(env, napi_val).cloned()
+    }
+}
+
+/// Typescript Wrapper for [aws_smithy_types::Document].
+///
+/// On the Typescript side a `Document` is any JSON-like value: `null`, booleans, numbers,
+/// strings, arrays and plain objects. Integral numbers are converted to
+/// [aws_smithy_types::Number::PosInt] or [aws_smithy_types::Number::NegInt], `BigInt`s are
+/// accepted as long as they fit into those variants.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Document(aws_smithy_types::Document);
+
+impl TypeName for Document {
+    fn type_name() -> &'static str {
+        "Document"
+    }
+
+    fn value_type() -> ValueType {
+        ValueType::Unknown
+    }
+}
+
+impl ValidateNapiValue for Document {}
+
+impl ToNapiValue for Document {
+    unsafe fn to_napi_value(env: sys::napi_env, val: Self) -> napi::Result<sys::napi_value> {
+        use aws_smithy_types::{Document as D, Number};
+
+        match val.0 {
+            D::Object(obj) => HashMap::to_napi_value(
+                env,
+                obj.into_iter()
+                    .map(|(k, v)| (k, Document(v)))
+                    .collect::<HashMap<_, _>>(),
+            ),
+            D::Array(vec) => Vec::to_napi_value(env, vec.into_iter().map(Document).collect()),
+            D::Number(Number::Float(f)) => f64::to_napi_value(env, f),
+            // Javascript numbers are `f64`s: integers that do not fit are sent as `BigInt`s.
+            D::Number(Number::PosInt(pi)) if pi > MAX_SAFE_INTEGER as u64 => {
+                BigInt::to_napi_value(env, BigInt::from(pi))
+            }
+            D::Number(Number::PosInt(pi)) => f64::to_napi_value(env, pi as f64),
+            D::Number(Number::NegInt(ni)) if ni < -MAX_SAFE_INTEGER => {
+                BigInt::to_napi_value(env, BigInt::from(ni))
+            }
+            D::Number(Number::NegInt(ni)) => f64::to_napi_value(env, ni as f64),
+            D::String(str) => String::to_napi_value(env, str),
+            D::Bool(bool) => bool::to_napi_value(env, bool),
+            D::Null => Null::to_napi_value(env, Null),
+        }
+    }
+}
+
+/// Largest integer that can be represented exactly by a Javascript number.
+const MAX_SAFE_INTEGER: i64 = (1 << 53) - 1;
+
+impl FromNapiValue for Document {
+    unsafe fn from_napi_value(env: sys::napi_env, napi_val: sys::napi_value) -> napi::Result<Self> {
+        use aws_smithy_types::{Document as D, Number};
+
+        let mut value_type = 0;
+        napi::check_status!(sys::napi_typeof(env, napi_val, &mut value_type))?;
+        let doc = match ValueType::from(value_type) {
+            ValueType::Null | ValueType::Undefined => D::Null,
+            ValueType::Boolean => D::Bool(bool::from_napi_value(env, napi_val)?),
+            ValueType::Number => {
+                let f = f64::from_napi_value(env, napi_val)?;
+                if f.fract() == 0.0 && f.abs() <= MAX_SAFE_INTEGER as f64 {
+                    if f >= 0.0 {
+                        D::Number(Number::PosInt(f as u64))
+                    } else {
+                        D::Number(Number::NegInt(f as i64))
+                    }
+                } else {
+                    D::Number(Number::Float(f))
+                }
+            }
+            ValueType::BigInt => {
+                let (value, lossless) = BigInt::from_napi_value(env, napi_val)?.get_i128();
+                match (lossless, u64::try_from(value), i64::try_from(value)) {
+                    (true, Ok(pi), _) => D::Number(Number::PosInt(pi)),
+                    (true, _, Ok(ni)) => D::Number(Number::NegInt(ni)),
+                    _ => {
+                        return Err(napi::Error::from_reason(
+                            "'BigInt' is out of range for 'Document'",
+                        ))
+                    }
+                }
+            }
+            ValueType::String => D::String(String::from_napi_value(env, napi_val)?),
+            ValueType::Object => {
+                let mut is_array = false;
+                napi::check_status!(sys::napi_is_array(env, napi_val, &mut is_array))?;
+                if is_array {
+                    D::Array(
+                        Vec::<Document>::from_napi_value(env, napi_val)?
+                            .into_iter()
+                            .map(|d| d.0)
+                            .collect(),
+                    )
+                } else {
+                    D::Object(
+                        HashMap::<String, Document>::from_napi_value(env, napi_val)?
+                            .into_iter()
+                            .map(|(k, v)| (k, v.0))
+                            .collect(),
+                    )
+                }
+            }
+            other => {
+                return Err(napi::Error::from_reason(format!(
+                    "'{other}' cannot be converted to 'Document'"
+                )))
+            }
+        };
+        Ok(Self(doc))
+    }
+}
+
+impl Deref for Document {
+    type Target = aws_smithy_types::Document;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl From<aws_smithy_types::Document> for Document {
+    fn from(other: aws_smithy_types::Document) -> Document {
+        Document(other)
+    }
+}
+
+impl From<Document> for aws_smithy_types::Document {
+    fn from(other: Document) -> aws_smithy_types::Document {
+        other.0
+    }
+}
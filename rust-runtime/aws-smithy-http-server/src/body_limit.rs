@@ -0,0 +1,297 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! Limits the size of request bodies.
+//!
+//! Non-streaming operation inputs are deserialized from a buffered request body: without a limit, a single client can
+//! make the service allocate as much memory as it is willing to send. [`RequestBodyLimitPlugin`] caps the size of
+//! request bodies, globally and per operation:
+//!
+//! - Requests declaring a `Content-Length` larger than the limit are rejected before the body is read and before the
+//!   operation is invoked.
+//! - Other bodies, for example bodies using chunked transfer encoding, are wrapped in a [`Limited`] body, which fails
+//!   as soon as more bytes than the limit have been read.
+//!
+//! In both cases, the client of an operation with a non-streaming input receives the protocol-specific
+//! `PayloadTooLargeException`, with a `413 Payload Too Large` status code, produced by the
+//! `RequestRejection::PayloadTooLarge` variant of the protocol's `rejection` module.
+//!
+//! `@streaming` operation inputs are not buffered before the handler is called, so a body exceeding the limit
+//! without declaring it in `Content-Length` is seen by the handler as an error when reading past the limit, and the
+//! response is the one the handler returns.
+//!
+//! # Example
+//!
+//! ```rust,ignore
+//! use aws_smithy_http_server::body_limit::RequestBodyLimitPlugin;
+//! use aws_smithy_http_server::plugin::HttpPlugins;
+//!
+//! let http_plugins = HttpPlugins::new().push(
+//!     // Requests are limited to 1 MiB, except for `PutObject` which accepts up to 5 GiB.
+//!     RequestBodyLimitPlugin::new(1024 * 1024).operation_limit(operation_shape::PutObject::ID, 5 * 1024 * 1024 * 1024),
+//! );
+//! let config = PokemonServiceConfig::builder().http_plugin(http_plugins).build();
+//! ```
+
+use std::{
+    collections::HashMap,
+    marker::PhantomData,
+    task::{Context, Poll},
+};
+
+use futures_util::future::{ready, Either, Ready};
+use http::{header::CONTENT_LENGTH, Request, Response};
+use http_body::Limited;
+use tower::Service;
+
+use crate::{
+    body::BoxBody,
+    operation::OperationShape,
+    plugin::{HttpMarker, Plugin},
+    response::IntoResponse,
+    runtime_error::PayloadTooLargeException,
+    service::ServiceShape,
+    shape_id::ShapeId,
+};
+
+/// A [`Plugin`] which applies [`RequestBodyLimit`] to every operation.
+///
+/// The limit of an operation is the one configured with [`RequestBodyLimitPlugin::operation_limit`], if any, otherwise
+/// the global limit given in [`RequestBodyLimitPlugin::new`].
+#[derive(Debug, Clone)]
+pub struct RequestBodyLimitPlugin {
+    limit: usize,
+    operation_limits: HashMap<ShapeId, usize>,
+}
+
+impl RequestBodyLimitPlugin {
+    /// Limits the request bodies of all operations to `limit` bytes.
+    pub fn new(limit: usize) -> Self {
+        Self {
+            limit,
+            operation_limits: HashMap::new(),
+        }
+    }
+
+    /// Overrides the limit, in bytes, for the operation identified by `operation`.
+    ///
+    /// The [`ShapeId`] of an operation is available through [`OperationShape::ID`].
+    pub fn operation_limit(mut self, operation: ShapeId, limit: usize) -> Self {
+        self.operation_limits.insert(operation, limit);
+        self
+    }
+}
+
+impl<Ser, Op, T> Plugin<Ser, Op, T> for RequestBodyLimitPlugin
+where
+    Ser: ServiceShape,
+    Op: OperationShape,
+{
+    type Output = RequestBodyLimit<Ser::Protocol, T>;
+
+    fn apply(&self, inner: T) -> Self::Output {
+        let limit = self.operation_limits.get(&Op::ID).copied().unwrap_or(self.limit);
+        RequestBodyLimit::new(inner, limit)
+    }
+}
+
+impl HttpMarker for RequestBodyLimitPlugin {}
+
+/// A middleware [`Service`] limiting the size of request bodies to a fixed number of bytes.
+///
+/// Requests with a `Content-Length` larger than the limit are rejected with the `PayloadTooLargeException` of the
+/// protocol `P` without calling the inner service. The bodies of other requests are wrapped in a [`Limited`] body.
+pub struct RequestBodyLimit<P, S> {
+    inner: S,
+    limit: usize,
+    _protocol: PhantomData<P>,
+}
+
+impl<P, S> RequestBodyLimit<P, S> {
+    /// Limits the request bodies passed to `inner` to `limit` bytes.
+    pub fn new(inner: S, limit: usize) -> Self {
+        Self {
+            inner,
+            limit,
+            _protocol: PhantomData,
+        }
+    }
+}
+
+impl<P, S> Clone for RequestBodyLimit<P, S>
+where
+    S: Clone,
+{
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+            limit: self.limit,
+            _protocol: PhantomData,
+        }
+    }
+}
+
+impl<P, S> std::fmt::Debug for RequestBodyLimit<P, S>
+where
+    S: std::fmt::Debug,
+{
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("RequestBodyLimit")
+            .field("inner", &self.inner)
+            .field("limit", &self.limit)
+            .finish()
+    }
+}
+
+impl<P, B, S> Service<Request<B>> for RequestBodyLimit<P, S>
+where
+    S: Service<Request<Limited<B>>, Response = Response<BoxBody>>,
+    PayloadTooLargeException: IntoResponse<P>,
+{
+    type Response = Response<BoxBody>;
+    type Error = S::Error;
+    type Future = Either<Ready<Result<Self::Response, Self::Error>>, S::Future>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Request<B>) -> Self::Future {
+        // A malformed `Content-Length` is left to Hyper, which does not let such requests through.
+        let content_length = request
+            .headers()
+            .get(CONTENT_LENGTH)
+            .and_then(|value| value.to_str().ok())
+            .and_then(|value| value.parse::<u64>().ok());
+        if matches!(content_length, Some(length) if length > self.limit as u64) {
+            tracing::debug!(
+                content_length,
+                limit = self.limit,
+                "rejecting request whose `Content-Length` exceeds the body size limit"
+            );
+            return Either::Left(ready(Ok(PayloadTooLargeException.into_response())));
+        }
+
+        let limit = self.limit;
+        Either::Right(self.inner.call(request.map(|body| Limited::new(body, limit))))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::Infallible;
+
+    use http::{Request, Response, StatusCode};
+    use tower::{service_fn, ServiceExt};
+
+    use super::*;
+    use crate::body::Body;
+    use crate::protocol::rest_json_1::{rejection::RequestRejection, runtime_error::RuntimeError, RestJson1};
+
+    struct TestService;
+
+    impl ServiceShape for TestService {
+        const ID: ShapeId = ShapeId::new("namespace#TestService", "namespace", "TestService");
+        const VERSION: Option<&'static str> = None;
+        type Protocol = RestJson1;
+        type Operations = ();
+    }
+
+    struct Small;
+
+    impl OperationShape for Small {
+        const ID: ShapeId = ShapeId::new("namespace#Small", "namespace", "Small");
+        type Input = ();
+        type Output = ();
+        type Error = ();
+    }
+
+    struct Large;
+
+    impl OperationShape for Large {
+        const ID: ShapeId = ShapeId::new("namespace#Large", "namespace", "Large");
+        type Input = ();
+        type Output = ();
+        type Error = ();
+    }
+
+    // Buffers the body like non-streaming operations do, rendering rejections as RestJson1 runtime errors.
+    async fn buffer(request: Request<Limited<Body>>) -> Result<Response<BoxBody>, Infallible> {
+        let response = match hyper::body::to_bytes(request.into_body()).await {
+            Ok(bytes) => Response::new(crate::body::to_boxed(bytes)),
+            Err(err) => RuntimeError::from(RequestRejection::from(err)).into_response(),
+        };
+        Ok(response)
+    }
+
+    fn chunked(chunks: &'static [&'static str]) -> Body {
+        let chunks = futures_util::stream::iter(chunks.iter().map(|chunk| Ok::<_, Infallible>(*chunk)));
+        Body::wrap_stream(chunks)
+    }
+
+    async fn assert_payload_too_large(response: Response<BoxBody>) {
+        assert_eq!(response.status(), StatusCode::PAYLOAD_TOO_LARGE);
+        assert_eq!(
+            response.headers().get("X-Amzn-Errortype").unwrap(),
+            "PayloadTooLargeException"
+        );
+    }
+
+    #[tokio::test]
+    async fn rejects_on_content_length_without_calling_the_operation() {
+        async fn operation(_request: Request<Limited<Body>>) -> Result<Response<BoxBody>, Infallible> {
+            panic!("the operation must not be called")
+        }
+
+        let svc = RequestBodyLimit::<RestJson1, _>::new(service_fn(operation), 4);
+        let request = Request::builder()
+            .header(CONTENT_LENGTH, "5")
+            .body(Body::from("hello"))
+            .unwrap();
+
+        assert_payload_too_large(svc.oneshot(request).await.unwrap()).await;
+    }
+
+    #[tokio::test]
+    async fn aborts_streaming_body_once_exceeded() {
+        let svc = RequestBodyLimit::<RestJson1, _>::new(service_fn(buffer), 8);
+        let request = Request::new(chunked(&["hello", " ", "world"]));
+
+        assert_payload_too_large(svc.oneshot(request).await.unwrap()).await;
+    }
+
+    #[tokio::test]
+    async fn accepts_body_within_limit() {
+        let svc = RequestBodyLimit::<RestJson1, _>::new(service_fn(buffer), 11);
+        let request = Request::builder()
+            .header(CONTENT_LENGTH, "11")
+            .body(Body::from("hello world"))
+            .unwrap();
+        let response = svc.oneshot(request).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+
+        let svc = RequestBodyLimit::<RestJson1, _>::new(service_fn(buffer), 11);
+        let response = svc
+            .oneshot(Request::new(chunked(&["hello", " ", "world"])))
+            .await
+            .unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+        let body = hyper::body::to_bytes(response.into_body()).await.unwrap();
+        assert_eq!(body, "hello world");
+    }
+
+    #[tokio::test]
+    async fn operation_limit_overrides_global_limit() {
+        let plugin = RequestBodyLimitPlugin::new(4).operation_limit(Large::ID, 16);
+
+        let small = Plugin::<TestService, Small, _>::apply(&plugin, service_fn(buffer));
+        let response = small.oneshot(Request::new(Body::from("hello"))).await.unwrap();
+        assert_payload_too_large(response).await;
+
+        let large = Plugin::<TestService, Large, _>::apply(&plugin, service_fn(buffer));
+        let response = large.oneshot(Request::new(Body::from("hello"))).await.unwrap();
+        assert_eq!(response.status(), StatusCode::OK);
+    }
+}
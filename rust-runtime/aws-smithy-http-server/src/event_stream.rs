@@ -0,0 +1,102 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! # Event stream message verification
+//!
+//! The messages of an event stream sent by a client can be signed, with each signature chained
+//! to the signature of the previous message, starting with the signature of the initial request.
+//! Operations receive event streams as a [`Receiver`], and [`verify_messages`] makes it verify
+//! every message before it's unmarshalled.
+//!
+//! ## Examples
+//!
+//! With the SigV4 `MessageVerifier` from `aws-sigv4`, seeded with the signature of the initial
+//! request:
+//!
+//! ```rust,ignore
+//! pub async fn handler(mut input: Input) -> Result<Output, Error> {
+//!     let verifier = MessageVerifier::new(seed_signature, seed_time, identity, "us-east-1", "service");
+//!     verify_messages(&mut input.events, verifier, |err| {
+//!         EventsError::ValidationException(ValidationException::builder().message(err.to_string()).build())
+//!     });
+//!     while let Some(event) = input.events.recv().await? {
+//!         /* Handle the verified event */
+//!     }
+//!     todo!()
+//! }
+//! ```
+
+use aws_smithy_eventstream::frame::{VerifyMessage, VerifyMessageError};
+use aws_smithy_http::event_stream::Receiver;
+
+/// Verifies the signature of every message received by `receiver`, in order.
+///
+/// A message that fails verification ends the stream with the modeled error returned by
+/// `into_error`, which [`Receiver::recv`] returns as a `ServiceError`. No further messages are
+/// received after it.
+pub fn verify_messages<T, E>(
+    receiver: &mut Receiver<T, E>,
+    verifier: impl VerifyMessage + Send + Sync + 'static,
+    into_error: impl Fn(VerifyMessageError) -> E + Send + Sync + 'static,
+) {
+    receiver.set_verifier_with_error(verifier, into_error);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::verify_messages;
+    use aws_smithy_eventstream::frame::{write_message_to, UnmarshallMessage, UnmarshalledMessage};
+    use aws_smithy_http::event_stream::test_util::SequenceVerifier;
+    use aws_smithy_http::event_stream::Receiver;
+    use aws_smithy_runtime_api::client::result::SdkError;
+    use aws_smithy_types::body::SdkBody;
+    use aws_smithy_types::event_stream::Message;
+    use bytes::Bytes;
+
+    #[derive(Debug, PartialEq)]
+    struct ModeledError(String);
+
+    #[derive(Debug)]
+    struct Unmarshaller;
+
+    impl UnmarshallMessage for Unmarshaller {
+        type Output = String;
+        type Error = ModeledError;
+
+        fn unmarshall(
+            &self,
+            message: &Message,
+        ) -> Result<UnmarshalledMessage<Self::Output, Self::Error>, aws_smithy_eventstream::error::Error> {
+            Ok(UnmarshalledMessage::Event(
+                String::from_utf8(message.payload().to_vec()).unwrap(),
+            ))
+        }
+    }
+
+    fn wrapped(sequence: i32, payload: &'static str) -> Vec<u8> {
+        let mut inner = Vec::new();
+        write_message_to(&Message::new(payload), &mut inner).unwrap();
+        SequenceVerifier::wrap(sequence, Bytes::from(inner)).to_vec()
+    }
+
+    #[tokio::test]
+    async fn verification_failure_ends_the_stream_with_a_modeled_error() {
+        let mut body = wrapped(1, "one");
+        // A replayed message
+        body.extend(wrapped(1, "one"));
+        body.extend(wrapped(2, "two"));
+        let mut receiver = Receiver::new(Unmarshaller, SdkBody::from(body));
+        verify_messages(&mut receiver, SequenceVerifier::new(), |err| {
+            ModeledError(err.to_string())
+        });
+
+        assert_eq!("one", receiver.recv().await.unwrap().unwrap());
+        match receiver.recv().await.unwrap_err() {
+            SdkError::ServiceError(context) => assert_eq!(&ModeledError("expected sequence 2".into()), context.err()),
+            err => panic!("expected a modeled error, found {err:?}"),
+        }
+        assert_eq!(None, receiver.recv().await.unwrap());
+    }
+}
@@ -59,6 +59,9 @@
 //!
 //! [sensitive trait]: https://smithy.io/2.0/spec/documentation-traits.html#sensitive-trait
 
+#[cfg(feature = "opentelemetry")]
+#[cfg_attr(docsrs, doc(cfg(feature = "opentelemetry")))]
+pub mod otel;
 mod plugin;
 pub mod sensitivity;
 mod service;
@@ -0,0 +1,30 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! Provides [`OtelOperation`] and [`OtelPlugin`], which create a span per operation carrying the
+//! [OpenTelemetry semantic conventions] and propagate the [W3C Trace Context] and [AWS X-Ray] trace headers.
+//!
+//! The spans are regular [`tracing`] spans: export them by installing a [`tracing_opentelemetry`] layer in your
+//! subscriber.
+//!
+//! # Example
+//!
+//! ```
+//! # use aws_smithy_http_server::plugin::HttpPlugins;
+//! use aws_smithy_http_server::instrumentation::otel::OtelExt;
+//!
+//! let http_plugins = HttpPlugins::new().otel();
+//! ```
+//!
+//! [OpenTelemetry semantic conventions]: https://opentelemetry.io/docs/specs/semconv/rpc/rpc-spans/
+//! [W3C Trace Context]: https://www.w3.org/TR/trace-context/
+//! [AWS X-Ray]: https://docs.aws.amazon.com/xray/latest/devguide/xray-concepts.html#xray-concepts-tracingheader
+
+mod plugin;
+pub mod propagation;
+mod service;
+
+pub use plugin::*;
+pub use service::*;
@@ -0,0 +1,47 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+use crate::instrumentation::sensitivity::Sensitivity;
+use crate::plugin::{HttpMarker, HttpPlugins, PluginStack};
+use crate::service::ServiceShape;
+use crate::{operation::OperationShape, plugin::Plugin};
+
+use super::OtelOperation;
+
+/// A [`Plugin`] which applies [`OtelOperation`] to every operation.
+#[derive(Debug)]
+pub struct OtelPlugin;
+
+impl<Ser, Op, T> Plugin<Ser, Op, T> for OtelPlugin
+where
+    Ser: ServiceShape,
+    Op: OperationShape,
+    Op: Sensitivity,
+{
+    type Output = OtelOperation<T, Op::RequestFmt, Op::ResponseFmt>;
+
+    fn apply(&self, input: T) -> Self::Output {
+        OtelOperation::new(input, Ser::ID, Op::ID)
+            .request_fmt(Op::request_fmt())
+            .response_fmt(Op::response_fmt())
+    }
+}
+
+impl HttpMarker for OtelPlugin {}
+
+/// An extension trait for applying [`OtelPlugin`].
+pub trait OtelExt<CurrentPlugin> {
+    /// Applies an [`OtelOperation`] to every operation, propagating the incoming trace context and respecting the
+    /// [@sensitive] trait given on the input and output models. See [`OtelOperation`] for more information.
+    ///
+    /// [@sensitive]: https://smithy.io/2.0/spec/documentation-traits.html#sensitive-trait
+    fn otel(self) -> HttpPlugins<PluginStack<OtelPlugin, CurrentPlugin>>;
+}
+
+impl<CurrentPlugin> OtelExt<CurrentPlugin> for HttpPlugins<CurrentPlugin> {
+    fn otel(self) -> HttpPlugins<PluginStack<OtelPlugin, CurrentPlugin>> {
+        self.push(OtelPlugin)
+    }
+}
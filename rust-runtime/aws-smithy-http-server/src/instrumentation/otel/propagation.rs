@@ -0,0 +1,337 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! Extraction and injection of trace context from and into HTTP headers.
+//!
+//! Two formats are supported:
+//!
+//! - [W3C Trace Context], using the `traceparent` and `tracestate` headers.
+//! - [AWS X-Ray], using the `X-Amzn-Trace-Id` header.
+//!
+//! When a request carries both, the W3C Trace Context takes precedence.
+//!
+//! [W3C Trace Context]: https://www.w3.org/TR/trace-context/
+//! [AWS X-Ray]: https://docs.aws.amazon.com/xray/latest/devguide/xray-concepts.html#xray-concepts-tracingheader
+
+use std::str::FromStr;
+
+use http::{header::HeaderName, HeaderMap, HeaderValue};
+use opentelemetry::trace::{SpanContext, SpanId, TraceFlags, TraceId, TraceState};
+
+/// The W3C `traceparent` header.
+pub const TRACEPARENT: HeaderName = HeaderName::from_static("traceparent");
+/// The W3C `tracestate` header.
+pub const TRACESTATE: HeaderName = HeaderName::from_static("tracestate");
+/// The AWS X-Ray `X-Amzn-Trace-Id` header.
+pub const X_AMZN_TRACE_ID: HeaderName = HeaderName::from_static("x-amzn-trace-id");
+
+const TRACEPARENT_VERSION: &str = "00";
+const XRAY_ROOT_VERSION: &str = "1";
+
+/// The format a trace context was propagated with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TraceFormat {
+    /// The [W3C Trace Context](https://www.w3.org/TR/trace-context/) format.
+    W3c,
+    /// The [AWS X-Ray](https://docs.aws.amazon.com/xray/latest/devguide/xray-concepts.html#xray-concepts-tracingheader) format.
+    XRay,
+}
+
+/// Extracts the remote trace context from the request `headers`.
+///
+/// Returns `None` if no trace header is present or if it is malformed. A malformed `tracestate` is
+/// ignored without discarding the `traceparent`, as mandated by the specification.
+pub fn extract(headers: &HeaderMap) -> Option<(SpanContext, TraceFormat)> {
+    if let Some(traceparent) = headers.get(TRACEPARENT) {
+        let tracestate = headers
+            .get_all(TRACESTATE)
+            .iter()
+            .map(|value| value.to_str())
+            .collect::<Result<Vec<_>, _>>()
+            .ok()
+            .and_then(|values| TraceState::from_str(&values.join(",")).ok())
+            .unwrap_or_default();
+        return traceparent
+            .to_str()
+            .ok()
+            .and_then(|traceparent| parse_traceparent(traceparent, tracestate))
+            .map(|span_context| (span_context, TraceFormat::W3c));
+    }
+
+    headers
+        .get(X_AMZN_TRACE_ID)
+        .and_then(|value| value.to_str().ok())
+        .and_then(parse_xray)
+        .map(|span_context| (span_context, TraceFormat::XRay))
+}
+
+/// Injects `span_context` into `headers` using the given `format`.
+///
+/// Invalid span contexts are not injected.
+pub fn inject(span_context: &SpanContext, format: TraceFormat, headers: &mut HeaderMap) {
+    if !span_context.is_valid() {
+        return;
+    }
+
+    match format {
+        TraceFormat::W3c => {
+            let traceparent = format!(
+                "{TRACEPARENT_VERSION}-{:032x}-{:016x}-{:02x}",
+                span_context.trace_id(),
+                span_context.span_id(),
+                span_context.trace_flags().to_u8()
+            );
+            headers.insert(TRACEPARENT, header_value(traceparent));
+
+            let tracestate = span_context.trace_state().header();
+            if !tracestate.is_empty() {
+                if let Ok(tracestate) = HeaderValue::from_str(&tracestate) {
+                    headers.insert(TRACESTATE, tracestate);
+                }
+            }
+        }
+        TraceFormat::XRay => {
+            let trace_id = format!("{:032x}", span_context.trace_id());
+            let (epoch, unique) = trace_id.split_at(8);
+            let xray = format!(
+                "Root={XRAY_ROOT_VERSION}-{epoch}-{unique};Parent={:016x};Sampled={}",
+                span_context.span_id(),
+                if span_context.is_sampled() { 1 } else { 0 }
+            );
+            headers.insert(X_AMZN_TRACE_ID, header_value(xray));
+        }
+    }
+}
+
+// Note on `expect`: the values are built from lowercase hexadecimal digits and ASCII separators.
+fn header_value(value: String) -> HeaderValue {
+    HeaderValue::try_from(value).expect("trace headers only contain visible ASCII characters")
+}
+
+/// Returns `true` if `value` is made of exactly `len` lowercase hexadecimal digits.
+fn is_lower_hex(value: &str, len: usize) -> bool {
+    value.len() == len && value.bytes().all(|b| b.is_ascii_digit() || (b'a'..=b'f').contains(&b))
+}
+
+/// Parses a `traceparent` header, e.g. `00-0af7651916cd43dd8448eb211c80319c-b7ad6b7169203331-01`.
+fn parse_traceparent(traceparent: &str, tracestate: TraceState) -> Option<SpanContext> {
+    let mut parts = traceparent.trim().split('-');
+    let version = parts.next()?;
+    let trace_id = parts.next()?;
+    let span_id = parts.next()?;
+    let flags = parts.next()?;
+
+    // Version `ff` is forbidden. Future versions may append fields, the current version may not.
+    if !is_lower_hex(version, 2) || version == "ff" {
+        return None;
+    }
+    if version == TRACEPARENT_VERSION && parts.next().is_some() {
+        return None;
+    }
+    if !is_lower_hex(trace_id, 32) || !is_lower_hex(span_id, 16) || !is_lower_hex(flags, 2) {
+        return None;
+    }
+
+    let trace_id = TraceId::from_hex(trace_id).ok()?;
+    let span_id = SpanId::from_hex(span_id).ok()?;
+    let flags = u8::from_str_radix(flags, 16).ok()?;
+    let span_context = SpanContext::new(
+        trace_id,
+        span_id,
+        TraceFlags::new(flags) & TraceFlags::SAMPLED,
+        true,
+        tracestate,
+    );
+    span_context.is_valid().then_some(span_context)
+}
+
+/// Parses a `X-Amzn-Trace-Id` header, e.g.
+/// `Root=1-5759e988-bd862e3fe1be46a994272793;Parent=53995c3f42cd8ad8;Sampled=1`.
+///
+/// The header must carry a `Parent` to be used as a remote parent.
+fn parse_xray(header: &str) -> Option<SpanContext> {
+    let mut trace_id = None;
+    let mut span_id = None;
+    let mut flags = TraceFlags::default();
+
+    for field in header.split(';').map(str::trim).filter(|field| !field.is_empty()) {
+        let (key, value) = field.split_once('=')?;
+        match key {
+            "Root" => {
+                let mut root = value.split('-');
+                let (version, epoch, unique) = (root.next()?, root.next()?, root.next()?);
+                if version != XRAY_ROOT_VERSION
+                    || root.next().is_some()
+                    || !is_lower_hex(epoch, 8)
+                    || !is_lower_hex(unique, 24)
+                {
+                    return None;
+                }
+                trace_id = Some(TraceId::from_hex(&format!("{epoch}{unique}")).ok()?);
+            }
+            "Parent" => {
+                if !is_lower_hex(value, 16) {
+                    return None;
+                }
+                span_id = Some(SpanId::from_hex(value).ok()?);
+            }
+            "Sampled" if value == "1" => flags = TraceFlags::SAMPLED,
+            // Other fields, such as `Lineage` or custom data, are not relevant to the trace context.
+            _ => {}
+        }
+    }
+
+    let span_context = SpanContext::new(trace_id?, span_id?, flags, true, TraceState::default());
+    span_context.is_valid().then_some(span_context)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn headers(entries: &[(HeaderName, &str)]) -> HeaderMap {
+        let mut headers = HeaderMap::new();
+        for (name, value) in entries {
+            headers.append(name, HeaderValue::from_str(value).unwrap());
+        }
+        headers
+    }
+
+    #[test]
+    fn extract_traceparent() {
+        let headers = headers(&[
+            (TRACEPARENT, "00-0af7651916cd43dd8448eb211c80319c-b7ad6b7169203331-01"),
+            (TRACESTATE, "congo=t61rcWkgMzE"),
+            (TRACESTATE, "rojo=00f067aa0ba902b7"),
+        ]);
+        let (span_context, format) = extract(&headers).unwrap();
+
+        assert_eq!(format, TraceFormat::W3c);
+        assert_eq!(
+            span_context.trace_id(),
+            TraceId::from_hex("0af7651916cd43dd8448eb211c80319c").unwrap()
+        );
+        assert_eq!(span_context.span_id(), SpanId::from_hex("b7ad6b7169203331").unwrap());
+        assert!(span_context.is_sampled());
+        assert!(span_context.is_remote());
+        assert_eq!(span_context.trace_state().get("congo"), Some("t61rcWkgMzE"));
+        assert_eq!(span_context.trace_state().get("rojo"), Some("00f067aa0ba902b7"));
+    }
+
+    #[test]
+    fn extract_invalid_traceparent() {
+        let invalid = [
+            "",
+            "00-0af7651916cd43dd8448eb211c80319c-b7ad6b7169203331",
+            "00-0af7651916cd43dd8448eb211c80319c-b7ad6b7169203331-01-extra",
+            "ff-0af7651916cd43dd8448eb211c80319c-b7ad6b7169203331-01",
+            "00-0AF7651916CD43DD8448EB211C80319C-b7ad6b7169203331-01",
+            "00-00000000000000000000000000000000-b7ad6b7169203331-01",
+            "00-0af7651916cd43dd8448eb211c80319c-0000000000000000-01",
+            "00-+af7651916cd43dd8448eb211c80319c-b7ad6b7169203331-01",
+            "00-0af7651916cd43dd8448eb211c80319c-b7ad6b716920333-01",
+        ];
+        for traceparent in invalid {
+            let headers = headers(&[(TRACEPARENT, traceparent)]);
+            assert!(extract(&headers).is_none(), "{traceparent}");
+        }
+    }
+
+    #[test]
+    fn extract_future_traceparent_version() {
+        let headers = headers(&[(
+            TRACEPARENT,
+            "cc-0af7651916cd43dd8448eb211c80319c-b7ad6b7169203331-00-extra",
+        )]);
+        let (span_context, _) = extract(&headers).unwrap();
+        assert!(!span_context.is_sampled());
+    }
+
+    #[test]
+    fn invalid_tracestate_is_ignored() {
+        let headers = headers(&[
+            (TRACEPARENT, "00-0af7651916cd43dd8448eb211c80319c-b7ad6b7169203331-01"),
+            (TRACESTATE, "not a valid tracestate"),
+        ]);
+        let (span_context, _) = extract(&headers).unwrap();
+        assert_eq!(span_context.trace_state().header(), "");
+    }
+
+    #[test]
+    fn extract_xray() {
+        let headers = headers(&[(
+            X_AMZN_TRACE_ID,
+            "Root=1-5759e988-bd862e3fe1be46a994272793; Parent=53995c3f42cd8ad8; Sampled=1; Lineage=a87bd80c:0",
+        )]);
+        let (span_context, format) = extract(&headers).unwrap();
+
+        assert_eq!(format, TraceFormat::XRay);
+        assert_eq!(
+            span_context.trace_id(),
+            TraceId::from_hex("5759e988bd862e3fe1be46a994272793").unwrap()
+        );
+        assert_eq!(span_context.span_id(), SpanId::from_hex("53995c3f42cd8ad8").unwrap());
+        assert!(span_context.is_sampled());
+    }
+
+    #[test]
+    fn extract_xray_without_parent() {
+        let headers = headers(&[(X_AMZN_TRACE_ID, "Root=1-5759e988-bd862e3fe1be46a994272793;Sampled=1")]);
+        assert!(extract(&headers).is_none());
+    }
+
+    #[test]
+    fn traceparent_takes_precedence_over_xray() {
+        let headers = headers(&[
+            (
+                X_AMZN_TRACE_ID,
+                "Root=1-5759e988-bd862e3fe1be46a994272793;Parent=53995c3f42cd8ad8;Sampled=1",
+            ),
+            (TRACEPARENT, "00-0af7651916cd43dd8448eb211c80319c-b7ad6b7169203331-01"),
+        ]);
+        let (_, format) = extract(&headers).unwrap();
+        assert_eq!(format, TraceFormat::W3c);
+    }
+
+    #[test]
+    fn inject_round_trips() {
+        let inputs = [
+            (TRACEPARENT, "00-0af7651916cd43dd8448eb211c80319c-b7ad6b7169203331-01"),
+            (
+                X_AMZN_TRACE_ID,
+                "Root=1-5759e988-bd862e3fe1be46a994272793;Parent=53995c3f42cd8ad8;Sampled=0",
+            ),
+        ];
+        for (name, value) in inputs {
+            let (span_context, format) = extract(&headers(&[(name.clone(), value)])).unwrap();
+            let mut injected = HeaderMap::new();
+            inject(&span_context, format, &mut injected);
+            assert_eq!(injected.len(), 1);
+            assert_eq!(injected.get(name).unwrap(), value);
+        }
+    }
+
+    #[test]
+    fn inject_tracestate() {
+        let tracestate = TraceState::from_key_value([("rojo", "00f067aa0ba902b7")]).unwrap();
+        let span_context = SpanContext::new(
+            TraceId::from_hex("0af7651916cd43dd8448eb211c80319c").unwrap(),
+            SpanId::from_hex("b7ad6b7169203331").unwrap(),
+            TraceFlags::SAMPLED,
+            false,
+            tracestate,
+        );
+        let mut headers = HeaderMap::new();
+        inject(&span_context, TraceFormat::W3c, &mut headers);
+        assert_eq!(headers.get(TRACESTATE).unwrap(), "rojo=00f067aa0ba902b7");
+    }
+
+    #[test]
+    fn invalid_span_context_is_not_injected() {
+        let mut headers = HeaderMap::new();
+        inject(&SpanContext::empty_context(), TraceFormat::W3c, &mut headers);
+        assert!(headers.is_empty());
+    }
+}
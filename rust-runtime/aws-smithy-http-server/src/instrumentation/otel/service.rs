@@ -0,0 +1,303 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! A [`Service`] and it's associated [`Future`] creating OpenTelemetry-compatible spans.
+
+use std::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+use futures_util::{ready, TryFuture};
+use http::{Request, Response, StatusCode, Uri};
+use opentelemetry::trace::{SpanContext, TraceContextExt};
+use tower::Service;
+use tracing::{field, info_span, instrument::Instrumented, Instrument, Span};
+use tracing_opentelemetry::OpenTelemetrySpanExt;
+
+use crate::shape_id::ShapeId;
+
+use super::super::{MakeDisplay, MakeIdentity};
+use super::propagation::{self, TraceFormat};
+
+pin_project_lite::pin_project! {
+    /// A [`Future`] recording the response status code and injecting the trace context into the response.
+    pub struct OtelFuture<Fut, ResponseMakeFmt> {
+        #[pin]
+        inner: Instrumented<Fut>,
+        make: ResponseMakeFmt,
+        span: Span,
+        parent: Option<(SpanContext, TraceFormat)>,
+    }
+}
+
+impl<Fut, ResponseMakeFmt, T> Future for OtelFuture<Fut, ResponseMakeFmt>
+where
+    Fut: TryFuture<Ok = Response<T>>,
+    Fut: Future<Output = Result<Fut::Ok, Fut::Error>>,
+
+    ResponseMakeFmt: MakeDisplay<StatusCode>,
+{
+    type Output = Fut::Output;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.project();
+        let mut response = ready!(this.inner.poll(cx))?;
+
+        let status_code = this.make.make_display(response.status());
+        this.span
+            .record("http.response.status_code", field::display(status_code));
+
+        // Without an OpenTelemetry layer installed the span has no context of its own, in which case
+        // the incoming trace context is propagated untouched.
+        let current = this.span.context().span().span_context().clone();
+        let parent = this.parent.take();
+        let format = parent.as_ref().map_or(TraceFormat::W3c, |(_, format)| *format);
+        let span_context = if current.is_valid() {
+            Some(current)
+        } else {
+            parent.map(|(parent, _)| parent)
+        };
+        if let Some(span_context) = span_context {
+            propagation::inject(&span_context, format, response.headers_mut());
+        }
+
+        Poll::Ready(Ok(response))
+    }
+}
+
+/// A middleware [`Service`] responsible for:
+///   - Extracting the W3C Trace Context or AWS X-Ray trace context from the request headers and using it as the parent
+///     of the request span.
+///   - Opening a [`tracing::info_span`] for the lifetime of the request, recording the [OpenTelemetry semantic
+///     conventions] for RPC servers (`rpc.system`, `rpc.service` and `rpc.method`), the HTTP method, the [`Uri`] and
+///     the response status code.
+///   - Injecting the trace context into the response headers, using the format of the incoming request.
+///
+/// Spans are exported to OpenTelemetry by installing a [`tracing_opentelemetry`] layer in the subscriber.
+///
+/// The [`Display`](std::fmt::Display) of the [`Uri`] and of the status code can be modified using
+/// [`request_fmt`](OtelOperation::request_fmt) and [`response_fmt`](OtelOperation::response_fmt), to respect the
+/// [@sensitive] trait.
+///
+/// # Example
+///
+/// ```
+/// # use aws_smithy_http_server::instrumentation::{otel::*, sensitivity::*};
+/// # use aws_smithy_http_server::shape_id::ShapeId;
+/// # use tower::{Service, service_fn};
+/// # use http::{Request, Response};
+/// # async fn f(request: Request<()>) -> Result<Response<()>, ()> { Ok(Response::new(())) }
+/// # let mut svc = service_fn(f);
+/// # const SERVICE_ID: ShapeId = ShapeId::new("namespace#FooService", "namespace", "FooService");
+/// # const ID: ShapeId = ShapeId::new("namespace#FooOperation", "namespace", "FooOperation");
+/// let request_fmt = RequestFmt::new().label(|index| index == 1, None);
+/// let response_fmt = ResponseFmt::new().status_code();
+/// let mut svc = OtelOperation::new(svc, SERVICE_ID, ID)
+///     .request_fmt(request_fmt)
+///     .response_fmt(response_fmt);
+/// # svc.call(Request::new(()));
+/// ```
+///
+/// [OpenTelemetry semantic conventions]: https://opentelemetry.io/docs/specs/semconv/rpc/rpc-spans/
+/// [@sensitive]: https://smithy.io/2.0/spec/documentation-traits.html#sensitive-trait
+#[derive(Debug, Clone)]
+pub struct OtelOperation<S, RequestMakeFmt = MakeIdentity, ResponseMakeFmt = MakeIdentity> {
+    inner: S,
+    service_id: ShapeId,
+    operation_id: ShapeId,
+    make_request: RequestMakeFmt,
+    make_response: ResponseMakeFmt,
+}
+
+impl<S> OtelOperation<S> {
+    /// Constructs a new [`OtelOperation`] with no data redacted.
+    pub fn new(inner: S, service_id: ShapeId, operation_id: ShapeId) -> Self {
+        Self {
+            inner,
+            service_id,
+            operation_id,
+            make_request: MakeIdentity,
+            make_response: MakeIdentity,
+        }
+    }
+}
+
+impl<S, RequestMakeFmt, ResponseMakeFmt> OtelOperation<S, RequestMakeFmt, ResponseMakeFmt> {
+    /// Configures the request format.
+    ///
+    /// The argument is typically [`RequestFmt`](crate::instrumentation::sensitivity::RequestFmt).
+    pub fn request_fmt<R>(self, make_request: R) -> OtelOperation<S, R, ResponseMakeFmt> {
+        OtelOperation {
+            inner: self.inner,
+            service_id: self.service_id,
+            operation_id: self.operation_id,
+            make_request,
+            make_response: self.make_response,
+        }
+    }
+
+    /// Configures the response format.
+    ///
+    /// The argument is typically [`ResponseFmt`](crate::instrumentation::sensitivity::ResponseFmt).
+    pub fn response_fmt<R>(self, make_response: R) -> OtelOperation<S, RequestMakeFmt, R> {
+        OtelOperation {
+            inner: self.inner,
+            service_id: self.service_id,
+            operation_id: self.operation_id,
+            make_request: self.make_request,
+            make_response,
+        }
+    }
+}
+
+impl<S, U, V, RequestMakeFmt, ResponseMakeFmt> Service<Request<U>> for OtelOperation<S, RequestMakeFmt, ResponseMakeFmt>
+where
+    S: Service<Request<U>, Response = Response<V>>,
+
+    for<'a> RequestMakeFmt: MakeDisplay<&'a Uri>,
+
+    ResponseMakeFmt: Clone,
+    ResponseMakeFmt: MakeDisplay<StatusCode>,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = OtelFuture<S::Future, ResponseMakeFmt>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Request<U>) -> Self::Future {
+        let parent = propagation::extract(request.headers());
+        let span = {
+            let uri = self.make_request.make_display(request.uri());
+            info_span!(
+                "request",
+                otel.name = %format_args!("{}/{}", self.service_id.absolute(), self.operation_id.name()),
+                otel.kind = "server",
+                rpc.system = "smithy",
+                rpc.service = %self.service_id.absolute(),
+                rpc.method = %self.operation_id.name(),
+                http.request.method = %request.method(),
+                http.target = %uri,
+                http.response.status_code = field::Empty,
+            )
+        };
+        if let Some((parent, _)) = &parent {
+            span.set_parent(opentelemetry::Context::new().with_remote_span_context(parent.clone()));
+        }
+
+        OtelFuture {
+            inner: self.inner.call(request).instrument(span.clone()),
+            make: self.make_response.clone(),
+            span,
+            parent,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::convert::Infallible;
+
+    use http::{HeaderValue, Request, Response};
+    use opentelemetry::trace::{SpanId, TraceId, TracerProvider as _};
+    use opentelemetry_sdk::trace::TracerProvider;
+    use tower::{service_fn, ServiceExt};
+    use tracing_subscriber::layer::SubscriberExt;
+
+    use super::*;
+    use crate::instrumentation::otel::propagation::{TRACEPARENT, X_AMZN_TRACE_ID};
+
+    const SERVICE_ID: ShapeId = ShapeId::new("namespace#FooService", "namespace", "FooService");
+    const OPERATION_ID: ShapeId = ShapeId::new("namespace#FooOperation", "namespace", "FooOperation");
+
+    const TRACE_ID: &str = "0af7651916cd43dd8448eb211c80319c";
+    const PARENT_ID: &str = "b7ad6b7169203331";
+
+    async fn call(request: Request<()>) -> Response<()> {
+        let svc = service_fn(|_request: Request<()>| async {
+            // The handler runs within the request span, parented to the remote context.
+            let context = Span::current().context();
+            let span_context = context.span().span_context().clone();
+            Ok::<_, Infallible>(
+                Response::builder()
+                    .header("x-handler-trace-id", format!("{:032x}", span_context.trace_id()))
+                    .body(())
+                    .unwrap(),
+            )
+        });
+        OtelOperation::new(svc, SERVICE_ID, OPERATION_ID)
+            .oneshot(request)
+            .await
+            .unwrap()
+    }
+
+    fn traceparent_request() -> Request<()> {
+        Request::builder()
+            .header(TRACEPARENT, format!("00-{TRACE_ID}-{PARENT_ID}-01"))
+            .body(())
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn propagates_incoming_context_without_opentelemetry_layer() {
+        let response = call(traceparent_request()).await;
+        assert_eq!(
+            response.headers().get(TRACEPARENT).unwrap(),
+            HeaderValue::from_str(&format!("00-{TRACE_ID}-{PARENT_ID}-01")).unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn no_trace_context_without_incoming_context() {
+        let response = call(Request::new(())).await;
+        assert!(response.headers().get(TRACEPARENT).is_none());
+        assert!(response.headers().get(X_AMZN_TRACE_ID).is_none());
+    }
+
+    #[tokio::test]
+    async fn request_span_is_parented_to_incoming_context() {
+        let provider = TracerProvider::builder().build();
+        let subscriber =
+            tracing_subscriber::registry().with(tracing_opentelemetry::layer().with_tracer(provider.tracer("test")));
+        let _guard = tracing::subscriber::set_default(subscriber);
+
+        let response = call(traceparent_request()).await;
+
+        assert_eq!(response.headers().get("x-handler-trace-id").unwrap(), TRACE_ID);
+        let (span_context, format) = propagation::extract(response.headers()).unwrap();
+        assert_eq!(format, TraceFormat::W3c);
+        assert_eq!(span_context.trace_id(), TraceId::from_hex(TRACE_ID).unwrap());
+        // The server span is injected, not the incoming parent.
+        assert_ne!(span_context.span_id(), SpanId::from_hex(PARENT_ID).unwrap());
+        assert!(span_context.is_sampled());
+    }
+
+    #[tokio::test]
+    async fn xray_context_is_injected_as_xray() {
+        let provider = TracerProvider::builder().build();
+        let subscriber =
+            tracing_subscriber::registry().with(tracing_opentelemetry::layer().with_tracer(provider.tracer("test")));
+        let _guard = tracing::subscriber::set_default(subscriber);
+
+        let request = Request::builder()
+            .header(
+                X_AMZN_TRACE_ID,
+                "Root=1-5759e988-bd862e3fe1be46a994272793;Parent=53995c3f42cd8ad8;Sampled=1",
+            )
+            .body(())
+            .unwrap();
+        let response = call(request).await;
+
+        assert!(response.headers().get(TRACEPARENT).is_none());
+        let xray = response.headers().get(X_AMZN_TRACE_ID).unwrap().to_str().unwrap();
+        assert!(xray.starts_with("Root=1-5759e988-bd862e3fe1be46a994272793;Parent="));
+        assert!(!xray.contains("53995c3f42cd8ad8"));
+        assert!(xray.ends_with(";Sampled=1"));
+    }
+}
@@ -92,3 +92,20 @@ macro_rules! convert_to_request_rejection {
         }
     };
 }
+
+// Converts type-erased body errors into a `RequestRejection`. Bodies limited by
+// `crate::body_limit::RequestBodyLimitPlugin` fail with a `http_body::LengthLimitError` once the
+// limit is exceeded, which is rejected with `PayloadTooLarge` instead of `BufferHttpBodyBytes`.
+macro_rules! convert_box_error_to_request_rejection {
+    () => {
+        impl From<Box<dyn std::error::Error + Send + Sync + 'static>> for RequestRejection {
+            fn from(err: Box<dyn std::error::Error + Send + Sync + 'static>) -> Self {
+                if err.is::<http_body::LengthLimitError>() {
+                    Self::PayloadTooLarge
+                } else {
+                    Self::BufferHttpBodyBytes(crate::Error::new(err))
+                }
+            }
+        }
+    };
+}
@@ -19,6 +19,8 @@ pub enum ResponseRejection {
 pub enum RequestRejection {
     #[error("error converting non-streaming body to bytes: {0}")]
     BufferHttpBodyBytes(crate::Error),
+    #[error("request body exceeds the maximum allowed size")]
+    PayloadTooLarge,
     #[error("request contains invalid value for `Accept` header")]
     NotAcceptable,
     #[error("expected `Content-Type` header not found: {0}")]
@@ -40,4 +42,4 @@ impl From<std::convert::Infallible> for RequestRejection {
 }
 
 convert_to_request_rejection!(hyper::Error, BufferHttpBodyBytes);
-convert_to_request_rejection!(Box<dyn std::error::Error + Send + Sync + 'static>, BufferHttpBodyBytes);
+convert_box_error_to_request_rejection!();
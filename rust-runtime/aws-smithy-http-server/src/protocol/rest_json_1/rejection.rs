@@ -110,6 +110,13 @@ pub enum RequestRejection {
     #[error("error converting non-streaming body to bytes: {0}")]
     BufferHttpBodyBytes(crate::Error),
 
+    /// Used when the request body is larger than the maximum size configured with
+    /// [`crate::body_limit::RequestBodyLimitPlugin`]. Requests declaring a larger `Content-Length`
+    /// are rejected before reading the body; other bodies are aborted as soon as the limit is
+    /// exceeded.
+    #[error("request body exceeds the maximum allowed size")]
+    PayloadTooLarge,
+
     /// Used when the request contained an `Accept` header with a MIME type, and the server cannot
     /// return a response body adhering to that MIME type.
     #[error("request contains invalid value for `Accept` header")]
@@ -209,4 +216,4 @@ convert_to_request_rejection!(hyper::Error, BufferHttpBodyBytes);
 
 // Useful in general, but it also required in order to accept Lambda HTTP requests using
 // `Router<lambda_http::Body>` since `lambda_http::Error` is a type alias for `Box<dyn Error + ..>`.
-convert_to_request_rejection!(Box<dyn std::error::Error + Send + Sync + 'static>, BufferHttpBodyBytes);
+convert_box_error_to_request_rejection!();
@@ -29,6 +29,9 @@ pub enum RequestRejection {
     #[error("error converting non-streaming body to bytes: {0}")]
     BufferHttpBodyBytes(crate::Error),
 
+    #[error("request body exceeds the maximum allowed size")]
+    PayloadTooLarge,
+
     #[error("request contains invalid value for `Accept` header")]
     NotAcceptable,
 
@@ -78,4 +81,4 @@ impl From<nom::Err<nom::error::Error<&str>>> for RequestRejection {
 }
 
 convert_to_request_rejection!(hyper::Error, BufferHttpBodyBytes);
-convert_to_request_rejection!(Box<dyn std::error::Error + Send + Sync + 'static>, BufferHttpBodyBytes);
+convert_box_error_to_request_rejection!();
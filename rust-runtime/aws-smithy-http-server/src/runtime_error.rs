@@ -10,4 +10,11 @@
 /// [`crate::protocol::rest_json_1::runtime_error::RuntimeError::InternalFailure`] variant.
 pub struct InternalFailureException;
 
+/// A _protocol-agnostic_ type representing a request whose body exceeds the configured size limit.
+/// This is returned by [`crate::body_limit::RequestBodyLimit`].
+/// This type is converted into protocol-specific error variants. For example, in the
+/// [`crate::protocol::rest_json_1`] protocol, it is converted to the
+/// [`crate::protocol::rest_json_1::runtime_error::RuntimeError::PayloadTooLarge`] variant.
+pub struct PayloadTooLargeException;
+
 pub const INVALID_HTTP_RESPONSE_FOR_RUNTIME_ERROR_PANIC_MESSAGE: &str = "invalid HTTP response for `RuntimeError`; please file a bug report under https://github.com/smithy-lang/smithy-rs/issues";
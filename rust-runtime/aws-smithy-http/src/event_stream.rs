@@ -7,8 +7,14 @@
 
 use std::error::Error as StdError;
 
+#[cfg(feature = "event-stream-channel")]
+mod channel;
 mod receiver;
 mod sender;
+#[cfg(feature = "event-stream-test-util")]
+pub mod test_util;
+#[cfg(feature = "event-stream-websocket")]
+pub mod websocket;
 
 /// A generic, boxed error that's `Send`, `Sync`, and `'static`.
 pub type BoxError = Box<dyn StdError + Send + Sync + 'static>;
@@ -16,5 +22,13 @@ pub type BoxError = Box<dyn StdError + Send + Sync + 'static>;
 #[doc(inline)]
 pub use sender::{EventStreamSender, MessageStreamAdapter, MessageStreamError};
 
+#[cfg(feature = "event-stream-channel")]
+#[doc(inline)]
+pub use sender::Heartbeat;
+
+#[cfg(feature = "event-stream-channel")]
+#[doc(inline)]
+pub use channel::{EventStreamSenderHandle, SendBufferMetrics, SendError};
+
 #[doc(inline)]
 pub use receiver::{Receiver, ReceiverError};
@@ -0,0 +1,252 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! A bounded channel that feeds an [`EventStreamSender`](super::EventStreamSender).
+
+use futures_core::Stream;
+use std::error::Error as StdError;
+use std::fmt;
+use std::marker::PhantomData;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use tokio::sync::mpsc;
+use tokio::sync::mpsc::error::TrySendError;
+
+pub(super) enum Command<T> {
+    Event(T),
+    Close,
+}
+
+impl<T> Command<T> {
+    fn into_event(self) -> Option<T> {
+        match self {
+            Command::Event(event) => Some(event),
+            Command::Close => None,
+        }
+    }
+}
+
+pub(super) fn channel<T, E>(capacity: usize) -> (ChannelStream<T, E>, EventStreamSenderHandle<T>) {
+    let (tx, rx) = mpsc::channel(capacity);
+    (
+        ChannelStream {
+            rx,
+            done: false,
+            _phantom: PhantomData,
+        },
+        EventStreamSenderHandle {
+            tx,
+            capacity,
+            blocked_sends: Default::default(),
+        },
+    )
+}
+
+/// Sends events to an [`EventStreamSender`](super::EventStreamSender) created with
+/// [`EventStreamSender::channel`](super::EventStreamSender::channel).
+///
+/// The handle can be cloned to send events from multiple producers concurrently. Events are
+/// buffered up to the capacity of the channel, after which [`send`](Self::send) waits until the
+/// stream has room for them.
+pub struct EventStreamSenderHandle<T> {
+    tx: mpsc::Sender<Command<T>>,
+    capacity: usize,
+    blocked_sends: Arc<AtomicU64>,
+}
+
+impl<T> Clone for EventStreamSenderHandle<T> {
+    fn clone(&self) -> Self {
+        Self {
+            tx: self.tx.clone(),
+            capacity: self.capacity,
+            blocked_sends: self.blocked_sends.clone(),
+        }
+    }
+}
+
+impl<T> fmt::Debug for EventStreamSenderHandle<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name_t = std::any::type_name::<T>();
+        write!(f, "EventStreamSenderHandle<{name_t}>")
+    }
+}
+
+impl<T> EventStreamSenderHandle<T> {
+    /// Sends an event, waiting for room in the send buffer if it's full.
+    ///
+    /// Returns an error containing the event if the stream has been closed.
+    pub async fn send(&self, event: T) -> Result<(), SendError<T>> {
+        let command = match self.tx.try_send(Command::Event(event)) {
+            Ok(()) => return Ok(()),
+            Err(TrySendError::Closed(command)) => {
+                return Err(SendError::closed(command.into_event()))
+            }
+            Err(TrySendError::Full(command)) => command,
+        };
+        self.blocked_sends.fetch_add(1, Ordering::Relaxed);
+        self.tx
+            .send(command)
+            .await
+            .map_err(|err| SendError::closed(err.0.into_event()))
+    }
+
+    /// Sends an event if there's room for it in the send buffer.
+    ///
+    /// Returns an error containing the event if the send buffer is full or the stream has been
+    /// closed.
+    pub fn try_send(&self, event: T) -> Result<(), SendError<T>> {
+        self.tx
+            .try_send(Command::Event(event))
+            .map_err(|err| match err {
+                TrySendError::Full(command) => SendError::full(command.into_event()),
+                TrySendError::Closed(command) => SendError::closed(command.into_event()),
+            })
+    }
+
+    /// Closes the stream.
+    ///
+    /// The stream ends once the events that have already been sent are sent, which sends the
+    /// signed empty message that terminates the event stream. Sending events after the stream is
+    /// closed fails. The stream also closes once every handle has been dropped.
+    pub async fn close(&self) -> Result<(), SendError<T>> {
+        self.tx
+            .send(Command::Close)
+            .await
+            .map_err(|_| SendError::closed(None))
+    }
+
+    /// Returns true if the stream has been closed, or has ended.
+    pub fn is_closed(&self) -> bool {
+        self.tx.is_closed()
+    }
+
+    /// Returns a snapshot of the send buffer metrics.
+    pub fn metrics(&self) -> SendBufferMetrics {
+        SendBufferMetrics {
+            buffered: self.capacity - self.tx.capacity(),
+            capacity: self.capacity,
+            blocked_sends: self.blocked_sends.load(Ordering::Relaxed),
+        }
+    }
+}
+
+/// Metrics of the send buffer of an [`EventStreamSenderHandle`]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+#[non_exhaustive]
+pub struct SendBufferMetrics {
+    buffered: usize,
+    capacity: usize,
+    blocked_sends: u64,
+}
+
+impl SendBufferMetrics {
+    /// Number of events waiting in the send buffer
+    pub fn buffered(&self) -> usize {
+        self.buffered
+    }
+
+    /// Maximum number of events that can wait in the send buffer
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Number of sends that had to wait because the send buffer was full
+    ///
+    /// A steadily increasing count means that events are produced faster than they can be sent.
+    pub fn blocked_sends(&self) -> u64 {
+        self.blocked_sends
+    }
+}
+
+#[derive(Debug)]
+enum SendErrorKind {
+    Full,
+    Closed,
+}
+
+/// An error returned when an event can't be sent with an [`EventStreamSenderHandle`]
+pub struct SendError<T> {
+    kind: SendErrorKind,
+    event: Option<T>,
+}
+
+impl<T> SendError<T> {
+    fn full(event: Option<T>) -> Self {
+        Self {
+            kind: SendErrorKind::Full,
+            event,
+        }
+    }
+
+    fn closed(event: Option<T>) -> Self {
+        Self {
+            kind: SendErrorKind::Closed,
+            event,
+        }
+    }
+
+    /// Returns true if the event wasn't sent because the send buffer was full
+    pub fn is_full(&self) -> bool {
+        matches!(self.kind, SendErrorKind::Full)
+    }
+
+    /// Returns true if the event wasn't sent because the stream has been closed
+    pub fn is_closed(&self) -> bool {
+        matches!(self.kind, SendErrorKind::Closed)
+    }
+
+    /// Returns the event that wasn't sent, if any
+    pub fn into_event(self) -> Option<T> {
+        self.event
+    }
+}
+
+impl<T> fmt::Debug for SendError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SendError")
+            .field("kind", &self.kind)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<T> fmt::Display for SendError<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.kind {
+            SendErrorKind::Full => write!(f, "the event stream send buffer is full"),
+            SendErrorKind::Closed => write!(f, "the event stream has been closed"),
+        }
+    }
+}
+
+impl<T> StdError for SendError<T> {}
+
+/// The stream of events sent with an [`EventStreamSenderHandle`]
+pub(super) struct ChannelStream<T, E> {
+    rx: mpsc::Receiver<Command<T>>,
+    done: bool,
+    // Events are never errors, but the stream has the item type of an `EventStreamSender`
+    _phantom: PhantomData<fn() -> E>,
+}
+
+impl<T, E> Stream for ChannelStream<T, E> {
+    type Item = Result<T, E>;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        if self.done {
+            return Poll::Ready(None);
+        }
+        match self.rx.poll_recv(cx) {
+            Poll::Ready(Some(Command::Event(event))) => Poll::Ready(Some(Ok(event))),
+            Poll::Ready(Some(Command::Close)) | Poll::Ready(None) => {
+                self.done = true;
+                self.rx.close();
+                Poll::Ready(None)
+            }
+            Poll::Pending => Poll::Pending,
+        }
+    }
+}
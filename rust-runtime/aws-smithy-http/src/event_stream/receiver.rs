@@ -4,7 +4,8 @@
  */
 
 use aws_smithy_eventstream::frame::{
-    DecodedFrame, MessageFrameDecoder, UnmarshallMessage, UnmarshalledMessage,
+    DecodedFrame, MessageFrameDecoder, UnmarshallMessage, UnmarshalledMessage, VerifyMessage,
+    VerifyMessageError,
 };
 use aws_smithy_runtime_api::client::result::{ConnectorError, SdkError};
 use aws_smithy_types::body::SdkBody;
@@ -42,6 +43,14 @@ impl RecvBuf {
         }
     }
 
+    /// Returns the number of buffered bytes that haven't been decoded yet.
+    fn buffered_len(&self) -> usize {
+        match self {
+            RecvBuf::Empty | RecvBuf::Terminated => 0,
+            RecvBuf::Partial(segments) | RecvBuf::EosPartial(segments) => segments.remaining(),
+        }
+    }
+
     /// Returns true if the stream has ended.
     fn is_eos(&self) -> bool {
         matches!(self, RecvBuf::EosPartial(_) | RecvBuf::Terminated)
@@ -109,6 +118,22 @@ impl fmt::Display for ReceiverError {
 
 impl StdError for ReceiverError {}
 
+/// Verifies the received messages, and optionally converts verification failures into modeled
+/// errors.
+struct Verifier<E> {
+    verifier: Box<dyn VerifyMessage + Send + Sync>,
+    into_error: Option<Box<dyn Fn(VerifyMessageError) -> E + Send + Sync>>,
+}
+
+impl<E> fmt::Debug for Verifier<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Verifier")
+            .field("verifier", &self.verifier)
+            .field("into_error", &self.into_error.as_ref().map(|_| "** fn **"))
+            .finish()
+    }
+}
+
 /// Receives Smithy-modeled messages out of an Event Stream.
 #[derive(Debug)]
 pub struct Receiver<T, E> {
@@ -121,6 +146,7 @@ pub struct Receiver<T, E> {
     /// initial response, then the message will be stored in `buffered_message` so that it can
     /// be returned with the next call of `recv()`.
     buffered_message: Option<Message>,
+    verifier: Option<Verifier<E>>,
     _phantom: PhantomData<E>,
 }
 
@@ -136,10 +162,62 @@ impl<T, E> Receiver<T, E> {
             buffer: RecvBuf::Empty,
             body,
             buffered_message: None,
+            verifier: None,
             _phantom: Default::default(),
         }
     }
 
+    /// Sets a verifier for the signatures of the received messages.
+    ///
+    /// Every received message is verified before it's unmarshalled, and the signed messages are
+    /// unwrapped. If a message fails verification, the stream is terminated with a
+    /// `ResponseError` whose source is the verification error.
+    pub fn set_verifier(&mut self, verifier: impl VerifyMessage + Send + Sync + 'static) {
+        self.verifier = Some(Verifier {
+            verifier: Box::new(verifier),
+            into_error: None,
+        });
+    }
+
+    /// Sets a verifier for the signatures of the received messages, whose failures are reported
+    /// as modeled errors.
+    ///
+    /// This is the same as [`set_verifier`](Self::set_verifier), except that when a message fails
+    /// verification, the stream is terminated with a `ServiceError` containing the modeled error
+    /// returned by `into_error`.
+    pub fn set_verifier_with_error(
+        &mut self,
+        verifier: impl VerifyMessage + Send + Sync + 'static,
+        into_error: impl Fn(VerifyMessageError) -> E + Send + Sync + 'static,
+    ) {
+        self.verifier = Some(Verifier {
+            verifier: Box::new(verifier),
+            into_error: Some(Box::new(into_error)),
+        });
+    }
+
+    /// Sets the maximum length in bytes of a received message frame.
+    ///
+    /// A message that exceeds it terminates the stream with a `ResponseError` as soon as its
+    /// prelude is received, which bounds how much of the stream is buffered. Defaults to
+    /// [`DEFAULT_MAX_MESSAGE_SIZE`](aws_smithy_eventstream::frame::DEFAULT_MAX_MESSAGE_SIZE).
+    pub fn set_max_message_size(&mut self, max_message_size: u32) {
+        self.decoder.set_max_message_size(max_message_size);
+    }
+
+    /// Sets the maximum length in bytes of the headers of a received message frame.
+    ///
+    /// Defaults to
+    /// [`DEFAULT_MAX_HEADERS_SIZE`](aws_smithy_eventstream::frame::DEFAULT_MAX_HEADERS_SIZE).
+    pub fn set_max_headers_size(&mut self, max_headers_size: u32) {
+        self.decoder.set_max_headers_size(max_headers_size);
+    }
+
+    /// Returns the number of bytes that have been received but not yet decoded into a message.
+    pub fn buffered_len(&self) -> usize {
+        self.buffer.buffered_len()
+    }
+
     fn unmarshall(&self, message: Message) -> Result<Option<T>, SdkError<E, RawMessage>> {
         match self.unmarshaller.unmarshall(&message) {
             Ok(unmarshalled) => match unmarshalled {
@@ -152,6 +230,32 @@ impl<T, E> Receiver<T, E> {
         }
     }
 
+    fn verify(&mut self, message: Message) -> Result<Option<Message>, SdkError<E, RawMessage>> {
+        let verifier = match self.verifier.as_mut() {
+            Some(verifier) => verifier,
+            None => return Ok(Some(message)),
+        };
+        match verifier.verifier.verify(&message) {
+            Ok(Some(verified)) => {
+                trace!(message = ?verified, "verified event stream message");
+                Ok(Some(verified))
+            }
+            Ok(None) => {
+                trace!("received signed empty message that ends the event stream");
+                self.buffer = RecvBuf::Terminated;
+                Ok(None)
+            }
+            Err(err) => {
+                self.buffer = RecvBuf::Terminated;
+                let raw = RawMessage::Decoded(message);
+                Err(match &verifier.into_error {
+                    Some(into_error) => SdkError::service_error(into_error(err), raw),
+                    None => SdkError::response_error(err, raw),
+                })
+            }
+        }
+    }
+
     async fn buffer_next_chunk(&mut self) -> Result<(), SdkError<E, RawMessage>> {
         if !self.buffer.is_eos() {
             let next_chunk = self
@@ -185,7 +289,7 @@ impl<T, E> Receiver<T, E> {
                     })?
                 {
                     trace!(message = ?message, "received complete event stream message");
-                    return Ok(Some(message));
+                    return self.verify(message);
                 }
             }
 
@@ -532,6 +636,129 @@ mod tests {
         );
     }
 
+    #[cfg(feature = "event-stream-test-util")]
+    mod verification {
+        use super::{encode_message, EventStreamError, TestMessage, Unmarshaller};
+        use crate::event_stream::test_util::SequenceVerifier;
+        use crate::event_stream::Receiver;
+        use aws_smithy_runtime_api::client::result::SdkError;
+        use aws_smithy_types::body::SdkBody;
+        use aws_smithy_types::event_stream::RawMessage;
+        use bytes::Bytes;
+        use hyper::body::Body;
+        use std::error::Error as StdError;
+        use std::io::Error as IOError;
+
+        #[tokio::test]
+        async fn receive_verified_messages() {
+            let chunks: Vec<Result<_, IOError>> = vec![
+                Ok(SequenceVerifier::wrap(1, encode_message("one"))),
+                Ok(SequenceVerifier::wrap(2, encode_message("two"))),
+                Ok(SequenceVerifier::wrap(3, Bytes::new())),
+                // Messages after the end of the stream are ignored
+                Ok(encode_message("three")),
+            ];
+            let chunk_stream = futures_util::stream::iter(chunks);
+            let body = SdkBody::from_body_0_4(Body::wrap_stream(chunk_stream));
+            let mut receiver = Receiver::<TestMessage, EventStreamError>::new(Unmarshaller, body);
+            receiver.set_verifier(SequenceVerifier::new());
+            assert_eq!(
+                TestMessage("one".into()),
+                receiver.recv().await.unwrap().unwrap()
+            );
+            assert_eq!(
+                TestMessage("two".into()),
+                receiver.recv().await.unwrap().unwrap()
+            );
+            assert_eq!(None, receiver.recv().await.unwrap());
+            assert_eq!(None, receiver.recv().await.unwrap());
+        }
+
+        #[tokio::test]
+        async fn receive_verification_failure() {
+            let chunks: Vec<Result<_, IOError>> = vec![
+                Ok(SequenceVerifier::wrap(1, encode_message("one"))),
+                // A replayed message
+                Ok(SequenceVerifier::wrap(1, encode_message("one"))),
+                Ok(SequenceVerifier::wrap(3, encode_message("three"))),
+            ];
+            let chunk_stream = futures_util::stream::iter(chunks);
+            let body = SdkBody::from_body_0_4(Body::wrap_stream(chunk_stream));
+            let mut receiver = Receiver::<TestMessage, EventStreamError>::new(Unmarshaller, body);
+            receiver.set_verifier(SequenceVerifier::new());
+            assert_eq!(
+                TestMessage("one".into()),
+                receiver.recv().await.unwrap().unwrap()
+            );
+            let err = receiver.recv().await.unwrap_err();
+            assert!(matches!(
+                &err,
+                SdkError::ResponseError(context) if matches!(context.raw(), RawMessage::Decoded(_))
+            ));
+            assert_eq!("expected sequence 2", err.source().unwrap().to_string());
+            // The stream is terminated after a verification failure
+            assert_eq!(None, receiver.recv().await.unwrap());
+        }
+
+        #[tokio::test]
+        async fn receive_verification_failure_as_modeled_error() {
+            let chunks: Vec<Result<_, IOError>> = vec![
+                Ok(SequenceVerifier::wrap(1, encode_message("one"))),
+                Ok(SequenceVerifier::wrap(3, encode_message("three"))),
+            ];
+            let chunk_stream = futures_util::stream::iter(chunks);
+            let body = SdkBody::from_body_0_4(Body::wrap_stream(chunk_stream));
+            let mut receiver = Receiver::<TestMessage, EventStreamError>::new(Unmarshaller, body);
+            receiver.set_verifier_with_error(SequenceVerifier::new(), |err| {
+                EventStreamError::unmarshalling(format!("invalid signature: {err}"))
+            });
+            assert_eq!(
+                TestMessage("one".into()),
+                receiver.recv().await.unwrap().unwrap()
+            );
+            match receiver.recv().await.unwrap_err() {
+                SdkError::ServiceError(context) => assert_eq!(
+                    "failed to unmarshall message: invalid signature: expected sequence 2",
+                    context.err().to_string()
+                ),
+                err => panic!("expected a modeled error, found {err:?}"),
+            }
+            assert_eq!(None, receiver.recv().await.unwrap());
+        }
+    }
+
+    #[tokio::test]
+    async fn receive_message_exceeding_max_size() {
+        let one = encode_message("one");
+        let large = encode_message("a message that's too large");
+        let mut first_chunk = one.to_vec();
+        first_chunk.extend_from_slice(&large[..12]);
+        let chunks: Vec<Result<_, IOError>> =
+            vec![Ok(Bytes::from(first_chunk)), Ok(large.slice(12..))];
+        let chunk_stream = futures_util::stream::iter(chunks);
+        let body = SdkBody::from_body_0_4(Body::wrap_stream(chunk_stream));
+        let mut receiver = Receiver::<TestMessage, EventStreamError>::new(Unmarshaller, body);
+        receiver.set_max_message_size(one.len() as u32);
+
+        assert_eq!(0, receiver.buffered_len());
+        assert_eq!(
+            TestMessage("one".into()),
+            receiver.recv().await.unwrap().unwrap()
+        );
+        // The prelude of the large message has been received
+        assert_eq!(12, receiver.buffered_len());
+        let err = receiver.recv().await.unwrap_err();
+        assert!(matches!(&err, SdkError::ResponseError(_)));
+        let source = err.source().unwrap();
+        assert!(
+            source
+                .downcast_ref::<EventStreamError>()
+                .unwrap()
+                .is_max_size_exceeded(),
+            "{source}"
+        );
+    }
+
     fn assert_send_and_sync<T: Send + Sync>() {}
 
     #[tokio::test]
@@ -3,22 +3,34 @@
  * SPDX-License-Identifier: Apache-2.0
  */
 
+#[cfg(feature = "event-stream-channel")]
+use super::channel::{self, EventStreamSenderHandle};
+#[cfg(feature = "event-stream-channel")]
+use aws_smithy_async::rt::sleep::{AsyncSleep, SharedAsyncSleep, Sleep};
 use aws_smithy_eventstream::frame::{write_message_to, MarshallMessage, SignMessage};
+use aws_smithy_runtime_api::client::orchestrator::HttpResponse;
 use aws_smithy_runtime_api::client::result::SdkError;
 use aws_smithy_types::error::ErrorMetadata;
+use aws_smithy_types::event_stream::Message;
 use bytes::Bytes;
 use futures_core::Stream;
 use std::error::Error as StdError;
 use std::fmt;
 use std::fmt::Debug;
+#[cfg(feature = "event-stream-channel")]
+use std::future::Future;
 use std::marker::PhantomData;
 use std::pin::Pin;
 use std::task::{Context, Poll};
+#[cfg(feature = "event-stream-channel")]
+use std::{task::ready, time::Duration};
 use tracing::trace;
 
 /// Input type for Event Streams.
 pub struct EventStreamSender<T, E> {
     input_stream: Pin<Box<dyn Stream<Item = Result<T, E>> + Send + Sync>>,
+    #[cfg(feature = "event-stream-channel")]
+    heartbeat: Option<Heartbeat>,
 }
 
 impl<T, E> Debug for EventStreamSender<T, E> {
@@ -29,6 +41,35 @@ impl<T, E> Debug for EventStreamSender<T, E> {
     }
 }
 
+#[cfg(feature = "event-stream-channel")]
+impl<T: Send + 'static, E: 'static> EventStreamSender<T, E> {
+    /// Creates a sender whose events are sent with an [`EventStreamSenderHandle`].
+    ///
+    /// Up to `capacity` events are buffered before sends have to wait for the stream. The handle
+    /// can be cloned to send events from multiple producers, and the stream ends once it's closed
+    /// with [`EventStreamSenderHandle::close`] or every handle has been dropped.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `capacity` is zero.
+    pub fn channel(capacity: usize) -> (Self, EventStreamSenderHandle<T>) {
+        let (stream, handle) = channel::channel(capacity);
+        (Self::from(stream), handle)
+    }
+}
+
+#[cfg(feature = "event-stream-channel")]
+impl<T, E> EventStreamSender<T, E> {
+    /// Sends `heartbeat` messages whenever the stream is idle.
+    ///
+    /// Heartbeats keep streams that would otherwise be idle for long periods from being closed by
+    /// load balancers or the service.
+    pub fn with_heartbeat(mut self, heartbeat: Heartbeat) -> Self {
+        self.heartbeat = Some(heartbeat);
+        self
+    }
+}
+
 impl<T, E: StdError + Send + Sync + 'static> EventStreamSender<T, E> {
     #[doc(hidden)]
     pub fn into_body_stream(
@@ -37,7 +78,35 @@ impl<T, E: StdError + Send + Sync + 'static> EventStreamSender<T, E> {
         error_marshaller: impl MarshallMessage<Input = E> + Send + Sync + 'static,
         signer: impl SignMessage + Send + Sync + 'static,
     ) -> MessageStreamAdapter<T, E> {
-        MessageStreamAdapter::new(marshaller, error_marshaller, signer, self.input_stream)
+        let adapter =
+            MessageStreamAdapter::new(marshaller, error_marshaller, signer, self.input_stream);
+        #[cfg(feature = "event-stream-channel")]
+        let adapter = match self.heartbeat {
+            Some(heartbeat) => adapter.with_heartbeat(heartbeat),
+            None => adapter,
+        };
+        adapter
+    }
+
+    /// Marshalls the events of this sender into unsigned messages for a
+    /// [`WebSocketTransport`](crate::event_stream::websocket::WebSocketTransport).
+    #[cfg(feature = "event-stream-websocket")]
+    pub fn into_message_stream(
+        self,
+        marshaller: impl MarshallMessage<Input = T> + Send + Sync + 'static,
+        error_marshaller: impl MarshallMessage<Input = E> + Send + Sync + 'static,
+    ) -> crate::event_stream::websocket::MessageStream
+    where
+        T: 'static,
+    {
+        use futures_util::StreamExt;
+        Box::pin(self.input_stream.map(move |event| {
+            match event {
+                Ok(event) => marshaller.marshall(event),
+                Err(error) => error_marshaller.marshall(error),
+            }
+            .map_err(Into::into)
+        }))
     }
 }
 
@@ -48,8 +117,45 @@ where
     fn from(stream: S) -> Self {
         EventStreamSender {
             input_stream: Box::pin(stream),
+            #[cfg(feature = "event-stream-channel")]
+            heartbeat: None,
+        }
+    }
+}
+
+/// A message that's sent to keep an idle event stream alive.
+///
+/// The heartbeat message is signed like any other message, so it must be a message that the
+/// service accepts and ignores, such as an event with an empty payload.
+#[cfg(feature = "event-stream-channel")]
+#[derive(Clone, Debug)]
+pub struct Heartbeat {
+    interval: Duration,
+    message: Message,
+    sleep_impl: SharedAsyncSleep,
+}
+
+#[cfg(feature = "event-stream-channel")]
+impl Heartbeat {
+    /// Creates a new `Heartbeat` that sends `message` after the stream has been idle for
+    /// `interval`, using `sleep_impl` to wait.
+    pub fn new(interval: Duration, message: Message, sleep_impl: SharedAsyncSleep) -> Self {
+        Self {
+            interval,
+            message,
+            sleep_impl,
         }
     }
+
+    /// How long the stream can be idle before a heartbeat is sent
+    pub fn interval(&self) -> Duration {
+        self.interval
+    }
+
+    /// The unsigned heartbeat message
+    pub fn message(&self) -> &Message {
+        &self.message
+    }
 }
 
 /// An error that occurs within a message stream.
@@ -115,6 +221,10 @@ pub struct MessageStreamAdapter<T, E: StdError + Send + Sync + 'static> {
     error_marshaller: Box<dyn MarshallMessage<Input = E> + Send + Sync>,
     signer: Box<dyn SignMessage + Send + Sync>,
     stream: Pin<Box<dyn Stream<Item = Result<T, E>> + Send>>,
+    #[cfg(feature = "event-stream-channel")]
+    heartbeat: Option<Heartbeat>,
+    #[cfg(feature = "event-stream-channel")]
+    heartbeat_sleep: Option<Sleep>,
     end_signal_sent: bool,
     _phantom: PhantomData<E>,
 }
@@ -134,19 +244,66 @@ impl<T, E: StdError + Send + Sync + 'static> MessageStreamAdapter<T, E> {
             error_marshaller: Box::new(error_marshaller),
             signer: Box::new(signer),
             stream,
+            #[cfg(feature = "event-stream-channel")]
+            heartbeat: None,
+            #[cfg(feature = "event-stream-channel")]
+            heartbeat_sleep: None,
             end_signal_sent: false,
             _phantom: Default::default(),
         }
     }
+
+    /// Sends `heartbeat` messages whenever the stream is idle.
+    #[cfg(feature = "event-stream-channel")]
+    pub fn with_heartbeat(mut self, heartbeat: Heartbeat) -> Self {
+        self.heartbeat = Some(heartbeat);
+        self
+    }
+
+    #[allow(clippy::result_large_err)]
+    fn sign_and_encode(&mut self, message: Message) -> Result<Bytes, SdkError<E, HttpResponse>> {
+        trace!(unsigned_message = ?message, "signing event stream message");
+        let message = self
+            .signer
+            .sign(message)
+            .map_err(SdkError::construction_failure)?;
+
+        let mut buffer = Vec::new();
+        write_message_to(&message, &mut buffer).map_err(SdkError::construction_failure)?;
+        trace!(signed_message = ?buffer, "sending signed event stream message");
+        Ok(Bytes::from(buffer))
+    }
+
+    /// Sends a heartbeat once the stream has been idle for the heartbeat interval.
+    #[cfg(feature = "event-stream-channel")]
+    fn poll_heartbeat(&mut self, cx: &mut Context<'_>) -> Poll<Option<<Self as Stream>::Item>> {
+        let heartbeat = match &self.heartbeat {
+            Some(heartbeat) if !self.end_signal_sent => heartbeat,
+            _ => return Poll::Pending,
+        };
+        let sleep = self
+            .heartbeat_sleep
+            .get_or_insert_with(|| heartbeat.sleep_impl.sleep(heartbeat.interval));
+        ready!(Pin::new(sleep).poll(cx));
+        self.heartbeat_sleep = None;
+
+        let message = heartbeat.message.clone();
+        trace!("sending event stream heartbeat");
+        Poll::Ready(Some(self.sign_and_encode(message)))
+    }
 }
 
 impl<T, E: StdError + Send + Sync + 'static> Stream for MessageStreamAdapter<T, E> {
-    type Item =
-        Result<Bytes, SdkError<E, aws_smithy_runtime_api::client::orchestrator::HttpResponse>>;
+    type Item = Result<Bytes, SdkError<E, HttpResponse>>;
 
     fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
         match self.stream.as_mut().poll_next(cx) {
             Poll::Ready(message_option) => {
+                // Any activity restarts the heartbeat interval
+                #[cfg(feature = "event-stream-channel")]
+                {
+                    self.heartbeat_sleep = None;
+                }
                 if let Some(message_result) = message_option {
                     let message = match message_result {
                         Ok(message) => self
@@ -158,18 +315,7 @@ impl<T, E: StdError + Send + Sync + 'static> Stream for MessageStreamAdapter<T,
                             .marshall(message)
                             .map_err(SdkError::construction_failure)?,
                     };
-
-                    trace!(unsigned_message = ?message, "signing event stream message");
-                    let message = self
-                        .signer
-                        .sign(message)
-                        .map_err(SdkError::construction_failure)?;
-
-                    let mut buffer = Vec::new();
-                    write_message_to(&message, &mut buffer)
-                        .map_err(SdkError::construction_failure)?;
-                    trace!(signed_message = ?buffer, "sending signed event stream message");
-                    Poll::Ready(Some(Ok(Bytes::from(buffer))))
+                    Poll::Ready(Some(self.sign_and_encode(message)))
                 } else if !self.end_signal_sent {
                     self.end_signal_sent = true;
                     let mut buffer = Vec::new();
@@ -187,6 +333,9 @@ impl<T, E: StdError + Send + Sync + 'static> Stream for MessageStreamAdapter<T,
                     Poll::Ready(None)
                 }
             }
+            #[cfg(feature = "event-stream-channel")]
+            Poll::Pending => self.poll_heartbeat(cx),
+            #[cfg(not(feature = "event-stream-channel"))]
             Poll::Pending => Poll::Pending,
         }
     }
@@ -349,4 +498,144 @@ mod tests {
             yield Err(TestServiceError);
         });
     }
+
+    #[cfg(feature = "event-stream-channel")]
+    mod channel {
+        use super::{ErrorMarshaller, Marshaller, TestMessage, TestServiceError, TestSigner};
+        use crate::event_stream::{EventStreamSender, Heartbeat};
+        use aws_smithy_async::rt::sleep::{SharedAsyncSleep, TokioSleep};
+        use aws_smithy_eventstream::frame::read_message_from;
+        use aws_smithy_types::event_stream::Message;
+        use bytes::Bytes;
+        use futures_util::stream::StreamExt;
+        use std::time::Duration;
+
+        fn unwrap_signed(mut bytes: Bytes) -> Message {
+            let signed = read_message_from(&mut bytes).unwrap();
+            assert_eq!("signed", signed.headers()[0].name().as_str());
+            read_message_from(&mut (&signed.payload()[..])).unwrap()
+        }
+
+        #[tokio::test]
+        async fn channel_sender_with_multiple_producers() {
+            let (sender, handle) = EventStreamSender::<TestMessage, TestServiceError>::channel(4);
+            let mut adapter = sender.into_body_stream(Marshaller, ErrorMarshaller, TestSigner);
+
+            let producers: Vec<_> = (0..3)
+                .map(|i| {
+                    let handle = handle.clone();
+                    tokio::spawn(async move { handle.send(TestMessage(format!("{i}"))).await })
+                })
+                .collect();
+            for producer in producers {
+                producer.await.unwrap().unwrap();
+            }
+            handle.close().await.unwrap();
+
+            let mut received = Vec::new();
+            for _ in 0..3 {
+                let message = unwrap_signed(adapter.next().await.unwrap().unwrap());
+                received.push(String::from_utf8(message.payload().to_vec()).unwrap());
+            }
+            received.sort();
+            assert_eq!(vec!["0", "1", "2"], received);
+
+            // Closing sends the signed empty message that ends the stream
+            let mut end_signal_bytes = adapter.next().await.unwrap().unwrap();
+            let end_signal = read_message_from(&mut end_signal_bytes).unwrap();
+            assert_eq!("signed", end_signal.headers()[0].name().as_str());
+            assert_eq!(0, end_signal.payload().len());
+            assert!(adapter.next().await.is_none());
+
+            assert!(handle.is_closed());
+            let err = handle.send(TestMessage("late".into())).await.unwrap_err();
+            assert!(err.is_closed());
+            assert_eq!(TestMessage("late".into()), err.into_event().unwrap());
+        }
+
+        #[tokio::test]
+        async fn channel_sender_backpressure() {
+            let (sender, handle) = EventStreamSender::<TestMessage, TestServiceError>::channel(1);
+            let mut adapter = sender.into_body_stream(Marshaller, ErrorMarshaller, TestSigner);
+
+            handle.try_send(TestMessage("one".into())).unwrap();
+            let err = handle.try_send(TestMessage("two".into())).unwrap_err();
+            assert!(err.is_full());
+            let metrics = handle.metrics();
+            assert_eq!(
+                (1, 1, 0),
+                (
+                    metrics.buffered(),
+                    metrics.capacity(),
+                    metrics.blocked_sends()
+                )
+            );
+
+            let blocked = tokio::spawn({
+                let handle = handle.clone();
+                async move { handle.send(TestMessage("two".into())).await }
+            });
+            tokio::task::yield_now().await;
+            assert_eq!(1, handle.metrics().blocked_sends());
+
+            let message = unwrap_signed(adapter.next().await.unwrap().unwrap());
+            assert_eq!(&b"one"[..], &message.payload()[..]);
+            blocked.await.unwrap().unwrap();
+            let message = unwrap_signed(adapter.next().await.unwrap().unwrap());
+            assert_eq!(&b"two"[..], &message.payload()[..]);
+
+            // Dropping every handle ends the stream
+            drop(handle);
+            let end_signal = adapter.next().await.unwrap().unwrap();
+            assert_eq!(
+                0,
+                read_message_from(&mut &end_signal[..])
+                    .unwrap()
+                    .payload()
+                    .len()
+            );
+            assert!(adapter.next().await.is_none());
+        }
+
+        #[tokio::test(start_paused = true)]
+        async fn heartbeats_are_sent_when_idle() {
+            let (sender, handle) = EventStreamSender::<TestMessage, TestServiceError>::channel(1);
+            let heartbeat = Heartbeat::new(
+                Duration::from_secs(10),
+                Message::new(&b"heartbeat"[..]),
+                SharedAsyncSleep::new(TokioSleep::new()),
+            );
+            let mut adapter = sender.with_heartbeat(heartbeat).into_body_stream(
+                Marshaller,
+                ErrorMarshaller,
+                TestSigner,
+            );
+
+            let start = tokio::time::Instant::now();
+            let message = unwrap_signed(adapter.next().await.unwrap().unwrap());
+            assert_eq!(&b"heartbeat"[..], &message.payload()[..]);
+            assert_eq!(Duration::from_secs(10), start.elapsed());
+
+            // Sending an event restarts the interval
+            tokio::time::sleep(Duration::from_secs(5)).await;
+            handle.send(TestMessage("event".into())).await.unwrap();
+            let message = unwrap_signed(adapter.next().await.unwrap().unwrap());
+            assert_eq!(&b"event"[..], &message.payload()[..]);
+            let message = unwrap_signed(adapter.next().await.unwrap().unwrap());
+            assert_eq!(&b"heartbeat"[..], &message.payload()[..]);
+            assert_eq!(Duration::from_secs(25), start.elapsed());
+
+            // No heartbeats are sent after the stream is closed
+            handle.close().await.unwrap();
+            let end_signal = adapter.next().await.unwrap().unwrap();
+            assert_eq!(
+                0,
+                read_message_from(&mut &end_signal[..])
+                    .unwrap()
+                    .payload()
+                    .len()
+            );
+            assert!(adapter.next().await.is_none());
+        }
+    }
 }
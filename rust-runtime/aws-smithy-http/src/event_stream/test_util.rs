@@ -0,0 +1,413 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! Utilities to record and replay event stream traffic in tests.
+//!
+//! [`SequenceVerifier`] is a fake message verifier, to test message verification without signing.
+//!
+//! An [`EventStreamRecorder`] captures the decoded messages sent and received over an event
+//! stream, along with when they were sent, as [`EventStreamTraffic`]. The traffic is stored as
+//! readable JSON, and can be edited by hand to inject errors or delays.
+//!
+//! A [`ReplayingPeer`] replays recorded traffic as a fake peer: it sends the recorded responses
+//! with their recorded timing, and validates the requests it receives against the recorded
+//! requests.
+//!
+//! Each message is stored with its headers and payload. The payload is stored as a string if it's
+//! valid UTF-8, and as base64 otherwise:
+//!
+//! ```json
+//! {
+//!   "offset_millis": 250,
+//!   "direction": "Response",
+//!   "action": {
+//!     "Message": {
+//!       "message": {
+//!         "headers": [
+//!           { "name": ":message-type", "type": "string", "value": "event" },
+//!           { "name": ":event-type", "type": "string", "value": "TranscriptEvent" }
+//!         ],
+//!         "payload": { "utf8": "{\"Transcript\":{\"Results\":[]}}" }
+//!       }
+//!     }
+//!   }
+//! }
+//! ```
+
+use aws_smithy_eventstream::error::Error as EventStreamError;
+use aws_smithy_eventstream::frame::{DecodedFrame, MessageFrameDecoder};
+use aws_smithy_types::base64;
+use aws_smithy_types::date_time::Format;
+use aws_smithy_types::event_stream::{Header, HeaderValue, Message};
+use aws_smithy_types::DateTime;
+use bytes::{Buf, Bytes};
+use bytes_utils::SegmentedBuf;
+use serde::{Deserialize, Serialize};
+use std::error::Error as StdError;
+use std::path::Path;
+use std::time::Duration;
+
+mod record;
+mod replay;
+mod verify;
+
+pub use record::EventStreamRecorder;
+pub use replay::ReplayingPeer;
+pub use verify::SequenceVerifier;
+
+/// A recording of the traffic of an event stream
+///
+/// Event stream traffic can be recorded with [`EventStreamRecorder`] and replayed with
+/// [`ReplayingPeer`].
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct EventStreamTraffic {
+    events: Vec<Event>,
+    docs: Option<String>,
+    version: Version,
+}
+
+impl EventStreamTraffic {
+    /// Create new `EventStreamTraffic` from a list of events
+    pub fn new(events: Vec<Event>) -> Self {
+        Self {
+            events,
+            docs: None,
+            version: Version::V0,
+        }
+    }
+
+    /// Set the documentation of this recording
+    pub fn with_docs(mut self, docs: impl Into<String>) -> Self {
+        self.docs = Some(docs.into());
+        self
+    }
+
+    /// Events in the order they occurred
+    pub fn events(&self) -> &[Event] {
+        &self.events
+    }
+
+    /// Documentation of this recording
+    pub fn docs(&self) -> Option<&str> {
+        self.docs.as_deref()
+    }
+
+    /// Create an `EventStreamTraffic` instance from a file
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, Box<dyn StdError>> {
+        let contents = std::fs::read_to_string(path)?;
+        Ok(serde_json::from_str(&contents)?)
+    }
+
+    /// Write this `EventStreamTraffic` to a file
+    pub fn write_to_file(&self, path: impl AsRef<Path>) -> Result<(), Box<dyn StdError>> {
+        let serialized = serde_json::to_string_pretty(&self)?;
+        Ok(std::fs::write(path, serialized)?)
+    }
+}
+
+/// Serialization version of event stream traffic
+#[derive(Copy, Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
+pub enum Version {
+    /// Initial event stream traffic version
+    V0,
+}
+
+/// An event in one direction of an event stream
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+pub struct Event {
+    offset_millis: u64,
+    direction: Direction,
+    action: Action,
+}
+
+impl Event {
+    /// Create a new `Event` that occurred `offset` after the start of the stream
+    pub fn new(offset: Duration, direction: Direction, action: Action) -> Self {
+        Self {
+            offset_millis: offset.as_millis() as u64,
+            direction,
+            action,
+        }
+    }
+
+    /// Time after the start of the stream that this event occurred
+    pub fn offset(&self) -> Duration {
+        Duration::from_millis(self.offset_millis)
+    }
+
+    /// Direction of this event
+    pub fn direction(&self) -> Direction {
+        self.direction
+    }
+
+    /// What happened
+    pub fn action(&self) -> &Action {
+        &self.action
+    }
+}
+
+/// Event direction
+#[derive(Copy, Clone, Debug, Serialize, Deserialize, Eq, PartialEq, Hash)]
+pub enum Direction {
+    /// Messages sent by the client
+    Request,
+    /// Messages sent by the service
+    Response,
+}
+
+/// Event stream action
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
+#[non_exhaustive]
+pub enum Action {
+    /// A message was sent
+    Message {
+        /// The message headers and payload
+        #[serde(with = "message_json")]
+        message: Message,
+    },
+
+    /// The stream failed
+    Error {
+        /// Error message
+        message: String,
+    },
+
+    /// The stream ended
+    End,
+}
+
+// Decodes the messages in a stream of data chunks
+#[derive(Debug, Default)]
+struct FrameDecoder {
+    decoder: MessageFrameDecoder,
+    buffer: SegmentedBuf<Bytes>,
+}
+
+impl FrameDecoder {
+    fn decode(&mut self, data: Bytes) -> Result<Vec<Message>, EventStreamError> {
+        self.buffer.push(data);
+        let mut messages = Vec::new();
+        while self.buffer.remaining() > 0 {
+            match self.decoder.decode_frame(&mut self.buffer)? {
+                DecodedFrame::Complete(message) => messages.push(message),
+                DecodedFrame::Incomplete => break,
+            }
+        }
+        Ok(messages)
+    }
+
+    fn has_partial_frame(&self) -> bool {
+        self.buffer.remaining() > 0
+    }
+}
+
+// A readable JSON representation of event stream messages
+mod message_json {
+    use super::*;
+    use serde::de::Error as _;
+    use serde::ser::Error as _;
+    use serde::{Deserializer, Serializer};
+
+    #[derive(Serialize, Deserialize)]
+    struct MessageJson {
+        headers: Vec<HeaderJson>,
+        payload: PayloadJson,
+    }
+
+    #[derive(Serialize, Deserialize)]
+    struct HeaderJson {
+        name: String,
+        #[serde(flatten)]
+        value: HeaderValueJson,
+    }
+
+    #[derive(Serialize, Deserialize)]
+    #[serde(rename_all = "snake_case", tag = "type", content = "value")]
+    enum HeaderValueJson {
+        Bool(bool),
+        Byte(i8),
+        Int16(i16),
+        Int32(i32),
+        Int64(i64),
+        /// Base64 encoded
+        ByteArray(String),
+        String(String),
+        /// RFC-3339 formatted
+        Timestamp(String),
+        /// Hyphenated hex digits
+        Uuid(String),
+    }
+
+    #[derive(Serialize, Deserialize)]
+    #[serde(rename_all = "snake_case")]
+    enum PayloadJson {
+        Utf8(String),
+        Base64(String),
+    }
+
+    pub(super) fn serialize<S: Serializer>(
+        message: &Message,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        let headers = message
+            .headers()
+            .iter()
+            .map(|header| {
+                let value = match header.value() {
+                    HeaderValue::Bool(value) => HeaderValueJson::Bool(*value),
+                    HeaderValue::Byte(value) => HeaderValueJson::Byte(*value),
+                    HeaderValue::Int16(value) => HeaderValueJson::Int16(*value),
+                    HeaderValue::Int32(value) => HeaderValueJson::Int32(*value),
+                    HeaderValue::Int64(value) => HeaderValueJson::Int64(*value),
+                    HeaderValue::ByteArray(value) => {
+                        HeaderValueJson::ByteArray(base64::encode(value))
+                    }
+                    HeaderValue::String(value) => HeaderValueJson::String(value.as_str().into()),
+                    HeaderValue::Timestamp(value) => HeaderValueJson::Timestamp(
+                        value.fmt(Format::DateTime).map_err(S::Error::custom)?,
+                    ),
+                    HeaderValue::Uuid(value) => HeaderValueJson::Uuid(format_uuid(*value)),
+                    _ => return Err(S::Error::custom("unsupported header value type")),
+                };
+                Ok(HeaderJson {
+                    name: header.name().as_str().into(),
+                    value,
+                })
+            })
+            .collect::<Result<_, _>>()?;
+        let payload = match std::str::from_utf8(message.payload()) {
+            Ok(payload) => PayloadJson::Utf8(payload.into()),
+            Err(_) => PayloadJson::Base64(base64::encode(message.payload())),
+        };
+        MessageJson { headers, payload }.serialize(serializer)
+    }
+
+    pub(super) fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<Message, D::Error> {
+        let json = MessageJson::deserialize(deserializer)?;
+        let headers = json
+            .headers
+            .into_iter()
+            .map(|header| {
+                let value = match header.value {
+                    HeaderValueJson::Bool(value) => HeaderValue::Bool(value),
+                    HeaderValueJson::Byte(value) => HeaderValue::Byte(value),
+                    HeaderValueJson::Int16(value) => HeaderValue::Int16(value),
+                    HeaderValueJson::Int32(value) => HeaderValue::Int32(value),
+                    HeaderValueJson::Int64(value) => HeaderValue::Int64(value),
+                    HeaderValueJson::ByteArray(value) => HeaderValue::ByteArray(
+                        base64::decode(value).map_err(D::Error::custom)?.into(),
+                    ),
+                    HeaderValueJson::String(value) => HeaderValue::String(value.into()),
+                    HeaderValueJson::Timestamp(value) => HeaderValue::Timestamp(
+                        DateTime::from_str(&value, Format::DateTime).map_err(D::Error::custom)?,
+                    ),
+                    HeaderValueJson::Uuid(value) => HeaderValue::Uuid(
+                        u128::from_str_radix(&value.replace('-', ""), 16)
+                            .map_err(D::Error::custom)?,
+                    ),
+                };
+                Ok(Header::new(header.name, value))
+            })
+            .collect::<Result<_, D::Error>>()?;
+        let payload = match json.payload {
+            PayloadJson::Utf8(payload) => payload.into_bytes(),
+            PayloadJson::Base64(payload) => base64::decode(payload).map_err(D::Error::custom)?,
+        };
+        Ok(Message::new_from_parts(headers, payload))
+    }
+
+    fn format_uuid(uuid: u128) -> String {
+        let hex = format!("{uuid:032x}");
+        format!(
+            "{}-{}-{}-{}-{}",
+            &hex[0..8],
+            &hex[8..12],
+            &hex[12..16],
+            &hex[16..20],
+            &hex[20..32]
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Action, Direction, Event, EventStreamTraffic};
+    use aws_smithy_types::event_stream::{Header, HeaderValue, Message};
+    use aws_smithy_types::DateTime;
+    use std::time::Duration;
+
+    #[test]
+    fn traffic_round_trips_through_json() {
+        let message = Message::new(&b"{\"Transcript\":{}}"[..])
+            .add_header(Header::new(
+                ":message-type",
+                HeaderValue::String("event".into()),
+            ))
+            .add_header(Header::new("bool", HeaderValue::Bool(true)))
+            .add_header(Header::new("byte", HeaderValue::Byte(-1)))
+            .add_header(Header::new("int16", HeaderValue::Int16(16)))
+            .add_header(Header::new("int32", HeaderValue::Int32(32)))
+            .add_header(Header::new("int64", HeaderValue::Int64(64)))
+            .add_header(Header::new(
+                "bytes",
+                HeaderValue::ByteArray((&[0xff, 0x00][..]).into()),
+            ))
+            .add_header(Header::new(
+                ":date",
+                HeaderValue::Timestamp(DateTime::from_secs(1_611_160_427)),
+            ))
+            .add_header(Header::new(
+                "uuid",
+                HeaderValue::Uuid(0x0123_4567_89ab_cdef_0123_4567_89ab_cdef),
+            ));
+        let traffic = EventStreamTraffic::new(vec![
+            Event::new(
+                Duration::ZERO,
+                Direction::Request,
+                Action::Message {
+                    message: Message::new(vec![0xc3, 0x28]),
+                },
+            ),
+            Event::new(
+                Duration::from_millis(250),
+                Direction::Response,
+                Action::Message { message },
+            ),
+            Event::new(
+                Duration::from_millis(300),
+                Direction::Response,
+                Action::Error {
+                    message: "connection reset".into(),
+                },
+            ),
+            Event::new(Duration::from_millis(300), Direction::Request, Action::End),
+        ])
+        .with_docs("a test recording");
+
+        let json = serde_json::to_value(&traffic).unwrap();
+        assert_eq!(
+            serde_json::json!({ "utf8": "{\"Transcript\":{}}" }),
+            json["events"][1]["action"]["Message"]["message"]["payload"]
+        );
+        assert_eq!(
+            serde_json::json!({ "base64": "wyg=" }),
+            json["events"][0]["action"]["Message"]["message"]["payload"]
+        );
+        let headers = &json["events"][1]["action"]["Message"]["message"]["headers"];
+        assert_eq!(
+            serde_json::json!({ "name": ":date", "type": "timestamp", "value": "2021-01-20T16:33:47Z" }),
+            headers[7]
+        );
+        assert_eq!(
+            serde_json::json!({ "name": "uuid", "type": "uuid", "value": "01234567-89ab-cdef-0123-456789abcdef" }),
+            headers[8]
+        );
+
+        let deserialized: EventStreamTraffic = serde_json::from_value(json).unwrap();
+        assert_eq!(traffic, deserialized);
+    }
+}
@@ -0,0 +1,201 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+use super::{Action, Direction, Event, EventStreamTraffic, FrameDecoder};
+use aws_smithy_types::body::SdkBody;
+use bytes::Bytes;
+use futures_core::Stream;
+use http::{HeaderMap, HeaderValue};
+use pin_project_lite::pin_project;
+use std::fmt;
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{ready, Context, Poll};
+use tokio::time::Instant;
+
+/// Records the messages sent and received over an event stream
+///
+/// Wrap the encoded request stream with [`record_requests`](EventStreamRecorder::record_requests)
+/// and the response body with [`record_responses`](EventStreamRecorder::record_responses), and
+/// retrieve the recording with [`traffic`](EventStreamRecorder::traffic) once the stream is done.
+#[derive(Clone, Debug)]
+pub struct EventStreamRecorder {
+    state: Arc<Mutex<State>>,
+}
+
+#[derive(Debug)]
+struct State {
+    start: Instant,
+    events: Vec<Event>,
+}
+
+impl Default for EventStreamRecorder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl EventStreamRecorder {
+    /// Create a new `EventStreamRecorder`
+    ///
+    /// Event offsets are measured from when the recorder is created.
+    pub fn new() -> Self {
+        Self {
+            state: Arc::new(Mutex::new(State {
+                start: Instant::now(),
+                events: Vec::new(),
+            })),
+        }
+    }
+
+    /// Record the messages of an encoded request stream, such as a
+    /// [`MessageStreamAdapter`](crate::event_stream::MessageStreamAdapter)
+    pub fn record_requests<S, E>(&self, stream: S) -> impl Stream<Item = Result<Bytes, E>>
+    where
+        S: Stream<Item = Result<Bytes, E>>,
+        E: fmt::Display,
+    {
+        RecordingStream {
+            inner: stream,
+            recorder: self.direction(Direction::Request),
+        }
+    }
+
+    /// Record the messages of a response body
+    pub fn record_responses(&self, body: SdkBody) -> SdkBody {
+        SdkBody::from_body_0_4(RecordingBody {
+            inner: body,
+            recorder: self.direction(Direction::Response),
+        })
+    }
+
+    /// The traffic recorded so far
+    pub fn traffic(&self) -> EventStreamTraffic {
+        EventStreamTraffic::new(self.state.lock().unwrap().events.clone())
+    }
+
+    fn direction(&self, direction: Direction) -> DirectionRecorder {
+        DirectionRecorder {
+            recorder: self.clone(),
+            direction,
+            decoder: FrameDecoder::default(),
+        }
+    }
+
+    fn record(&self, direction: Direction, action: Action) {
+        let mut state = self.state.lock().unwrap();
+        let offset = state.start.elapsed();
+        state.events.push(Event::new(offset, direction, action));
+    }
+}
+
+// Records the messages in one direction of an event stream
+#[derive(Debug)]
+struct DirectionRecorder {
+    recorder: EventStreamRecorder,
+    direction: Direction,
+    decoder: FrameDecoder,
+}
+
+impl DirectionRecorder {
+    fn data(&mut self, data: &Bytes) {
+        match self.decoder.decode(data.clone()) {
+            Ok(messages) => {
+                for message in messages {
+                    self.recorder
+                        .record(self.direction, Action::Message { message });
+                }
+            }
+            Err(err) => self.error(&err),
+        }
+    }
+
+    fn error(&self, err: &dyn fmt::Display) {
+        self.recorder.record(
+            self.direction,
+            Action::Error {
+                message: err.to_string(),
+            },
+        );
+    }
+
+    fn end(&self) {
+        if self.decoder.has_partial_frame() {
+            self.error(&"the stream ended with an incomplete message");
+        } else {
+            self.recorder.record(self.direction, Action::End);
+        }
+    }
+}
+
+pin_project! {
+    struct RecordingStream<S> {
+        #[pin]
+        inner: S,
+        recorder: DirectionRecorder,
+    }
+}
+
+impl<S, E> Stream for RecordingStream<S>
+where
+    S: Stream<Item = Result<Bytes, E>>,
+    E: fmt::Display,
+{
+    type Item = Result<Bytes, E>;
+
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.project();
+        let item = ready!(this.inner.poll_next(cx));
+        match &item {
+            Some(Ok(data)) => this.recorder.data(data),
+            Some(Err(err)) => this.recorder.error(err),
+            None => this.recorder.end(),
+        }
+        Poll::Ready(item)
+    }
+}
+
+pin_project! {
+    struct RecordingBody {
+        #[pin]
+        inner: SdkBody,
+        recorder: DirectionRecorder,
+    }
+}
+
+impl http_body::Body for RecordingBody {
+    type Data = Bytes;
+    type Error = aws_smithy_types::body::Error;
+
+    fn poll_data(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Self::Data, Self::Error>>> {
+        let this = self.project();
+        let item = ready!(this.inner.poll_data(cx));
+        match &item {
+            Some(Ok(data)) => this.recorder.data(data),
+            Some(Err(err)) => this.recorder.error(err),
+            None => this.recorder.end(),
+        }
+        Poll::Ready(item)
+    }
+
+    fn poll_trailers(
+        self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Result<Option<HeaderMap<HeaderValue>>, Self::Error>> {
+        self.project().inner.poll_trailers(cx)
+    }
+
+    fn is_end_stream(&self) -> bool {
+        // Not forwarded, so that consumers poll the end of the body and it's recorded
+        false
+    }
+
+    fn size_hint(&self) -> http_body::SizeHint {
+        self.inner.size_hint()
+    }
+}
@@ -0,0 +1,479 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+use super::{Action, Direction, Event, EventStreamTraffic, FrameDecoder};
+use aws_smithy_eventstream::frame::write_message_to;
+use aws_smithy_types::body::SdkBody;
+use aws_smithy_types::event_stream::Message;
+use bytes::Bytes;
+use futures_core::Stream;
+use http::{HeaderMap, HeaderValue};
+use std::error::Error as StdError;
+use std::fmt;
+use std::future::{poll_fn, Future};
+use std::path::Path;
+use std::pin::{pin, Pin};
+use std::sync::{Arc, Mutex};
+use std::task::{ready, Context, Poll};
+use tokio::time::{Instant, Sleep};
+
+/// Replays recorded event stream traffic as a fake peer
+///
+/// The peer sends the recorded responses with [`response_body`](ReplayingPeer::response_body),
+/// and records the requests it's sent with [`receive_requests`](ReplayingPeer::receive_requests)
+/// so that they can be checked against the recorded requests with
+/// [`validate_requests`](ReplayingPeer::validate_requests).
+///
+/// Responses are sent with their recorded timing, so tests that replay long recordings should
+/// run with a paused clock, e.g. `#[tokio::test(start_paused = true)]`.
+#[derive(Clone, Debug)]
+pub struct ReplayingPeer {
+    traffic: Arc<EventStreamTraffic>,
+    received: Arc<Mutex<Vec<Action>>>,
+}
+
+impl ReplayingPeer {
+    /// Create a new `ReplayingPeer` that replays `traffic`
+    pub fn new(traffic: EventStreamTraffic) -> Self {
+        Self {
+            traffic: Arc::new(traffic),
+            received: Default::default(),
+        }
+    }
+
+    /// Create a new `ReplayingPeer` that replays the traffic recorded in a file
+    pub fn from_file(path: impl AsRef<Path>) -> Result<Self, Box<dyn StdError>> {
+        Ok(Self::new(EventStreamTraffic::from_file(path)?))
+    }
+
+    /// A response body that sends the recorded responses
+    ///
+    /// Each response is sent at its recorded offset from when the body is first polled. A recorded
+    /// error fails the body, and the body ends after a recorded end or once every response has
+    /// been sent.
+    pub fn response_body(&self) -> SdkBody {
+        let events = self
+            .traffic
+            .events()
+            .iter()
+            .filter(|event| event.direction() == Direction::Response)
+            .cloned()
+            .collect();
+        SdkBody::from_body_0_4(ReplayBody {
+            events,
+            next: 0,
+            start: None,
+            sleep: None,
+        })
+    }
+
+    /// Receive the messages of an encoded request stream until it ends
+    pub async fn receive_requests<S, E>(&self, stream: S)
+    where
+        S: Stream<Item = Result<Bytes, E>>,
+        E: fmt::Display,
+    {
+        let mut stream = pin!(stream);
+        let mut decoder = FrameDecoder::default();
+        loop {
+            let action = match poll_fn(|cx| stream.as_mut().poll_next(cx)).await {
+                Some(Ok(data)) => match decoder.decode(data) {
+                    Ok(messages) => {
+                        self.received(
+                            messages
+                                .into_iter()
+                                .map(|message| Action::Message { message }),
+                        );
+                        continue;
+                    }
+                    Err(err) => Action::Error {
+                        message: err.to_string(),
+                    },
+                },
+                Some(Err(err)) => Action::Error {
+                    message: err.to_string(),
+                },
+                None if decoder.has_partial_frame() => Action::Error {
+                    message: "the stream ended with an incomplete message".into(),
+                },
+                None => Action::End,
+            };
+            self.received([action]);
+            return;
+        }
+    }
+
+    /// The requests received so far
+    pub fn received_requests(&self) -> Vec<Action> {
+        self.received.lock().unwrap().clone()
+    }
+
+    /// Validate the received requests against the recorded requests
+    ///
+    /// Headers named in `ignore_headers` are removed from every message before they're compared.
+    /// Signed messages carry a new signature and date every time they're sent, so SigV4 signed
+    /// streams should ignore `&[":chunk-signature", ":date"]`.
+    pub fn validate_requests(&self, ignore_headers: &[&str]) -> Result<(), Box<dyn StdError>> {
+        let expected: Vec<Action> = self
+            .traffic
+            .events()
+            .iter()
+            .filter(|event| event.direction() == Direction::Request)
+            .map(|event| strip_headers(event.action(), ignore_headers))
+            .collect();
+        let actual: Vec<Action> = self
+            .received
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|action| strip_headers(action, ignore_headers))
+            .collect();
+
+        for (index, (expected, actual)) in expected.iter().zip(&actual).enumerate() {
+            if expected != actual {
+                return Err(format!(
+                    "request {index} doesn't match the recording\n\
+                     expected: {expected:?}\n  actual: {actual:?}"
+                )
+                .into());
+            }
+        }
+        if expected.len() != actual.len() {
+            return Err(format!(
+                "expected {} requests but received {}",
+                expected.len(),
+                actual.len()
+            )
+            .into());
+        }
+        Ok(())
+    }
+
+    fn received(&self, actions: impl IntoIterator<Item = Action>) {
+        self.received.lock().unwrap().extend(actions);
+    }
+}
+
+fn strip_headers(action: &Action, ignore_headers: &[&str]) -> Action {
+    match action {
+        Action::Message { message } => Action::Message {
+            message: Message::new_from_parts(
+                message
+                    .headers()
+                    .iter()
+                    .filter(|header| !ignore_headers.contains(&header.name().as_str()))
+                    .cloned()
+                    .collect(),
+                message.payload().clone(),
+            ),
+        },
+        other => other.clone(),
+    }
+}
+
+struct ReplayBody {
+    events: Vec<Event>,
+    next: usize,
+    start: Option<Instant>,
+    sleep: Option<Pin<Box<Sleep>>>,
+}
+
+impl http_body::Body for ReplayBody {
+    type Data = Bytes;
+    type Error = aws_smithy_types::body::Error;
+
+    fn poll_data(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Self::Data, Self::Error>>> {
+        let start = *self.start.get_or_insert_with(Instant::now);
+        let event = match self.events.get(self.next) {
+            Some(event) => event,
+            None => return Poll::Ready(None),
+        };
+        let deadline = start + event.offset();
+        let sleep = self
+            .sleep
+            .get_or_insert_with(|| Box::pin(tokio::time::sleep_until(deadline)));
+        ready!(sleep.as_mut().poll(cx));
+        self.sleep = None;
+
+        let event = &self.events[self.next];
+        let result = match event.action() {
+            Action::Message { message } => {
+                let mut buffer = Vec::new();
+                write_message_to(message, &mut buffer)
+                    .map(|_| Bytes::from(buffer))
+                    .map_err(Into::into)
+            }
+            Action::Error { message } => Err(message.clone().into()),
+            Action::End => Ok(Bytes::new()),
+        };
+        match (event.action(), result) {
+            (Action::Message { .. }, Ok(data)) => {
+                self.next += 1;
+                Poll::Ready(Some(Ok(data)))
+            }
+            (_, Err(err)) => {
+                self.next = self.events.len();
+                Poll::Ready(Some(Err(err)))
+            }
+            _ => {
+                self.next = self.events.len();
+                Poll::Ready(None)
+            }
+        }
+    }
+
+    fn poll_trailers(
+        self: Pin<&mut Self>,
+        _cx: &mut Context<'_>,
+    ) -> Poll<Result<Option<HeaderMap<HeaderValue>>, Self::Error>> {
+        Poll::Ready(Ok(None))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::event_stream::test_util::{
+        Action, Direction, Event, EventStreamRecorder, EventStreamTraffic, ReplayingPeer,
+    };
+    use crate::event_stream::Receiver;
+    use aws_smithy_eventstream::error::Error as EventStreamError;
+    use aws_smithy_eventstream::frame::{write_message_to, UnmarshallMessage, UnmarshalledMessage};
+    use aws_smithy_types::body::SdkBody;
+    use aws_smithy_types::event_stream::{Header, HeaderValue, Message};
+    use bytes::Bytes;
+    use std::convert::Infallible;
+    use std::time::Duration;
+
+    #[derive(Debug)]
+    struct Unmarshaller;
+    impl UnmarshallMessage for Unmarshaller {
+        type Output = String;
+        type Error = EventStreamError;
+
+        fn unmarshall(
+            &self,
+            message: &Message,
+        ) -> Result<UnmarshalledMessage<Self::Output, Self::Error>, EventStreamError> {
+            Ok(UnmarshalledMessage::Event(
+                std::str::from_utf8(message.payload()).unwrap().into(),
+            ))
+        }
+    }
+
+    fn message(payload: &'static str) -> Message {
+        Message::new(payload).add_header(Header::new(
+            ":date",
+            HeaderValue::Int64(payload.len() as i64),
+        ))
+    }
+
+    fn encode(payload: &'static str) -> Bytes {
+        let mut buffer = Vec::new();
+        write_message_to(&message(payload), &mut buffer).unwrap();
+        buffer.into()
+    }
+
+    fn event(offset_millis: u64, direction: Direction, action: Action) -> Event {
+        Event::new(Duration::from_millis(offset_millis), direction, action)
+    }
+
+    #[tokio::test]
+    async fn record_then_replay() {
+        let recorder = EventStreamRecorder::new();
+
+        // Split a message across chunks to check that the recording is of decoded messages
+        let encoded = encode("two");
+        let requests = futures_util::stream::iter([
+            Ok::<_, Infallible>(encode("one")),
+            Ok(encoded.slice(..5)),
+            Ok(encoded.slice(5..)),
+        ]);
+        let replayed = ReplayingPeer::new(EventStreamTraffic::new(Vec::new()));
+        replayed
+            .receive_requests(recorder.record_requests(requests))
+            .await;
+
+        let responses = hyper::Body::wrap_stream(futures_util::stream::iter([
+            Ok::<_, Infallible>(encode("three")),
+            Ok(encode("four")),
+        ]));
+        let body = recorder.record_responses(SdkBody::from_body_0_4(responses));
+        let mut receiver = Receiver::<String, EventStreamError>::new(Unmarshaller, body);
+        while receiver.recv().await.unwrap().is_some() {}
+
+        let traffic = recorder.traffic();
+        let actions: Vec<_> = traffic
+            .events()
+            .iter()
+            .map(|event| (event.direction(), event.action().clone()))
+            .collect();
+        assert_eq!(
+            vec![
+                (
+                    Direction::Request,
+                    Action::Message {
+                        message: message("one")
+                    }
+                ),
+                (
+                    Direction::Request,
+                    Action::Message {
+                        message: message("two")
+                    }
+                ),
+                (Direction::Request, Action::End),
+                (
+                    Direction::Response,
+                    Action::Message {
+                        message: message("three")
+                    }
+                ),
+                (
+                    Direction::Response,
+                    Action::Message {
+                        message: message("four")
+                    }
+                ),
+                (Direction::Response, Action::End),
+            ],
+            actions
+        );
+
+        let peer = ReplayingPeer::new(traffic);
+        let mut receiver =
+            Receiver::<String, EventStreamError>::new(Unmarshaller, peer.response_body());
+        assert_eq!("three", receiver.recv().await.unwrap().unwrap());
+        assert_eq!("four", receiver.recv().await.unwrap().unwrap());
+        assert_eq!(None, receiver.recv().await.unwrap());
+
+        let requests =
+            futures_util::stream::iter([Ok::<_, Infallible>(encode("one")), Ok(encode("two"))]);
+        peer.receive_requests(requests).await;
+        peer.validate_requests(&[]).unwrap();
+    }
+
+    #[tokio::test]
+    async fn record_end_of_responses_for_consumers_that_check_is_end_stream() {
+        use http_body::Body;
+
+        let recorder = EventStreamRecorder::new();
+        let mut body = recorder.record_responses(SdkBody::from(encode("one")));
+        // Stops as soon as the body reports the end of the stream, like hyper does
+        while !body.is_end_stream() {
+            if body.data().await.transpose().unwrap().is_none() {
+                break;
+            }
+        }
+
+        let traffic = recorder.traffic();
+        let actions: Vec<_> = traffic
+            .events()
+            .iter()
+            .map(|event| event.action().clone())
+            .collect();
+        assert_eq!(
+            vec![
+                Action::Message {
+                    message: message("one")
+                },
+                Action::End,
+            ],
+            actions
+        );
+    }
+
+    #[tokio::test]
+    async fn validate_requests_ignores_headers() {
+        let peer = ReplayingPeer::new(EventStreamTraffic::new(vec![
+            event(
+                0,
+                Direction::Request,
+                Action::Message {
+                    message: message("one"),
+                },
+            ),
+            event(0, Direction::Request, Action::End),
+        ]));
+        // The `:date` header of this message is different to the recorded one
+        let mut buffer = Vec::new();
+        let sent = Message::new("one").add_header(Header::new(":date", HeaderValue::Int64(-1)));
+        write_message_to(&sent, &mut buffer).unwrap();
+        peer.receive_requests(futures_util::stream::iter([Ok::<_, Infallible>(
+            Bytes::from(buffer),
+        )]))
+        .await;
+
+        let err = peer.validate_requests(&[]).unwrap_err().to_string();
+        assert!(
+            err.contains("request 0 doesn't match the recording"),
+            "{err}"
+        );
+        peer.validate_requests(&[":date"]).unwrap();
+    }
+
+    #[tokio::test]
+    async fn replay_injected_error() {
+        let peer = ReplayingPeer::new(EventStreamTraffic::new(vec![
+            event(
+                0,
+                Direction::Response,
+                Action::Message {
+                    message: message("one"),
+                },
+            ),
+            event(
+                0,
+                Direction::Response,
+                Action::Error {
+                    message: "connection reset".into(),
+                },
+            ),
+            event(
+                0,
+                Direction::Response,
+                Action::Message {
+                    message: message("two"),
+                },
+            ),
+        ]));
+        let mut receiver =
+            Receiver::<String, EventStreamError>::new(Unmarshaller, peer.response_body());
+        assert_eq!("one", receiver.recv().await.unwrap().unwrap());
+        let err = receiver.recv().await.unwrap_err();
+        assert!(format!("{:?}", err).contains("connection reset"), "{err:?}");
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn replay_with_recorded_timing() {
+        let peer = ReplayingPeer::new(EventStreamTraffic::new(vec![
+            event(
+                100,
+                Direction::Response,
+                Action::Message {
+                    message: message("one"),
+                },
+            ),
+            event(
+                5000,
+                Direction::Response,
+                Action::Message {
+                    message: message("two"),
+                },
+            ),
+            event(5000, Direction::Response, Action::End),
+        ]));
+        let start = tokio::time::Instant::now();
+        let mut receiver =
+            Receiver::<String, EventStreamError>::new(Unmarshaller, peer.response_body());
+        assert_eq!("one", receiver.recv().await.unwrap().unwrap());
+        assert_eq!(Duration::from_millis(100), start.elapsed());
+        assert_eq!("two", receiver.recv().await.unwrap().unwrap());
+        assert_eq!(Duration::from_millis(5000), start.elapsed());
+        assert_eq!(None, receiver.recv().await.unwrap());
+    }
+}
@@ -0,0 +1,59 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+use aws_smithy_eventstream::frame::{
+    read_message_from, write_message_to, VerifyMessage, VerifyMessageError,
+};
+use aws_smithy_types::event_stream::{Header, HeaderValue, Message};
+use bytes::Bytes;
+
+const SEQUENCE_HEADER: &str = "sequence";
+
+/// A fake [`VerifyMessage`] to test message verification with
+///
+/// Each message must be wrapped with [`wrap`](SequenceVerifier::wrap) in a message with a
+/// `sequence` header that counts up from 1, like signed messages are chained. A wrapped empty
+/// message ends the stream.
+#[derive(Debug, Default)]
+pub struct SequenceVerifier {
+    sequence: i32,
+}
+
+impl SequenceVerifier {
+    /// Creates a verifier that expects the sequence to start at 1.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Wraps the encoded message `inner` in a message with the given `sequence` header.
+    ///
+    /// `inner` is empty to end the stream.
+    pub fn wrap(sequence: i32, inner: Bytes) -> Bytes {
+        let mut buffer = Vec::new();
+        let message = Message::new(inner)
+            .add_header(Header::new(SEQUENCE_HEADER, HeaderValue::Int32(sequence)));
+        write_message_to(&message, &mut buffer).expect("valid message");
+        buffer.into()
+    }
+}
+
+impl VerifyMessage for SequenceVerifier {
+    fn verify(&mut self, message: &Message) -> Result<Option<Message>, VerifyMessageError> {
+        self.sequence += 1;
+        match message
+            .headers()
+            .iter()
+            .find(|h| h.name().as_str() == SEQUENCE_HEADER)
+        {
+            Some(header) if header.value() == &HeaderValue::Int32(self.sequence) => {}
+            _ => return Err(format!("expected sequence {}", self.sequence).into()),
+        }
+        if message.payload().is_empty() {
+            Ok(None)
+        } else {
+            Ok(Some(read_message_from(&message.payload()[..])?))
+        }
+    }
+}
@@ -0,0 +1,807 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! Transport for event streams over WebSocket.
+//!
+//! Event streams are usually sent and received as the bodies of an HTTP/2 request and response.
+//! Some services, such as Amazon Transcribe, can also stream events over a WebSocket, which works
+//! in environments that block HTTP/2. Each event stream message is sent as a binary WebSocket
+//! message, and is signed with a [`SignMessage`] implementation whose signature chain starts
+//! with the signature of the presigned upgrade URL (see
+//! `aws_sigv4::event_stream::presign_websocket_url`).
+//!
+//! [`WebSocketTransport::start`] returns an [`SdkBody`] containing the received messages, which
+//! can be read with a [`Receiver`](super::Receiver), and a driver future that sends and receives
+//! the messages. The driver future must be polled to completion, usually by spawning it.
+//!
+//! The transport pings the server every [`ping_interval`](WebSocketTransport::ping_interval),
+//! and treats the connection as lost if the server doesn't respond before the next ping. When the
+//! connection is lost, the [`ReconnectHook`] decides whether to reconnect.
+
+use crate::event_stream::BoxError;
+use aws_smithy_eventstream::frame::{write_message_to, SignMessage};
+use aws_smithy_types::body::SdkBody;
+use aws_smithy_types::event_stream::Message;
+use bytes::Bytes;
+use futures_core::Stream;
+use futures_util::{SinkExt, StreamExt};
+use http::{HeaderMap, HeaderValue};
+use std::fmt;
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use std::time::Duration;
+use tokio::io::{AsyncRead, AsyncWrite};
+use tokio::sync::mpsc;
+use tokio_tungstenite::tungstenite::Message as WsMessage;
+use tokio_tungstenite::WebSocketStream;
+use tracing::{debug, trace};
+
+const DEFAULT_PING_INTERVAL: Duration = Duration::from_secs(30);
+
+// The number of received messages buffered before the transport stops reading from the socket
+const RECEIVE_BUFFER_SIZE: usize = 16;
+
+/// A stream of unsigned event stream messages to send.
+pub type MessageStream = Pin<Box<dyn Stream<Item = Result<Message, BoxError>> + Send>>;
+
+/// A boxed future returned by [`ConnectWebSocket::connect`].
+pub type ConnectFuture<'a, Io> =
+    Pin<Box<dyn Future<Output = Result<WebSocketConnection<Io>, BoxError>> + Send + 'a>>;
+
+/// An established WebSocket connection and the signer for the messages sent over it.
+pub struct WebSocketConnection<Io> {
+    socket: WebSocketStream<Io>,
+    signer: Box<dyn SignMessage + Send + Sync>,
+}
+
+impl<Io> fmt::Debug for WebSocketConnection<Io> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WebSocketConnection")
+            .field("signer", &self.signer)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<Io> WebSocketConnection<Io> {
+    /// Creates a new `WebSocketConnection` from a WebSocket that has completed the upgrade
+    /// handshake and the signer for the messages sent over it.
+    pub fn new(
+        socket: WebSocketStream<Io>,
+        signer: impl SignMessage + Send + Sync + 'static,
+    ) -> Self {
+        Self {
+            socket,
+            signer: Box::new(signer),
+        }
+    }
+}
+
+/// Connects to a WebSocket event stream endpoint.
+///
+/// The transport calls [`connect`](ConnectWebSocket::connect) when it starts and every time it
+/// reconnects, so every connection should use a newly presigned upgrade URL and start a new
+/// message signature chain.
+pub trait ConnectWebSocket: fmt::Debug + Send + Sync {
+    /// The IO stream underlying the WebSocket, e.g. a TLS stream.
+    type Io: AsyncRead + AsyncWrite + Unpin + Send + 'static;
+
+    /// Connects to the endpoint.
+    fn connect(&self) -> ConnectFuture<'_, Self::Io>;
+}
+
+/// Decides whether to reconnect when a WebSocket connection is lost.
+pub trait ReconnectHook: fmt::Debug + Send + Sync {
+    /// Called when the connection is lost before the event stream ended, or when connecting
+    /// fails. `attempt` is the number of consecutive failures, starting at one.
+    ///
+    /// Returns the delay before reconnecting, or `None` to end the event stream with `error`.
+    fn on_disconnect(&self, attempt: u32, error: &BoxError) -> Option<Duration>;
+
+    /// Called after reconnecting following `attempt` consecutive failures.
+    fn on_reconnect(&self, attempt: u32) {
+        let _ = attempt;
+    }
+}
+
+/// A [`ReconnectHook`] that reconnects after a fixed delay, up to a maximum number of
+/// consecutive attempts.
+#[derive(Debug, Clone)]
+pub struct FixedDelayReconnect {
+    max_attempts: u32,
+    delay: Duration,
+}
+
+impl FixedDelayReconnect {
+    /// Creates a new `FixedDelayReconnect`.
+    pub fn new(max_attempts: u32, delay: Duration) -> Self {
+        Self {
+            max_attempts,
+            delay,
+        }
+    }
+}
+
+impl ReconnectHook for FixedDelayReconnect {
+    fn on_disconnect(&self, attempt: u32, _error: &BoxError) -> Option<Duration> {
+        (attempt <= self.max_attempts).then_some(self.delay)
+    }
+}
+
+/// Sends and receives event stream messages over a WebSocket.
+#[derive(Debug)]
+pub struct WebSocketTransport<C> {
+    connector: C,
+    ping_interval: Option<Duration>,
+    reconnect_hook: Option<Box<dyn ReconnectHook>>,
+}
+
+impl<C: ConnectWebSocket> WebSocketTransport<C> {
+    /// Creates a new `WebSocketTransport` that connects with `connector`.
+    ///
+    /// By default, the transport pings the server every 30 seconds and doesn't reconnect.
+    pub fn new(connector: C) -> Self {
+        Self {
+            connector,
+            ping_interval: Some(DEFAULT_PING_INTERVAL),
+            reconnect_hook: None,
+        }
+    }
+
+    /// Sets how often to ping the server, or disables pings if `None`.
+    ///
+    /// The connection is considered lost if the server doesn't respond to a ping before the next
+    /// one is due. Time spent waiting for the received messages to be consumed doesn't count.
+    pub fn ping_interval(mut self, ping_interval: Option<Duration>) -> Self {
+        self.ping_interval = ping_interval;
+        self
+    }
+
+    /// Sets the hook that decides whether to reconnect when the connection is lost.
+    pub fn reconnect_hook(mut self, reconnect_hook: impl ReconnectHook + 'static) -> Self {
+        self.reconnect_hook = Some(Box::new(reconnect_hook));
+        self
+    }
+
+    /// Starts sending `messages`.
+    ///
+    /// Returns a body containing the received messages, and the future that drives the
+    /// connection. The future completes when the server closes the connection after all the
+    /// messages have been sent, or when the connection is lost and isn't reconnected, in which
+    /// case the body ends with the error.
+    ///
+    /// A message that was being sent when the connection was lost is sent again after
+    /// reconnecting, signed with the new connection's signer.
+    pub fn start(self, messages: MessageStream) -> (SdkBody, impl Future<Output = ()> + Send)
+    where
+        C: 'static,
+    {
+        let (sender, receiver) = mpsc::channel(RECEIVE_BUFFER_SIZE);
+        let driver = Driver {
+            transport: self,
+            messages,
+            pending: None,
+            messages_ended: false,
+            received: sender,
+        };
+        (SdkBody::from_body_0_4(ReceivedBody(receiver)), driver.run())
+    }
+}
+
+enum ConnectionError {
+    /// The connection was lost, and may be reconnected.
+    Lost(BoxError),
+    /// The event stream can't continue, even on a new connection.
+    Fatal(BoxError),
+}
+
+struct Driver<C> {
+    transport: WebSocketTransport<C>,
+    messages: MessageStream,
+    // A message that hasn't been sent yet
+    pending: Option<Message>,
+    messages_ended: bool,
+    received: mpsc::Sender<Result<Bytes, BoxError>>,
+}
+
+impl<C: ConnectWebSocket> Driver<C> {
+    async fn run(mut self) {
+        let mut attempt = 0;
+        loop {
+            let result = match self.transport.connector.connect().await {
+                Ok(connection) => {
+                    if attempt > 0 {
+                        debug!(attempt, "reconnected event stream WebSocket");
+                        if let Some(hook) = &self.transport.reconnect_hook {
+                            hook.on_reconnect(attempt);
+                        }
+                        attempt = 0;
+                    }
+                    self.run_connection(connection).await
+                }
+                Err(err) => Err(ConnectionError::Lost(err)),
+            };
+            let err = match result {
+                Ok(()) => return,
+                Err(ConnectionError::Lost(err)) => {
+                    attempt += 1;
+                    let delay = self
+                        .transport
+                        .reconnect_hook
+                        .as_ref()
+                        .and_then(|hook| hook.on_disconnect(attempt, &err));
+                    match delay {
+                        Some(delay) => {
+                            debug!(attempt, error = %err, ?delay, "event stream WebSocket connection lost, reconnecting");
+                            tokio::time::sleep(delay).await;
+                            continue;
+                        }
+                        None => err,
+                    }
+                }
+                Err(ConnectionError::Fatal(err)) => err,
+            };
+            debug!(error = %err, "event stream WebSocket failed");
+            let _ = self.received.send(Err(err)).await;
+            return;
+        }
+    }
+
+    async fn run_connection(
+        &mut self,
+        connection: WebSocketConnection<C::Io>,
+    ) -> Result<(), ConnectionError> {
+        let WebSocketConnection {
+            mut socket,
+            mut signer,
+        } = connection;
+        let Self {
+            transport,
+            messages,
+            pending,
+            messages_ended,
+            received,
+        } = self;
+        let mut ping = transport.ping_interval.map(|interval| {
+            let mut ping =
+                tokio::time::interval_at(tokio::time::Instant::now() + interval, interval);
+            // Pings missed while waiting for the consumer must not be sent in a burst, or the
+            // second one would find the first unanswered
+            ping.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            ping
+        });
+        let mut awaiting_pong = false;
+        // Whether the end of the stream has been sent on this connection
+        let mut ended = false;
+        // Room in the buffer for the next received message. The socket is only read once there is
+        // room, so that a stalled consumer doesn't stop the messages from being sent
+        let mut permit = None;
+        let mut receiver_dropped = false;
+
+        loop {
+            // While waiting for the consumer, pongs can't be read, so the pong timeout is paused
+            let reading = permit.is_some() || receiver_dropped;
+            tokio::select! {
+                // Only check for a missing pong once everything the server sent has been read
+                biased;
+
+                reserved = received.reserve(), if !reading => match reserved {
+                    Ok(reserved) => permit = Some(reserved),
+                    // If the receiver was dropped, the received messages are discarded
+                    Err(_) => receiver_dropped = true,
+                },
+                message = next_message(messages, pending, *messages_ended), if !ended => {
+                    let signed = match message {
+                        Some(Ok(message)) => {
+                            *pending = Some(message.clone());
+                            trace!(unsigned_message = ?message, "signing event stream message");
+                            signer.sign(message)
+                        }
+                        Some(Err(err)) => return Err(ConnectionError::Fatal(err)),
+                        None => {
+                            *messages_ended = true;
+                            ended = true;
+                            match signer.sign_empty() {
+                                Some(signed) => signed,
+                                None => continue,
+                            }
+                        }
+                    };
+                    let message = signed.map_err(ConnectionError::Fatal)?;
+                    let mut buffer = Vec::new();
+                    write_message_to(&message, &mut buffer)
+                        .map_err(|err| ConnectionError::Fatal(err.into()))?;
+                    trace!(signed_message = ?buffer, "sending signed event stream message");
+                    socket
+                        .send(WsMessage::Binary(buffer))
+                        .await
+                        .map_err(|err| ConnectionError::Lost(err.into()))?;
+                    *pending = None;
+                }
+                message = socket.next(), if reading => match message {
+                    Some(Ok(WsMessage::Binary(data))) => {
+                        trace!(len = data.len(), "received event stream WebSocket message");
+                        if let Some(permit) = permit.take() {
+                            permit.send(Ok(data.into()));
+                        }
+                    }
+                    Some(Ok(WsMessage::Pong(_))) => awaiting_pong = false,
+                    // Pings are answered automatically
+                    Some(Ok(WsMessage::Ping(_))) => {}
+                    Some(Ok(WsMessage::Close(_))) | None => {
+                        return if ended {
+                            Ok(())
+                        } else {
+                            Err(ConnectionError::Lost(
+                                "the server closed the WebSocket before the event stream ended"
+                                    .into(),
+                            ))
+                        };
+                    }
+                    Some(Ok(other)) => trace!(message = ?other, "ignoring non-binary WebSocket message"),
+                    Some(Err(err)) => return Err(ConnectionError::Lost(err.into())),
+                },
+                _ = tick(&mut ping), if reading => {
+                    if awaiting_pong {
+                        return Err(ConnectionError::Lost(
+                            "the server didn't respond to a ping before the next one was due".into(),
+                        ));
+                    }
+                    trace!("sending WebSocket ping");
+                    socket
+                        .send(WsMessage::Ping(Vec::new()))
+                        .await
+                        .map_err(|err| ConnectionError::Lost(err.into()))?;
+                    awaiting_pong = true;
+                }
+            }
+        }
+    }
+}
+
+// Returns the pending message, or the next message. If the messages already ended on a previous
+// connection, ends them again on this one.
+async fn next_message(
+    messages: &mut MessageStream,
+    pending: &mut Option<Message>,
+    messages_ended: bool,
+) -> Option<Result<Message, BoxError>> {
+    if let Some(message) = pending.take() {
+        return Some(Ok(message));
+    }
+    if messages_ended {
+        return None;
+    }
+    messages.next().await
+}
+
+async fn tick(ping: &mut Option<tokio::time::Interval>) {
+    match ping {
+        Some(ping) => {
+            ping.tick().await;
+        }
+        None => std::future::pending().await,
+    }
+}
+
+struct ReceivedBody(mpsc::Receiver<Result<Bytes, BoxError>>);
+
+impl http_body::Body for ReceivedBody {
+    type Data = Bytes;
+    type Error = BoxError;
+
+    fn poll_data(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Self::Data, Self::Error>>> {
+        self.0.poll_recv(cx)
+    }
+
+    fn poll_trailers(
+        self: Pin<&mut Self>,
+        _cx: &mut Context<'_>,
+    ) -> Poll<Result<Option<HeaderMap<HeaderValue>>, Self::Error>> {
+        Poll::Ready(Ok(None))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        ConnectFuture, ConnectWebSocket, FixedDelayReconnect, ReconnectHook, WebSocketConnection,
+        WebSocketTransport,
+    };
+    use crate::event_stream::{BoxError, Receiver};
+    use aws_smithy_eventstream::error::Error as EventStreamError;
+    use aws_smithy_eventstream::frame::{
+        read_message_from, write_message_to, SignMessage, SignMessageError, UnmarshallMessage,
+        UnmarshalledMessage,
+    };
+    use aws_smithy_types::event_stream::{Header, HeaderValue, Message};
+    use bytes::Bytes;
+    use futures_util::{SinkExt, StreamExt};
+    use std::sync::{Arc, Mutex};
+    use std::time::Duration;
+    use tokio::io::DuplexStream;
+    use tokio::sync::mpsc;
+    use tokio_tungstenite::tungstenite::protocol::Role;
+    use tokio_tungstenite::tungstenite::Message as WsMessage;
+    use tokio_tungstenite::WebSocketStream;
+
+    // Signs messages by wrapping them with the connection number and message sequence number
+    #[derive(Debug)]
+    struct TestSigner {
+        connection: i32,
+        sequence: i32,
+    }
+
+    impl TestSigner {
+        fn wrap(&mut self, payload: Bytes) -> Message {
+            self.sequence += 1;
+            Message::new(payload)
+                .add_header(Header::new(
+                    "connection",
+                    HeaderValue::Int32(self.connection),
+                ))
+                .add_header(Header::new("sequence", HeaderValue::Int32(self.sequence)))
+        }
+    }
+
+    impl SignMessage for TestSigner {
+        fn sign(&mut self, message: Message) -> Result<Message, SignMessageError> {
+            let mut payload = Vec::new();
+            write_message_to(&message, &mut payload)?;
+            Ok(self.wrap(payload.into()))
+        }
+
+        fn sign_empty(&mut self) -> Option<Result<Message, SignMessageError>> {
+            Some(Ok(self.wrap(Bytes::new())))
+        }
+    }
+
+    // Hands out in-memory WebSockets whose server sides are sent to the test
+    #[derive(Debug)]
+    struct TestConnector {
+        connections: Mutex<i32>,
+        servers: mpsc::UnboundedSender<WebSocketStream<DuplexStream>>,
+    }
+
+    impl TestConnector {
+        fn new() -> (Self, mpsc::UnboundedReceiver<WebSocketStream<DuplexStream>>) {
+            let (servers, rx) = mpsc::unbounded_channel();
+            let connector = Self {
+                connections: Mutex::new(0),
+                servers,
+            };
+            (connector, rx)
+        }
+    }
+
+    impl ConnectWebSocket for TestConnector {
+        type Io = DuplexStream;
+
+        fn connect(&self) -> ConnectFuture<'_, Self::Io> {
+            Box::pin(async move {
+                let connection = {
+                    let mut connections = self.connections.lock().unwrap();
+                    *connections += 1;
+                    *connections
+                };
+                let (client, server) = tokio::io::duplex(4096);
+                let client = WebSocketStream::from_raw_socket(client, Role::Client, None).await;
+                let server = WebSocketStream::from_raw_socket(server, Role::Server, None).await;
+                self.servers.send(server).unwrap();
+                Ok(WebSocketConnection::new(
+                    client,
+                    TestSigner {
+                        connection,
+                        sequence: 0,
+                    },
+                ))
+            })
+        }
+    }
+
+    #[derive(Debug)]
+    struct Unmarshaller;
+
+    impl UnmarshallMessage for Unmarshaller {
+        type Output = String;
+        type Error = EventStreamError;
+
+        fn unmarshall(
+            &self,
+            message: &Message,
+        ) -> Result<UnmarshalledMessage<Self::Output, Self::Error>, EventStreamError> {
+            Ok(UnmarshalledMessage::Event(
+                String::from_utf8(message.payload().to_vec()).unwrap(),
+            ))
+        }
+    }
+
+    fn messages(payloads: &[&'static str]) -> super::MessageStream {
+        let messages: Vec<Result<Message, BoxError>> = payloads
+            .iter()
+            .map(|payload| Ok(Message::new(Bytes::from_static(payload.as_bytes()))))
+            .collect();
+        Box::pin(futures_util::stream::iter(messages))
+    }
+
+    fn event(text: &str) -> WsMessage {
+        let mut buffer = Vec::new();
+        write_message_to(&Message::new(text.to_string()), &mut buffer).unwrap();
+        WsMessage::Binary(buffer)
+    }
+
+    // Returns the (connection, sequence, unsigned payload) of a signed message
+    fn unwrap_signed(message: WsMessage) -> (i32, i32, Bytes) {
+        let signed = read_message_from(&message.into_data()[..]).unwrap();
+        let header = |name: &str| match signed
+            .headers()
+            .iter()
+            .find(|h| h.name().as_str() == name)
+            .unwrap()
+            .value()
+        {
+            HeaderValue::Int32(value) => *value,
+            _ => unreachable!(),
+        };
+        let payload = if signed.payload().is_empty() {
+            Bytes::new()
+        } else {
+            read_message_from(&signed.payload()[..])
+                .unwrap()
+                .payload()
+                .clone()
+        };
+        (header("connection"), header("sequence"), payload)
+    }
+
+    #[tokio::test]
+    async fn sends_and_receives_signed_messages() {
+        let (connector, mut servers) = TestConnector::new();
+        let (body, driver) = WebSocketTransport::new(connector).start(messages(&["one", "two"]));
+        let driver = tokio::spawn(driver);
+        let mut receiver = Receiver::<String, EventStreamError>::new(Unmarshaller, body);
+
+        let mut server = servers.recv().await.unwrap();
+        let mut received = Vec::new();
+        for _ in 0..3 {
+            received.push(unwrap_signed(server.next().await.unwrap().unwrap()));
+        }
+        assert_eq!(
+            vec![
+                (1, 1, Bytes::from("one")),
+                (1, 2, Bytes::from("two")),
+                // The signed empty message ends the stream
+                (1, 3, Bytes::new()),
+            ],
+            received
+        );
+
+        server.send(event("first result")).await.unwrap();
+        server.send(event("second result")).await.unwrap();
+        server.close(None).await.unwrap();
+
+        assert_eq!("first result", receiver.recv().await.unwrap().unwrap());
+        assert_eq!("second result", receiver.recv().await.unwrap().unwrap());
+        assert_eq!(None, receiver.recv().await.unwrap());
+        driver.await.unwrap();
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn pings_the_server() {
+        let (connector, mut servers) = TestConnector::new();
+        let (body, driver) = WebSocketTransport::new(connector)
+            .ping_interval(Some(Duration::from_secs(5)))
+            .start(Box::pin(futures_util::stream::pending()));
+        let driver = tokio::spawn(driver);
+
+        let mut server = servers.recv().await.unwrap();
+        // Reading from the server responds to the ping with a pong
+        assert!(matches!(
+            server.next().await.unwrap().unwrap(),
+            WsMessage::Ping(_)
+        ));
+        assert!(matches!(
+            server.next().await.unwrap().unwrap(),
+            WsMessage::Ping(_)
+        ));
+
+        // The connection is lost when the server goes away
+        drop(server);
+        let mut receiver = Receiver::<String, EventStreamError>::new(Unmarshaller, body);
+        assert!(receiver.recv().await.is_err());
+        driver.await.unwrap();
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn stalled_consumer_does_not_lose_the_connection() {
+        let (connector, mut servers) = TestConnector::new();
+        let (body, driver) = WebSocketTransport::new(connector)
+            .ping_interval(Some(Duration::from_secs(5)))
+            .start(Box::pin(futures_util::stream::pending()));
+        let driver = tokio::spawn(driver);
+        let mut receiver = Receiver::<String, EventStreamError>::new(Unmarshaller, body);
+
+        // The server keeps reading, so that it responds to every ping
+        let mut server = servers.recv().await.unwrap();
+        let (events, mut events_rx) = mpsc::unbounded_channel::<WsMessage>();
+        tokio::spawn(async move {
+            loop {
+                tokio::select! {
+                    event = events_rx.recv() => match event {
+                        Some(event) => server.send(event).await.unwrap(),
+                        None => return server.close(None).await.unwrap(),
+                    },
+                    message = server.next() => if message.is_none() {
+                        return;
+                    },
+                }
+            }
+        });
+
+        // More events than are buffered, so that the driver waits for the consumer while several
+        // pings are missed
+        let stalled = super::RECEIVE_BUFFER_SIZE + 4;
+        for i in 0..stalled {
+            events.send(event(&format!("event {i}"))).unwrap();
+        }
+        tokio::time::sleep(Duration::from_secs(60)).await;
+        for i in 0..stalled {
+            assert_eq!(
+                format!("event {i}"),
+                receiver.recv().await.unwrap().unwrap()
+            );
+        }
+
+        // The missed pings aren't sent in a burst that would declare the connection lost
+        tokio::time::sleep(Duration::from_secs(12)).await;
+        events.send(event("after the stall")).unwrap();
+        assert_eq!("after the stall", receiver.recv().await.unwrap().unwrap());
+        assert!(servers.try_recv().is_err());
+        driver.abort();
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn consumer_stalled_while_awaiting_a_pong_does_not_lose_the_connection() {
+        let (connector, mut servers) = TestConnector::new();
+        let (body, driver) = WebSocketTransport::new(connector)
+            .ping_interval(Some(Duration::from_secs(5)))
+            .start(Box::pin(futures_util::stream::pending()));
+        let driver = tokio::spawn(driver);
+        let mut receiver = Receiver::<String, EventStreamError>::new(Unmarshaller, body);
+
+        // The server doesn't read the first ping until it has sent more events than are buffered,
+        // so that its pong arrives after the driver started waiting for the consumer
+        let mut server = servers.recv().await.unwrap();
+        tokio::time::sleep(Duration::from_secs(6)).await;
+        let stalled = super::RECEIVE_BUFFER_SIZE + 4;
+        for i in 0..stalled {
+            server.send(event(&format!("event {i}"))).await.unwrap();
+        }
+        let (events, mut events_rx) = mpsc::unbounded_channel::<WsMessage>();
+        tokio::spawn(async move {
+            loop {
+                tokio::select! {
+                    event = events_rx.recv() => match event {
+                        Some(event) => server.send(event).await.unwrap(),
+                        None => return server.close(None).await.unwrap(),
+                    },
+                    message = server.next() => if message.is_none() {
+                        return;
+                    },
+                }
+            }
+        });
+
+        tokio::time::sleep(Duration::from_secs(60)).await;
+        for i in 0..stalled {
+            assert_eq!(
+                format!("event {i}"),
+                receiver.recv().await.unwrap().unwrap()
+            );
+        }
+
+        tokio::time::sleep(Duration::from_secs(12)).await;
+        events.send(event("after the stall")).unwrap();
+        assert_eq!("after the stall", receiver.recv().await.unwrap().unwrap());
+        assert!(servers.try_recv().is_err());
+        driver.abort();
+    }
+
+    #[derive(Debug, Default)]
+    struct RecordingHook {
+        events: Arc<Mutex<Vec<String>>>,
+    }
+
+    impl ReconnectHook for RecordingHook {
+        fn on_disconnect(&self, attempt: u32, error: &BoxError) -> Option<Duration> {
+            self.events
+                .lock()
+                .unwrap()
+                .push(format!("disconnect {attempt}: {error}"));
+            FixedDelayReconnect::new(1, Duration::from_secs(1)).on_disconnect(attempt, error)
+        }
+
+        fn on_reconnect(&self, attempt: u32) {
+            self.events
+                .lock()
+                .unwrap()
+                .push(format!("reconnect {attempt}"));
+        }
+    }
+
+    #[tokio::test(start_paused = true)]
+    async fn reconnects_when_the_connection_is_lost() {
+        let (connector, mut servers) = TestConnector::new();
+        let hook = RecordingHook::default();
+        let events = hook.events.clone();
+        let (input, input_rx) = mpsc::unbounded_channel::<Result<Message, BoxError>>();
+        let messages = Box::pin(futures_util::stream::unfold(input_rx, |mut rx| async {
+            rx.recv().await.map(|message| (message, rx))
+        }));
+        let (body, driver) = WebSocketTransport::new(connector)
+            .ping_interval(None)
+            .reconnect_hook(hook)
+            .start(messages);
+        let driver = tokio::spawn(driver);
+        let mut receiver = Receiver::<String, EventStreamError>::new(Unmarshaller, body);
+
+        let mut server = servers.recv().await.unwrap();
+        input.send(Ok(Message::new(&b"one"[..]))).unwrap();
+        assert_eq!(
+            (1, 1, Bytes::from("one")),
+            unwrap_signed(server.next().await.unwrap().unwrap())
+        );
+        server.send(event("result")).await.unwrap();
+        assert_eq!("result", receiver.recv().await.unwrap().unwrap());
+        server.close(None).await.unwrap();
+        drop(server);
+
+        // The new connection starts a new signature chain
+        let mut server = servers.recv().await.unwrap();
+        input.send(Ok(Message::new(&b"two"[..]))).unwrap();
+        drop(input);
+        assert_eq!(
+            (2, 1, Bytes::from("two")),
+            unwrap_signed(server.next().await.unwrap().unwrap())
+        );
+        assert_eq!(
+            (2, 2, Bytes::new()),
+            unwrap_signed(server.next().await.unwrap().unwrap())
+        );
+        server.close(None).await.unwrap();
+        assert_eq!(None, receiver.recv().await.unwrap());
+        driver.await.unwrap();
+
+        assert_eq!(
+            vec![
+                "disconnect 1: the server closed the WebSocket before the event stream ended"
+                    .to_string(),
+                "reconnect 1".to_string(),
+            ],
+            *events.lock().unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn fails_when_not_reconnecting() {
+        let (connector, mut servers) = TestConnector::new();
+        let (body, driver) =
+            WebSocketTransport::new(connector).start(Box::pin(futures_util::stream::pending()));
+        let driver = tokio::spawn(driver);
+        let mut receiver = Receiver::<String, EventStreamError>::new(Unmarshaller, body);
+
+        let mut server = servers.recv().await.unwrap();
+        server.close(None).await.unwrap();
+        let err = receiver.recv().await.unwrap_err();
+        assert!(
+            format!("{:?}", err).contains("the server closed the WebSocket"),
+            "{err:?}"
+        );
+        driver.await.unwrap();
+    }
+}
@@ -23,6 +23,9 @@
 //! |----------------|-------------|
 //! | `rt-tokio`     | Provides features that are dependent on `tokio` including the `ByteStream::from_path` util |
 //! | `event-stream` | Provides Sender/Receiver implementations for Event Stream codegen. |
+//! | `event-stream-channel` | Provides a multi-producer channel and heartbeats for event stream senders. |
+//! | `event-stream-websocket` | Provides a transport for event streams over WebSocket. |
+//! | `event-stream-test-util` | Provides utilities to record and replay event stream traffic in tests. |
 
 #![allow(clippy::derive_partial_eq_without_eq)]
 #![cfg_attr(docsrs, feature(doc_cfg))]
@@ -4,12 +4,16 @@
  */
 
 use crate::deserialize::error::{DeserializeError as Error, DeserializeErrorKind as ErrorKind};
+use crate::scan::find_escapable;
 use aws_smithy_types::Number;
+use std::num::IntErrorKind;
 use ErrorKind::*;
 
 pub mod error;
+mod incremental;
 pub mod token;
 
+pub use incremental::{ByteStreamTokenizer, IncrementalTokenizer};
 pub use token::{EscapeError, EscapedStr, Offset, Token};
 
 /// JSON token parser as a Rust iterator
@@ -29,10 +33,25 @@ pub fn json_token_iter(input: &[u8]) -> JsonTokenIterator<'_> {
     JsonTokenIterator {
         input,
         index: 0,
+        base_offset: 0,
         state_stack: vec![State::Initial],
     }
 }
 
+/// Returns the number starting at `index` in `input`, which is empty if there is none.
+pub(crate) fn scan_number_literal(input: &[u8], index: usize) -> &str {
+    let mut iter = JsonTokenIterator {
+        input,
+        index: index.min(input.len()),
+        base_offset: 0,
+        state_stack: Vec::new(),
+    };
+    let (start, end, _, _) = iter.scan_number();
+    // Unsafe: we examined every character in the range, and they are all number characters
+    debug_assert!(std::str::from_utf8(&input[start..end]).is_ok());
+    unsafe { std::str::from_utf8_unchecked(&input[start..end]) }
+}
+
 /// Internal parser state for the iterator. Used to context between successive `next` calls.
 #[derive(Copy, Clone, Debug, Eq, PartialEq)]
 enum State {
@@ -55,6 +74,8 @@ enum State {
 pub struct JsonTokenIterator<'a> {
     input: &'a [u8],
     index: usize,
+    /// Offset of `input` in the JSON document, added to the offsets of tokens and errors.
+    base_offset: usize,
     state_stack: Vec<State>,
 }
 
@@ -96,7 +117,7 @@ impl<'a> JsonTokenIterator<'a> {
 
     /// Creates an error at the given `offset` in the stream.
     fn error_at(&self, offset: usize, kind: ErrorKind) -> Error {
-        Error::new(kind, Some(offset))
+        Error::new(kind, Some(self.base_offset + offset))
     }
 
     /// Creates an error at the current offset in the stream.
@@ -129,7 +150,7 @@ impl<'a> JsonTokenIterator<'a> {
 
     /// Returns current offset
     fn offset(&self) -> Offset {
-        Offset(self.index)
+        Offset(self.base_offset + self.index)
     }
 
     /// Discards the '{' character and pushes the `ObjectFirstKeyOrEnd` state.
@@ -181,6 +202,9 @@ impl<'a> JsonTokenIterator<'a> {
         // Read bytes until a non-escaped end-quote, unescaping sequences as needed on the fly
         let start = self.index;
         loop {
+            // Skip to the next quote, escape, or control character
+            let remaining = &self.input[self.index..];
+            self.index += find_escapable(remaining).unwrap_or(remaining.len());
             match self.peek_expect()? {
                 b'"' => {
                     let value = std::str::from_utf8(&self.input[start..self.index])
@@ -319,6 +343,13 @@ impl<'a> JsonTokenIterator<'a> {
         let number_str = unsafe { std::str::from_utf8_unchecked(number_slice) };
 
         use std::str::FromStr;
+        // Integers that overflow 64 bits are stuffed into an f64, like serde_json does.
+        // Arbitrary precision numbers are parsed from the input with `token::number_literal`.
+        let integer_overflow = || {
+            f64::from_str(number_str)
+                .map(Number::Float)
+                .map_err(|_| self.error_at(start, InvalidNumber))
+        };
         Ok(Token::ValueNumber {
             offset,
             value: if floating {
@@ -331,18 +362,24 @@ impl<'a> JsonTokenIterator<'a> {
                 )
             } else if negative {
                 // If the negative value overflows, then stuff it into an f64
-                let positive = u64::from_str(&number_str[1..])
-                    .map_err(|_| self.error_at(start, InvalidNumber))?;
-                let negative = positive.wrapping_neg() as i64;
-                if negative > 0 {
-                    Number::Float(-(positive as f64))
-                } else {
-                    Number::NegInt(negative)
+                match u64::from_str(&number_str[1..]) {
+                    Ok(positive) => {
+                        let negative = positive.wrapping_neg() as i64;
+                        if negative > 0 {
+                            Number::Float(-(positive as f64))
+                        } else {
+                            Number::NegInt(negative)
+                        }
+                    }
+                    Err(err) if *err.kind() == IntErrorKind::PosOverflow => integer_overflow()?,
+                    Err(_) => return Err(self.error_at(start, InvalidNumber)),
                 }
             } else {
-                Number::PosInt(
-                    u64::from_str(number_str).map_err(|_| self.error_at(start, InvalidNumber))?,
-                )
+                match u64::from_str(number_str) {
+                    Ok(positive) => Number::PosInt(positive),
+                    Err(err) if *err.kind() == IntErrorKind::PosOverflow => integer_overflow()?,
+                    Err(_) => return Err(self.error_at(start, InvalidNumber)),
+                }
             },
         })
     }
@@ -457,6 +494,19 @@ impl<'a> JsonTokenIterator<'a> {
             byte => Err(self.error(UnexpectedToken(byte.into(), "':'"))),
         }
     }
+
+    /// Reads the next token without invalidating the stream on error.
+    fn read_next(&mut self) -> Option<Result<Token<'a>, Error>> {
+        self.discard_whitespace();
+        match self.state() {
+            State::Initial => self.peek_byte().map(|_| self.read_value()),
+            State::ArrayFirstValueOrEnd => Some(self.state_array_first_value_or_end()),
+            State::ArrayNextValueOrEnd => Some(self.state_array_next_value_or_end()),
+            State::ObjectFirstKeyOrEnd => Some(self.state_object_first_key_or_end()),
+            State::ObjectNextKeyOrEnd => Some(self.state_object_next_key_or_end()),
+            State::ObjectFieldValue => Some(self.state_object_field_value()),
+        }
+    }
 }
 
 impl<'a> Iterator for JsonTokenIterator<'a> {
@@ -468,15 +518,7 @@ impl<'a> Iterator for JsonTokenIterator<'a> {
             return None;
         }
 
-        self.discard_whitespace();
-        let result = match self.state() {
-            State::Initial => self.peek_byte().map(|_| self.read_value()),
-            State::ArrayFirstValueOrEnd => Some(self.state_array_first_value_or_end()),
-            State::ArrayNextValueOrEnd => Some(self.state_array_next_value_or_end()),
-            State::ObjectFirstKeyOrEnd => Some(self.state_object_first_key_or_end()),
-            State::ObjectNextKeyOrEnd => Some(self.state_object_next_key_or_end()),
-            State::ObjectFieldValue => Some(self.state_object_field_value()),
-        };
+        let result = self.read_next();
         // Invalidate the stream if we encountered an error
         if result.as_ref().map(|r| r.is_err()).unwrap_or(false) {
             self.index = self.input.len();
@@ -609,6 +651,15 @@ mod tests {
             expect_token(None, iter.next());
         }
 
+        // Long enough to exercise the vectorized search, with sparse escapes
+        #[test]
+        fn long_string_prop_test(input in "([a-zA-Z0-9 \u{80}-\u{10FFFF}]{0,40}[\"\\\\\\x00-\\x1F]?){0,8}") {
+            let json: String = serde_json::to_string(&input).unwrap();
+            let mut iter = json_token_iter(json.as_bytes());
+            expect_token(value_string(0, &json[1..(json.len() - 1)]), iter.next());
+            expect_token(None, iter.next());
+        }
+
         #[test]
         fn integer_prop_test(input: i64) {
             let json = serde_json::to_string(&input).unwrap();
@@ -648,6 +699,9 @@ mod tests {
             Number::Float(-18446744073709551615.0),
             b"-18446744073709551615",
         );
+        // Integers too large for 64 bits are stuffed into an f64
+        expect(Number::Float(1e20), b"100000000000000000000");
+        expect(Number::Float(-1e20), b"-100000000000000000000");
     }
 
     // These cases actually shouldn't parse according to the spec, but it's easier
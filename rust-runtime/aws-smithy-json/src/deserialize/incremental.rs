@@ -0,0 +1,599 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+use crate::deserialize::error::{DeserializeError as Error, DeserializeErrorKind as ErrorKind};
+use crate::deserialize::{JsonTokenIterator, State, Token};
+use aws_smithy_types::byte_stream::ByteStream;
+
+/// Push-based JSON tokenizer
+///
+/// Unlike [`json_token_iter`](crate::deserialize::json_token_iter), which requires the entire
+/// JSON document up front, this tokenizer is fed the document one chunk at a time with
+/// [`push`](Self::push), for example with the `Bytes` chunks of a response body as they are
+/// received. Tokens can be read with [`next_token`](Self::next_token) as soon as they are
+/// complete, so that parsing overlaps with network reads. Only the bytes of tokens that haven't
+/// been read yet are kept in memory, and each byte is only scanned a bounded number of times,
+/// however many chunks a token spans. [`ByteStreamTokenizer`] feeds it the chunks of a
+/// [`ByteStream`].
+///
+/// The tokens and errors are the same as those produced by
+/// [`json_token_iter`](crate::deserialize::json_token_iter) on the concatenation of all chunks,
+/// including their [`Offset`](crate::deserialize::Offset)s, regardless of where the chunk
+/// boundaries fall.
+///
+/// # Example
+///
+/// ```
+/// use aws_smithy_json::deserialize::{IncrementalTokenizer, Token};
+///
+/// let mut tokenizer = IncrementalTokenizer::new();
+/// let mut keys = Vec::new();
+/// for chunk in [&b"{\"na"[..], b"me\": \"sm", b"ithy\", \"vers", b"ion\": 2}"] {
+///     tokenizer.push(chunk);
+///     while let Some(token) = tokenizer.next_token() {
+///         if let Token::ObjectKey { key, .. } = token.unwrap() {
+///             keys.push(key.to_unescaped().unwrap().into_owned());
+///         }
+///     }
+/// }
+/// tokenizer.finish();
+/// assert!(tokenizer.next_token().is_none());
+/// assert_eq!(vec!["name", "version"], keys);
+/// ```
+#[derive(Debug)]
+pub struct IncrementalTokenizer {
+    /// Bytes that haven't been consumed by a token yet, starting at `buffer_offset` in the
+    /// document.
+    buffer: Vec<u8>,
+    buffer_offset: usize,
+    /// Index in `buffer` of the next token.
+    index: usize,
+    state_stack: Vec<State>,
+    /// Lexical state at the end of `buffer`.
+    scan: Scan,
+    /// Whether the next token was incomplete, and no chunk that may complete it was pushed since.
+    waiting: bool,
+    finished: bool,
+    failed: bool,
+}
+
+impl Default for IncrementalTokenizer {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl IncrementalTokenizer {
+    /// Creates a tokenizer with no input.
+    pub fn new() -> Self {
+        Self {
+            buffer: Vec::new(),
+            buffer_offset: 0,
+            index: 0,
+            state_stack: vec![State::Initial],
+            scan: Scan::Between,
+            waiting: false,
+            finished: false,
+            failed: false,
+        }
+    }
+
+    /// Appends the next chunk of the document to the input.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called after [`finish`](Self::finish).
+    pub fn push(&mut self, chunk: impl AsRef<[u8]>) {
+        assert!(!self.finished, "cannot push a chunk after `finish`");
+        // Discard the bytes of the tokens that have already been read
+        self.buffer.drain(..self.index);
+        self.buffer_offset += self.index;
+        self.index = 0;
+        let chunk = chunk.as_ref();
+        if self.scan.scan(chunk) {
+            self.waiting = false;
+        }
+        self.buffer.extend_from_slice(chunk);
+    }
+
+    /// Signals that the whole document has been pushed.
+    ///
+    /// Afterwards, an incomplete token at the end of the input is reported as an error by
+    /// [`next_token`](Self::next_token).
+    pub fn finish(&mut self) {
+        self.finished = true;
+    }
+
+    /// Returns `true` once [`finish`](Self::finish) has been called.
+    pub fn is_finished(&self) -> bool {
+        self.finished
+    }
+
+    /// Returns the next complete token.
+    ///
+    /// `None` is returned when more input is required to complete the next token or, after
+    /// [`finish`](Self::finish), at the end of the document. As with
+    /// [`json_token_iter`](crate::deserialize::json_token_iter), no more tokens are returned
+    /// after an error.
+    pub fn next_token(&mut self) -> Option<Result<Token<'_>, Error>> {
+        if self.failed || self.index == self.buffer.len() || (self.waiting && !self.finished) {
+            return None;
+        }
+
+        let (start, state_depth, state) = (
+            self.index,
+            self.state_stack.len(),
+            self.state_stack[self.state_stack.len() - 1],
+        );
+        let mut iter = JsonTokenIterator {
+            input: &self.buffer,
+            index: self.index,
+            base_offset: self.buffer_offset,
+            state_stack: std::mem::take(&mut self.state_stack),
+        };
+        let result = iter.read_next();
+        let end = iter.index;
+        self.state_stack = iter.state_stack;
+
+        if !self.finished && is_incomplete(&result, end == self.buffer.len()) {
+            // Wait for the next chunk, and try again from the same state. Tokens that complete
+            // successfully never shrink the state stack, so it only needs to be restored to its
+            // prior depth and top state.
+            self.state_stack.truncate(state_depth);
+            self.state_stack[state_depth - 1] = state;
+            self.index = start;
+            self.waiting = true;
+            return None;
+        }
+
+        self.index = end;
+        if matches!(result, Some(Err(_))) {
+            self.failed = true;
+        }
+        result
+    }
+
+    /// Returns `true` if [`next_token`](Self::next_token) doesn't need more input to return its
+    /// result.
+    fn is_ready(&mut self) -> bool {
+        if self.finished || self.failed {
+            return true;
+        }
+        if self.waiting || self.index == self.buffer.len() {
+            return false;
+        }
+        // Read the next token, and rewind if it's complete so that `next_token` returns it
+        let (index, state_stack) = (self.index, self.state_stack.clone());
+        if self.next_token().is_none() {
+            return false;
+        }
+        self.index = index;
+        self.state_stack = state_stack;
+        self.failed = false;
+        true
+    }
+}
+
+/// JSON document tokenized incrementally from a [`ByteStream`]
+///
+/// Pushes each chunk of the body into an [`IncrementalTokenizer`] as it is received, and returns
+/// each token as soon as it is complete.
+///
+/// # Example
+///
+/// ```
+/// use aws_smithy_json::deserialize::error::DeserializeError;
+/// use aws_smithy_json::deserialize::{ByteStreamTokenizer, Token};
+/// use aws_smithy_types::byte_stream::ByteStream;
+///
+/// async fn keys(body: ByteStream) -> Result<Vec<String>, DeserializeError> {
+///     let mut tokenizer = ByteStreamTokenizer::new(body);
+///     let mut keys = Vec::new();
+///     while let Some(token) = tokenizer.next_token().await {
+///         if let Token::ObjectKey { key, .. } = token? {
+///             keys.push(key.to_unescaped()?.into_owned());
+///         }
+///     }
+///     Ok(keys)
+/// }
+/// ```
+#[derive(Debug)]
+pub struct ByteStreamTokenizer {
+    body: ByteStream,
+    tokenizer: IncrementalTokenizer,
+}
+
+impl ByteStreamTokenizer {
+    /// Creates a tokenizer that reads the document from `body`.
+    pub fn new(body: ByteStream) -> Self {
+        Self {
+            body,
+            tokenizer: IncrementalTokenizer::new(),
+        }
+    }
+
+    /// Returns the next token, reading more of the body as needed.
+    ///
+    /// `None` is returned at the end of the document. Failing to read the body is reported as an
+    /// error, and no more tokens are returned after an error.
+    pub async fn next_token(&mut self) -> Option<Result<Token<'_>, Error>> {
+        while !self.tokenizer.is_ready() {
+            match self.body.next().await {
+                Some(Ok(chunk)) => self.tokenizer.push(chunk),
+                Some(Err(err)) => {
+                    self.tokenizer.failed = true;
+                    return Some(Err(Error::custom_source("failed to read the body", err)));
+                }
+                None => self.tokenizer.finish(),
+            }
+        }
+        self.tokenizer.next_token()
+    }
+}
+
+/// Lexical state of the input, used to skip reading tokens until a chunk that may complete them
+/// is pushed.
+///
+/// Only strings need to be tracked for that: outside of them, a token can only be completed by a
+/// structural character, or by the end of a number or a literal.
+#[derive(Debug, Clone, Copy)]
+enum Scan {
+    /// Between tokens, or in whitespace, commas and colons.
+    Between,
+    /// In a string, after an escaping backslash if `escaped`.
+    String { escaped: bool },
+    /// In a number or a literal, e.g. `true`.
+    Word,
+}
+
+impl Scan {
+    /// Scans `chunk`, and returns `true` if it contains the end of a token.
+    fn scan(&mut self, chunk: &[u8]) -> bool {
+        let mut token_end = false;
+        for &byte in chunk {
+            *self = match (*self, byte) {
+                (Scan::String { escaped: true }, _) => Scan::String { escaped: false },
+                (Scan::String { .. }, b'\\') => Scan::String { escaped: true },
+                (Scan::String { .. }, b'"') => {
+                    token_end = true;
+                    Scan::Between
+                }
+                (Scan::String { .. }, _) => Scan::String { escaped: false },
+                (Scan::Word, byte) if !is_delimiter(byte) => Scan::Word,
+                (_, b' ' | b'\t' | b'\n' | b'\r') => {
+                    token_end |= matches!(self, Scan::Word);
+                    Scan::Between
+                }
+                (_, b'"') => {
+                    token_end |= matches!(self, Scan::Word);
+                    Scan::String { escaped: false }
+                }
+                (_, b'{' | b'}' | b'[' | b']' | b',' | b':') => {
+                    token_end = true;
+                    Scan::Between
+                }
+                (_, _) => Scan::Word,
+            };
+        }
+        token_end
+    }
+}
+
+fn is_delimiter(byte: u8) -> bool {
+    matches!(
+        byte,
+        b' ' | b'\t' | b'\n' | b'\r' | b'"' | b'{' | b'}' | b'[' | b']' | b',' | b':'
+    )
+}
+
+/// Returns true if the `result` of reading a token could be different with more input.
+///
+/// Numbers and literals that end at the end of the input may continue in the next chunk, and the
+/// validation of the bytes trailing them hasn't happened yet.
+fn is_incomplete(result: &Option<Result<Token<'_>, Error>>, at_end_of_input: bool) -> bool {
+    match result {
+        None => false,
+        Some(Err(err)) => {
+            matches!(err.kind, ErrorKind::UnexpectedEos)
+                || (at_end_of_input && matches!(err.kind, ErrorKind::InvalidNumber))
+        }
+        Some(Ok(token)) => {
+            at_end_of_input
+                && matches!(
+                    token,
+                    Token::ValueNumber { .. } | Token::ValueBool { .. } | Token::ValueNull { .. }
+                )
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::deserialize::error::DeserializeErrorKind as ErrorKind;
+    use crate::deserialize::{json_token_iter, IncrementalTokenizer, Token};
+    use proptest::prelude::*;
+
+    /// Tokenizes `input` split into the given chunks, rendering each token or error with `Debug`.
+    fn tokenize_chunks<'a>(chunks: impl IntoIterator<Item = &'a [u8]>) -> Vec<String> {
+        let mut tokenizer = IncrementalTokenizer::new();
+        let mut tokens = Vec::new();
+        for chunk in chunks {
+            tokenizer.push(chunk);
+            while let Some(token) = tokenizer.next_token() {
+                tokens.push(format!("{token:?}"));
+            }
+        }
+        tokenizer.finish();
+        while let Some(token) = tokenizer.next_token() {
+            tokens.push(format!("{token:?}"));
+        }
+        tokens
+    }
+
+    fn tokenize_all(input: &[u8]) -> Vec<String> {
+        json_token_iter(input)
+            .map(|token| format!("{token:?}"))
+            .collect()
+    }
+
+    #[track_caller]
+    fn assert_same_tokens_for_every_split(input: &[u8]) {
+        let expected = tokenize_all(input);
+        for chunk_size in 1..=input.len().max(1) {
+            assert_eq!(
+                expected,
+                tokenize_chunks(input.chunks(chunk_size)),
+                "chunk size: {chunk_size}"
+            );
+        }
+        for split in 0..=input.len() {
+            let (left, right) = input.split_at(split);
+            assert_eq!(expected, tokenize_chunks([left, right]), "split: {split}");
+        }
+    }
+
+    const DOCUMENT: &str = r#"{ "some_int": 5,
+        "some_float": 5.2e-3,
+        "some_negative": -5,
+        "some_string": "tést \"escaped\" ✓",
+        "some_struct": { "nested": [true, false, null, {}, []] },
+        "some_array": ["one", "two"] }"#;
+
+    #[test]
+    fn same_tokens_as_json_token_iter() {
+        assert_same_tokens_for_every_split(DOCUMENT.as_bytes());
+        assert_same_tokens_for_every_split(b"12345");
+        assert_same_tokens_for_every_split(b" null true 1.5e10 ");
+        assert_same_tokens_for_every_split(b"");
+    }
+
+    #[test]
+    fn same_errors_as_json_token_iter() {
+        assert_same_tokens_for_every_split(br#"{"test": "trailing", }"#);
+        assert_same_tokens_for_every_split(br#"[null "#);
+        assert_same_tokens_for_every_split(b"[1e-]");
+        assert_same_tokens_for_every_split(b"[truex]");
+        assert_same_tokens_for_every_split(b"[1, 2");
+        assert_same_tokens_for_every_split(b"\"\\u00");
+        assert_same_tokens_for_every_split(b"\"\xFF\"");
+    }
+
+    #[test]
+    fn waits_for_incomplete_tokens() {
+        let mut tokenizer = IncrementalTokenizer::new();
+        tokenizer.push(b"[12");
+        assert!(tokenizer.next_token().unwrap().is_ok());
+        // `12` could be the beginning of a larger number
+        assert!(tokenizer.next_token().is_none());
+        tokenizer.push(b"34, \"ab");
+        assert_eq!(
+            "Some(Ok(ValueNumber { offset: Offset(1), value: PosInt(1234) }))",
+            format!("{:?}", tokenizer.next_token())
+        );
+        assert!(tokenizer.next_token().is_none());
+        tokenizer.push(b"c\"]");
+        assert_eq!(
+            "Some(Ok(ValueString { offset: Offset(7), value: EscapedStr(\"abc\") }))",
+            format!("{:?}", tokenizer.next_token())
+        );
+        assert!(tokenizer.next_token().unwrap().is_ok());
+        assert!(tokenizer.next_token().is_none());
+        assert!(!tokenizer.is_finished());
+        tokenizer.finish();
+        assert!(tokenizer.next_token().is_none());
+    }
+
+    #[test]
+    fn error_offsets_span_chunks() {
+        let mut tokenizer = IncrementalTokenizer::new();
+        tokenizer.push(b"[null, ");
+        assert!(tokenizer.next_token().unwrap().is_ok());
+        assert!(tokenizer.next_token().unwrap().is_ok());
+        tokenizer.push(b"  nul");
+        assert!(tokenizer.next_token().is_none());
+        tokenizer.finish();
+        let err = tokenizer.next_token().unwrap().unwrap_err();
+        assert!(matches!(err.kind, ErrorKind::UnexpectedEos));
+        assert_eq!(Some(12), err.offset);
+        assert!(tokenizer.next_token().is_none());
+    }
+
+    #[test]
+    fn only_buffers_unread_tokens() {
+        let mut tokenizer = IncrementalTokenizer::new();
+        tokenizer.push(b"[\"first\", \"sec");
+        assert!(tokenizer.next_token().unwrap().is_ok());
+        assert!(tokenizer.next_token().unwrap().is_ok());
+        assert!(tokenizer.next_token().is_none());
+        tokenizer.push(b"ond\"]");
+        assert_eq!(b", \"second\"]", &tokenizer.buffer[..]);
+    }
+
+    #[test]
+    fn only_reads_tokens_once_they_may_be_complete() {
+        let mut tokenizer = IncrementalTokenizer::new();
+        tokenizer.push(b"[\"a \\\"");
+        assert!(tokenizer.next_token().unwrap().is_ok());
+        assert!(tokenizer.next_token().is_none());
+        // None of these chunks can complete the string, so it isn't read again
+        for chunk in [
+            &b"quoted\\"[..],
+            b"\" string",
+            b" \\\\ ",
+            b"{}[],:",
+            b"true",
+        ] {
+            tokenizer.push(chunk);
+            assert!(tokenizer.waiting);
+            assert!(tokenizer.next_token().is_none());
+        }
+        tokenizer.push(b"\"]");
+        assert!(!tokenizer.waiting);
+        match tokenizer.next_token() {
+            Some(Ok(Token::ValueString { value, .. })) => assert_eq!(
+                r#"a \"quoted\" string \\ {}[],:true"#,
+                value.as_escaped_str()
+            ),
+            token => panic!("expected a string, got {token:?}"),
+        }
+    }
+
+    #[test]
+    fn long_tokens_split_in_many_chunks() {
+        let string = "x".repeat(4 * 1024 * 1024);
+        let mut tokenizer = IncrementalTokenizer::new();
+        tokenizer.push(b"\"");
+        for chunk in string.as_bytes().chunks(16) {
+            tokenizer.push(chunk);
+            assert!(tokenizer.next_token().is_none());
+        }
+        tokenizer.push(b"\"");
+        match tokenizer.next_token() {
+            Some(Ok(Token::ValueString { value, .. })) => {
+                assert_eq!(string, value.as_escaped_str())
+            }
+            token => panic!("expected a string, got {token:?}"),
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn arbitrary_splits(
+            input in "[\\[\\]{}:, \"a-z0-9.eE+\\-\\\\]{0,40}",
+            splits in proptest::collection::vec(0usize..40, 0..5),
+        ) {
+            let input = input.as_bytes();
+            let mut splits: Vec<usize> =
+                splits.into_iter().filter(|split| *split <= input.len()).collect();
+            splits.sort_unstable();
+            let mut chunks = Vec::new();
+            let mut start = 0;
+            for split in splits {
+                chunks.push(&input[start..split]);
+                start = split;
+            }
+            chunks.push(&input[start..]);
+            prop_assert_eq!(tokenize_all(input), tokenize_chunks(chunks));
+        }
+    }
+
+    mod byte_stream {
+        use crate::deserialize::{ByteStreamTokenizer, Token};
+        use aws_smithy_types::body::SdkBody;
+        use aws_smithy_types::byte_stream::ByteStream;
+        use bytes::Bytes;
+        use std::collections::VecDeque;
+        use std::pin::Pin;
+        use std::task::{Context, Poll};
+
+        /// Body that returns each of its frames separately, ending with an optional error
+        struct Frames {
+            frames: VecDeque<&'static [u8]>,
+            error: Option<&'static str>,
+        }
+
+        impl http_body_0_4::Body for Frames {
+            type Data = Bytes;
+            type Error = &'static str;
+
+            fn poll_data(
+                mut self: Pin<&mut Self>,
+                _cx: &mut Context<'_>,
+            ) -> Poll<Option<Result<Self::Data, Self::Error>>> {
+                Poll::Ready(match self.frames.pop_front() {
+                    Some(frame) => Some(Ok(Bytes::from_static(frame))),
+                    None => self.error.take().map(Err),
+                })
+            }
+
+            fn poll_trailers(
+                self: Pin<&mut Self>,
+                _cx: &mut Context<'_>,
+            ) -> Poll<Result<Option<http::HeaderMap>, Self::Error>> {
+                Poll::Ready(Ok(None))
+            }
+        }
+
+        fn body(frames: &[&'static [u8]], error: Option<&'static str>) -> ByteStream {
+            ByteStream::new(SdkBody::from_body_0_4(Frames {
+                frames: frames.iter().copied().collect(),
+                error,
+            }))
+        }
+
+        #[tokio::test]
+        async fn tokenizes_body_frames() {
+            let mut tokenizer = ByteStreamTokenizer::new(body(
+                &[
+                    b"{\"na",
+                    b"me\": \"sm",
+                    b"ithy\", \"vers",
+                    b"ion\": 2",
+                    b"0}",
+                ],
+                None,
+            ));
+            let mut tokens = Vec::new();
+            while let Some(token) = tokenizer.next_token().await {
+                tokens.push(match token.unwrap() {
+                    Token::ObjectKey { key, .. } => key.as_escaped_str().to_owned(),
+                    Token::ValueString { value, .. } => value.as_escaped_str().to_owned(),
+                    Token::ValueNumber { value, .. } => format!("{value:?}"),
+                    token => format!("{token:?}"),
+                });
+            }
+            assert_eq!(
+                vec![
+                    "StartObject { offset: Offset(0) }",
+                    "name",
+                    "smithy",
+                    "version",
+                    "PosInt(20)",
+                    "EndObject { offset: Offset(32) }",
+                ],
+                tokens
+            );
+        }
+
+        #[tokio::test]
+        async fn body_errors() {
+            let mut tokenizer = ByteStreamTokenizer::new(body(&[b"[1, 2"], Some("boom")));
+            assert!(tokenizer.next_token().await.unwrap().is_ok());
+            assert!(tokenizer.next_token().await.unwrap().is_ok());
+            let err = tokenizer.next_token().await.unwrap().unwrap_err();
+            assert_eq!(
+                "failed to parse JSON: failed to read the body",
+                err.to_string()
+            );
+            assert!(tokenizer.next_token().await.is_none());
+
+            let mut tokenizer = ByteStreamTokenizer::new(body(&[b"[1", b", "], None));
+            assert!(tokenizer.next_token().await.unwrap().is_ok());
+            assert!(tokenizer.next_token().await.unwrap().is_ok());
+            assert!(tokenizer.next_token().await.unwrap().is_err());
+            assert!(tokenizer.next_token().await.is_none());
+        }
+    }
+}
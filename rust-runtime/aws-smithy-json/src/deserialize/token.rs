@@ -4,12 +4,12 @@
  */
 
 use crate::deserialize::error::DeserializeError as Error;
-use crate::deserialize::must_not_be_finite;
+use crate::deserialize::{must_not_be_finite, scan_number_literal};
 use crate::escape::unescape_string;
 pub use crate::escape::EscapeError;
 use aws_smithy_types::date_time::Format;
 use aws_smithy_types::primitive::Parse;
-use aws_smithy_types::{base64, Blob, DateTime, Document, Number};
+use aws_smithy_types::{base64, BigDecimal, BigInteger, Blob, DateTime, Document, Number};
 use std::borrow::Cow;
 use std::collections::HashMap;
 use std::iter::Peekable;
@@ -185,6 +185,50 @@ pub fn expect_number_or_null(
     }
 }
 
+/// Returns the literal of the [Token::ValueNumber] at `offset` in `input`, the JSON document the
+/// token was read from.
+///
+/// Unlike the token's [Number], which is an `f64` for integers that overflow 64 bits, the literal
+/// is exact, so arbitrary precision numbers are parsed from it.
+pub fn number_literal(input: &[u8], offset: Offset) -> Result<&str, Error> {
+    match scan_number_literal(input, offset.0) {
+        "" | "-" => Err(offset.error("expected ValueNumber".into())),
+        literal => Ok(literal),
+    }
+}
+
+/// Expects a [Token::ValueNumber] or [Token::ValueNull] read from `input`, and returns the
+/// number's literal as a [BigInteger] without loss of precision if it's not null.
+pub fn expect_big_integer_or_null(
+    input: &[u8],
+    token: Option<Result<Token<'_>, Error>>,
+) -> Result<Option<BigInteger>, Error> {
+    match token.transpose()? {
+        Some(Token::ValueNull { .. }) => Ok(None),
+        Some(Token::ValueNumber { offset, .. }) => number_literal(input, offset)?
+            .parse()
+            .map(Some)
+            .map_err(|err| Error::custom_source("expected an integer", err).with_offset(offset.0)),
+        _ => Err(Error::custom("expected ValueNumber or ValueNull")),
+    }
+}
+
+/// Expects a [Token::ValueNumber] or [Token::ValueNull] read from `input`, and returns the
+/// number's literal as a [BigDecimal] without loss of precision if it's not null.
+pub fn expect_big_decimal_or_null(
+    input: &[u8],
+    token: Option<Result<Token<'_>, Error>>,
+) -> Result<Option<BigDecimal>, Error> {
+    match token.transpose()? {
+        Some(Token::ValueNull { .. }) => Ok(None),
+        Some(Token::ValueNumber { offset, .. }) => number_literal(input, offset)?
+            .parse()
+            .map(Some)
+            .map_err(|err| Error::custom_source("expected a number", err).with_offset(offset.0)),
+        _ => Err(Error::custom("expected ValueNumber or ValueNull")),
+    }
+}
+
 /// Expects a [Token::ValueString] or [Token::ValueNull]. If the value is a string, it interprets it as a base64 encoded [Blob] value.
 pub fn expect_blob_or_null(token: Option<Result<Token<'_>, Error>>) -> Result<Option<Blob>, Error> {
     Ok(match expect_string_or_null(token)? {
@@ -539,6 +583,84 @@ pub mod test {
         );
     }
 
+    #[test]
+    fn test_expect_big_numbers_or_null() {
+        let big_integer = "-123456789012345678901234567890";
+        let big_decimal = "1.23456789012345678901234567890e-5";
+        let input = format!("[{big_integer}, {big_decimal}, null, 1.5, \"1\"]");
+        let input = input.as_bytes();
+        let mut tokens = json_token_iter(input);
+        tokens.next();
+        assert_eq!(
+            Some(big_integer),
+            expect_big_integer_or_null(input, tokens.next())
+                .unwrap()
+                .as_ref()
+                .map(BigInteger::as_str)
+        );
+        assert_eq!(
+            Some(big_decimal),
+            expect_big_decimal_or_null(input, tokens.next())
+                .unwrap()
+                .as_ref()
+                .map(BigDecimal::as_str)
+        );
+        assert_eq!(
+            None,
+            expect_big_integer_or_null(input, tokens.next()).unwrap()
+        );
+        expect_err_custom(
+            "expected an integer",
+            Some(76),
+            expect_big_integer_or_null(input, tokens.next()),
+        );
+        expect_err_custom(
+            "expected ValueNumber or ValueNull",
+            None,
+            expect_big_decimal_or_null(input, tokens.next()),
+        );
+    }
+
+    #[test]
+    fn test_expect_big_integer_that_overflows_64_bits() {
+        let input = b"{\"value\": 18446744073709551617}";
+        let mut tokens = json_token_iter(input);
+        tokens.next();
+        tokens.next();
+        let token = tokens.next();
+        // The number is lossy, but the literal isn't
+        assert!(matches!(
+            token,
+            Some(Ok(Token::ValueNumber {
+                value: Number::Float(_),
+                ..
+            }))
+        ));
+        assert_eq!(
+            Some("18446744073709551617"),
+            expect_big_integer_or_null(input, token)
+                .unwrap()
+                .as_ref()
+                .map(BigInteger::as_str)
+        );
+    }
+
+    #[test]
+    fn test_number_literal() {
+        let input = b"[-12.5e3,true]";
+        assert_eq!("-12.5e3", number_literal(input, Offset(1)).unwrap());
+        expect_err_custom(
+            "expected ValueNumber",
+            Some(9),
+            number_literal(input, Offset(9)),
+        );
+        expect_err_custom(
+            "expected ValueNumber",
+            Some(20),
+            number_literal(input, Offset(20)),
+        );
+    }
+
     #[test]
     fn test_expect_number_or_null() {
         assert_eq!(None, expect_number_or_null(value_null(0)).unwrap());
@@ -3,6 +3,7 @@
  * SPDX-License-Identifier: Apache-2.0
  */
 
+use crate::scan::find_escapable;
 use std::borrow::Cow;
 use std::fmt;
 
@@ -55,23 +56,20 @@ impl From<EscapeErrorKind> for EscapeError {
 /// Escapes a string for embedding in a JSON string value.
 pub(crate) fn escape_string(value: &str) -> Cow<'_, str> {
     let bytes = value.as_bytes();
-    for (index, byte) in bytes.iter().enumerate() {
-        match byte {
-            0..=0x1F | b'"' | b'\\' => {
-                return Cow::Owned(escape_string_inner(&bytes[0..index], &bytes[index..]))
-            }
-            _ => {}
-        }
+    match find_escapable(bytes) {
+        Some(index) => Cow::Owned(escape_string_inner(&bytes[0..index], &bytes[index..])),
+        None => Cow::Borrowed(value),
     }
-    Cow::Borrowed(value)
 }
 
-fn escape_string_inner(start: &[u8], rest: &[u8]) -> String {
+fn escape_string_inner(start: &[u8], mut rest: &[u8]) -> String {
     let mut escaped = Vec::with_capacity(start.len() + rest.len() + 1);
     escaped.extend(start);
 
-    for byte in rest {
-        match byte {
+    // Copy the runs of bytes that don't need escaping in bulk
+    while let Some(index) = find_escapable(rest) {
+        escaped.extend_from_slice(&rest[..index]);
+        match rest[index] {
             b'"' => escaped.extend(b"\\\""),
             b'\\' => escaped.extend(b"\\\\"),
             0x08 => escaped.extend(b"\\b"),
@@ -79,10 +77,12 @@ fn escape_string_inner(start: &[u8], rest: &[u8]) -> String {
             b'\n' => escaped.extend(b"\\n"),
             b'\r' => escaped.extend(b"\\r"),
             b'\t' => escaped.extend(b"\\t"),
-            0..=0x1F => escaped.extend(format!("\\u{:04x}", byte).bytes()),
-            _ => escaped.push(*byte),
+            byte @ 0..=0x1F => escaped.extend(format!("\\u{:04x}", byte).bytes()),
+            byte => unreachable!("{byte:#X} doesn't need escaping"),
         }
+        rest = &rest[index + 1..];
     }
+    escaped.extend_from_slice(rest);
 
     // This is safe because:
     // - The original input was valid UTF-8 since it came in as a `&str`
@@ -299,6 +299,14 @@ mod test {
             assert_eq!(serde_escaped,escape_string(&s))
         }
 
+        // Long enough to exercise the vectorized search, with sparse escapes
+        #[test]
+        fn matches_serde_json_long(s in "([a-zA-Z0-9 \u{80}-\u{10FFFF}]{0,40}[\"\\\\\\x00-\\x1F]?){0,8}") {
+            let serde_escaped = serde_json::to_string(&s).unwrap();
+            let serde_escaped = &serde_escaped[1..(serde_escaped.len() - 1)];
+            assert_eq!(serde_escaped,escape_string(&s))
+        }
+
         #[test]
         fn round_trip(chr in proptest::char::any()) {
             let mut original = String::new();
@@ -19,4 +19,5 @@
 
 pub mod deserialize;
 mod escape;
+mod scan;
 pub mod serialize;
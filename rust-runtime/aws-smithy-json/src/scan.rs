@@ -0,0 +1,208 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! Vectorized search for the bytes that need attention inside of JSON strings.
+//!
+//! Both escaping a string and scanning a string token skip over ordinary bytes until a quote,
+//! a backslash, or a control character. On `x86_64` (SSE2, and AVX2 when detected at runtime) and
+//! `aarch64` (NEON), 16 or 32 bytes are compared at a time. Other targets, and inputs shorter than
+//! a vector, use the scalar implementation.
+
+/// Returns the index of the first `"`, `\`, or control character (`0x00..=0x1F`) in `bytes`.
+pub(crate) fn find_escapable(bytes: &[u8]) -> Option<usize> {
+    #[cfg(target_arch = "x86_64")]
+    {
+        x86_64::find_escapable(bytes)
+    }
+    #[cfg(target_arch = "aarch64")]
+    {
+        aarch64::find_escapable(bytes)
+    }
+    #[cfg(not(any(target_arch = "x86_64", target_arch = "aarch64")))]
+    {
+        scalar::find_escapable(bytes)
+    }
+}
+
+mod scalar {
+    pub(super) fn find_escapable(bytes: &[u8]) -> Option<usize> {
+        bytes
+            .iter()
+            .position(|byte| matches!(byte, 0..=0x1F | b'"' | b'\\'))
+    }
+}
+
+#[cfg(target_arch = "x86_64")]
+mod x86_64 {
+    use super::scalar;
+    use std::arch::x86_64::*;
+
+    pub(super) fn find_escapable(bytes: &[u8]) -> Option<usize> {
+        if bytes.len() >= 32 && is_x86_feature_detected!("avx2") {
+            // SAFETY: AVX2 support was just checked
+            unsafe { avx2(bytes) }
+        } else {
+            sse2(bytes)
+        }
+    }
+
+    /// SSE2 is part of the `x86_64` baseline, so it is always available.
+    pub(super) fn sse2(bytes: &[u8]) -> Option<usize> {
+        let mut offset = 0;
+        // SAFETY: each load reads 16 bytes starting at `offset`, with `offset + 16 <= bytes.len()`
+        unsafe {
+            let (quote, backslash, max_control) = (
+                _mm_set1_epi8(b'"' as i8),
+                _mm_set1_epi8(b'\\' as i8),
+                _mm_set1_epi8(0x1F),
+            );
+            while offset + 16 <= bytes.len() {
+                let chunk = _mm_loadu_si128(bytes.as_ptr().add(offset) as *const __m128i);
+                let matches = _mm_or_si128(
+                    _mm_or_si128(
+                        _mm_cmpeq_epi8(chunk, quote),
+                        _mm_cmpeq_epi8(chunk, backslash),
+                    ),
+                    // There is no unsigned comparison: a byte is a control character if it is
+                    // unchanged by an unsigned minimum with 0x1F.
+                    _mm_cmpeq_epi8(_mm_min_epu8(chunk, max_control), chunk),
+                );
+                let mask = _mm_movemask_epi8(matches) as u32;
+                if mask != 0 {
+                    return Some(offset + mask.trailing_zeros() as usize);
+                }
+                offset += 16;
+            }
+        }
+        scalar::find_escapable(&bytes[offset..]).map(|index| offset + index)
+    }
+
+    /// # Safety
+    ///
+    /// The CPU must support AVX2.
+    #[target_feature(enable = "avx2")]
+    pub(super) unsafe fn avx2(bytes: &[u8]) -> Option<usize> {
+        let (quote, backslash, max_control) = (
+            _mm256_set1_epi8(b'"' as i8),
+            _mm256_set1_epi8(b'\\' as i8),
+            _mm256_set1_epi8(0x1F),
+        );
+        let mut offset = 0;
+        while offset + 32 <= bytes.len() {
+            // SAFETY: reads 32 bytes starting at `offset`, with `offset + 32 <= bytes.len()`
+            let chunk = _mm256_loadu_si256(bytes.as_ptr().add(offset) as *const __m256i);
+            let matches = _mm256_or_si256(
+                _mm256_or_si256(
+                    _mm256_cmpeq_epi8(chunk, quote),
+                    _mm256_cmpeq_epi8(chunk, backslash),
+                ),
+                _mm256_cmpeq_epi8(_mm256_min_epu8(chunk, max_control), chunk),
+            );
+            let mask = _mm256_movemask_epi8(matches) as u32;
+            if mask != 0 {
+                return Some(offset + mask.trailing_zeros() as usize);
+            }
+            offset += 32;
+        }
+        sse2(&bytes[offset..]).map(|index| offset + index)
+    }
+}
+
+#[cfg(target_arch = "aarch64")]
+mod aarch64 {
+    use super::scalar;
+    use std::arch::aarch64::*;
+
+    /// NEON is part of the `aarch64` baseline, so it is always available.
+    pub(super) fn find_escapable(bytes: &[u8]) -> Option<usize> {
+        let mut offset = 0;
+        // SAFETY: each load reads 16 bytes starting at `offset`, with `offset + 16 <= bytes.len()`
+        unsafe {
+            let (quote, backslash, min_non_control) =
+                (vdupq_n_u8(b'"'), vdupq_n_u8(b'\\'), vdupq_n_u8(0x20));
+            while offset + 16 <= bytes.len() {
+                let chunk = vld1q_u8(bytes.as_ptr().add(offset));
+                let matches = vorrq_u8(
+                    vorrq_u8(vceqq_u8(chunk, quote), vceqq_u8(chunk, backslash)),
+                    vcltq_u8(chunk, min_non_control),
+                );
+                // NEON has no movemask: narrowing each 16-bit lane by 4 bits leaves 4 bits per
+                // byte.
+                let mask = vget_lane_u64(
+                    vreinterpret_u64_u8(vshrn_n_u16(vreinterpretq_u16_u8(matches), 4)),
+                    0,
+                );
+                if mask != 0 {
+                    return Some(offset + (mask.trailing_zeros() / 4) as usize);
+                }
+                offset += 16;
+            }
+        }
+        scalar::find_escapable(&bytes[offset..]).map(|index| offset + index)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{find_escapable, scalar};
+    use proptest::prelude::*;
+
+    type Find = fn(&[u8]) -> Option<usize>;
+
+    fn implementations() -> Vec<(&'static str, Find)> {
+        #[allow(unused_mut)]
+        let mut implementations: Vec<(&'static str, Find)> = vec![("dispatch", find_escapable)];
+        #[cfg(target_arch = "x86_64")]
+        {
+            implementations.push(("sse2", super::x86_64::sse2));
+            if is_x86_feature_detected!("avx2") {
+                // SAFETY: AVX2 support was just checked
+                implementations.push(("avx2", |bytes| unsafe { super::x86_64::avx2(bytes) }));
+            }
+        }
+        #[cfg(target_arch = "aarch64")]
+        implementations.push(("neon", super::aarch64::find_escapable));
+        implementations
+    }
+
+    #[test]
+    fn every_position_and_byte() {
+        for len in 0..70 {
+            for position in 0..len {
+                for byte in 0..=u8::MAX {
+                    let mut bytes = vec![b'a'; len];
+                    bytes[position] = byte;
+                    let expected = scalar::find_escapable(&bytes);
+                    for (name, find) in implementations() {
+                        assert_eq!(expected, find(&bytes), "{name}: {byte:#X} at {position}");
+                    }
+                }
+            }
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn matches_scalar(
+            bytes in proptest::collection::vec(any::<u8>(), 0..200),
+            start in 0usize..16,
+        ) {
+            // Vary the alignment of the input
+            let bytes = &bytes[start.min(bytes.len())..];
+            let expected = scalar::find_escapable(bytes);
+            for (name, find) in implementations() {
+                prop_assert_eq!(expected, find(bytes), "{}", name);
+            }
+        }
+
+        #[test]
+        fn matches_scalar_text(text in "[a-z \"\\\\\t\u{80}-\u{10FFFF}]{0,100}") {
+            let expected = scalar::find_escapable(text.as_bytes());
+            for (name, find) in implementations() {
+                prop_assert_eq!(expected, find(text.as_bytes()), "{}", name);
+            }
+        }
+    }
+}
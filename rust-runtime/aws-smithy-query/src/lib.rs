@@ -18,7 +18,7 @@
 
 use aws_smithy_types::date_time::{DateTimeFormatError, Format};
 use aws_smithy_types::primitive::Encoder;
-use aws_smithy_types::{DateTime, Number};
+use aws_smithy_types::{BigDecimal, BigInteger, DateTime, Number};
 use std::borrow::Cow;
 use std::fmt::Write;
 use urlencoding::encode;
@@ -201,6 +201,16 @@ impl<'a> QueryValueWriter<'a> {
         }
     }
 
+    /// Writes an arbitrary precision integer `value` without loss of precision.
+    pub fn big_integer(self, value: &BigInteger) {
+        self.string(value.as_str());
+    }
+
+    /// Writes an arbitrary precision decimal `value` without loss of precision.
+    pub fn big_decimal(self, value: &BigDecimal) {
+        self.string(value.as_str());
+    }
+
     /// Writes a date-time `value` with the given `format`.
     pub fn date_time(
         self,
@@ -408,6 +418,12 @@ mod tests {
             .number(Number::Float(f64::NEG_INFINITY));
         writer.prefix("NaN").number(Number::Float(f64::NAN));
         writer.prefix("Floating").number(Number::Float(5.2));
+        writer
+            .prefix("BigInteger")
+            .big_integer(&"-123456789012345678901234567890".parse().unwrap());
+        writer
+            .prefix("BigDecimal")
+            .big_decimal(&"1.5e+400".parse().unwrap());
         writer.finish();
 
         assert_eq!(
@@ -419,6 +435,8 @@ mod tests {
             &NegInfinity=-Infinity\
             &NaN=NaN\
             &Floating=5.2\
+            &BigInteger=-123456789012345678901234567890\
+            &BigDecimal=1.5e%2B400\
             ",
             out
         );
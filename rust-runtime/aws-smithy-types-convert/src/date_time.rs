@@ -4,8 +4,9 @@
  */
 
 //! Conversions from [`DateTime`] to the types in the
-//! [`time`](https://crates.io/crates/time) or
-//! [`chrono`](https://crates.io/crates/chrono)
+//! [`time`](https://crates.io/crates/time),
+//! [`chrono`](https://crates.io/crates/chrono), or
+//! [`jiff`](https://crates.io/crates/jiff)
 //! crates.
 
 use aws_smithy_types::DateTime;
@@ -53,7 +54,7 @@ impl fmt::Display for Error {
     }
 }
 
-/// Adds functions to [`DateTime`] to convert it to `time` or `chrono` types.
+/// Adds functions to [`DateTime`] to convert it to `time`, `chrono`, or `jiff` types.
 ///
 #[cfg_attr(
     feature = "convert-time",
@@ -102,6 +103,31 @@ let date_time: DateTime = DateTime::from_chrono_utc(chrono_date_time);
 ```
 "##
 )]
+#[cfg_attr(
+    feature = "convert-jiff",
+    doc = r##"
+# Example with `jiff`
+
+Make sure your **Cargo.toml** enables the `convert-jiff` feature:
+```toml
+[dependencies]
+aws-smithy-types-convert = { version = "VERSION", features = ["convert-jiff"] }
+```
+
+Then import [`DateTimeExt`] to use the conversions:
+```rust
+# use aws_smithy_types::DateTime;
+use aws_smithy_types_convert::date_time::DateTimeExt;
+use jiff::{tz::TimeZone, Timestamp};
+
+let timestamp: Timestamp = DateTime::from_secs(5).to_jiff_timestamp().unwrap();
+let date_time: DateTime = DateTime::from_jiff_timestamp(timestamp);
+
+let zoned = timestamp.to_zoned(TimeZone::fixed(jiff::tz::offset(-7)));
+assert_eq!(date_time, DateTime::from_jiff_zoned(&zoned));
+```
+"##
+)]
 pub trait DateTimeExt {
     /// Converts a [`DateTime`] to a [`chrono::DateTime`] with timezone UTC.
     #[cfg(feature = "convert-chrono")]
@@ -125,6 +151,21 @@ pub trait DateTimeExt {
     /// Converts a [`time::OffsetDateTime`] to a [`DateTime`].
     #[cfg(feature = "convert-time")]
     fn from_time(time: time::OffsetDateTime) -> DateTime;
+
+    /// Converts a [`DateTime`] to a [`jiff::Timestamp`].
+    ///
+    /// Returns an [`Error`] if the time is after
+    /// `9999-12-30T22:00:00Z` or before `-009999-01-02T01:59:59Z`.
+    #[cfg(feature = "convert-jiff")]
+    fn to_jiff_timestamp(&self) -> Result<jiff::Timestamp, Error>;
+
+    /// Converts a [`jiff::Timestamp`] to a [`DateTime`].
+    #[cfg(feature = "convert-jiff")]
+    fn from_jiff_timestamp(timestamp: jiff::Timestamp) -> DateTime;
+
+    /// Converts a [`jiff::Zoned`] in any time zone to a [`DateTime`].
+    #[cfg(feature = "convert-jiff")]
+    fn from_jiff_zoned(zoned: &jiff::Zoned) -> DateTime;
 }
 
 impl DateTimeExt for DateTime {
@@ -162,15 +203,48 @@ impl DateTimeExt for DateTime {
         DateTime::from_nanos(time.unix_timestamp_nanos())
             .expect("DateTime supports a greater range than OffsetDateTime")
     }
+
+    #[cfg(feature = "convert-jiff")]
+    fn to_jiff_timestamp(&self) -> Result<jiff::Timestamp, Error> {
+        let nanos = self.as_nanos();
+        let range = jiff::Timestamp::MIN.as_nanosecond()..=jiff::Timestamp::MAX.as_nanosecond();
+        if !range.contains(&nanos) {
+            return Err(Error::out_of_range(format!(
+                "out-of-range seconds {} or invalid nanoseconds {}",
+                self.secs(),
+                self.subsec_nanos()
+            )));
+        }
+        jiff::Timestamp::from_nanosecond(nanos).map_err(Error::out_of_range)
+    }
+
+    #[cfg(feature = "convert-jiff")]
+    fn from_jiff_timestamp(timestamp: jiff::Timestamp) -> DateTime {
+        DateTime::from_nanos(timestamp.as_nanosecond())
+            .expect("DateTime supports a greater range than Timestamp")
+    }
+
+    #[cfg(feature = "convert-jiff")]
+    fn from_jiff_zoned(zoned: &jiff::Zoned) -> DateTime {
+        Self::from_jiff_timestamp(zoned.timestamp())
+    }
 }
 
-#[cfg(all(test, any(feature = "convert-chrono", feature = "convert-time")))]
+#[cfg(all(
+    test,
+    any(
+        feature = "convert-chrono",
+        feature = "convert-time",
+        feature = "convert-jiff"
+    )
+))]
 mod test {
     use super::DateTimeExt;
     use aws_smithy_types::date_time::{DateTime, Format};
+    #[cfg(feature = "convert-chrono")]
     use chrono::Timelike;
 
-    #[cfg(feature = "convert-time")]
+    #[cfg(any(feature = "convert-time", feature = "convert-jiff"))]
     use super::{Error, ErrorKind};
 
     #[test]
@@ -284,4 +358,59 @@ mod test {
             })
         ));
     }
+
+    #[test]
+    #[cfg(feature = "convert-jiff")]
+    fn from_jiff() {
+        use jiff::civil::date;
+        use jiff::tz::TimeZone;
+
+        let zoned = date(2039, 7, 8)
+            .at(9, 3, 11, 123_000_000)
+            .to_zoned(TimeZone::UTC)
+            .unwrap();
+        let expected = DateTime::from_str("2039-07-08T09:03:11.123Z", Format::DateTime).unwrap();
+        assert_eq!(expected, DateTime::from_jiff_timestamp(zoned.timestamp()));
+        assert_eq!(expected, DateTime::from_jiff_zoned(&zoned));
+
+        let zoned = date(1000, 7, 8)
+            .at(9, 3, 11, 456_000_000)
+            .to_zoned(TimeZone::fixed(jiff::tz::offset(-2)))
+            .unwrap();
+        let expected = DateTime::from_str("1000-07-08T11:03:11.456Z", Format::DateTime).unwrap();
+        assert_eq!(expected, DateTime::from_jiff_zoned(&zoned));
+    }
+
+    #[test]
+    #[cfg(feature = "convert-jiff")]
+    fn to_jiff() {
+        use jiff::civil::date;
+        use jiff::tz::TimeZone;
+
+        let date_time = DateTime::from_str("2039-07-08T09:03:11.123Z", Format::DateTime).unwrap();
+        let expected = date(2039, 7, 8)
+            .at(9, 3, 11, 123_000_000)
+            .to_zoned(TimeZone::UTC)
+            .unwrap()
+            .timestamp();
+        assert_eq!(expected, date_time.to_jiff_timestamp().unwrap());
+
+        let date_time = DateTime::from_secs_and_nanos(-1, 999_999_999);
+        assert_eq!(
+            jiff::Timestamp::from_nanosecond(-1).unwrap(),
+            date_time.to_jiff_timestamp().unwrap()
+        );
+
+        for date_time in [
+            DateTime::from_secs_and_nanos(i64::MAX, 0),
+            DateTime::from_secs_and_nanos(i64::MIN, 0),
+        ] {
+            assert!(matches!(
+                date_time.to_jiff_timestamp(),
+                Err(Error {
+                    kind: ErrorKind::OutOfRange(_)
+                })
+            ));
+        }
+    }
 }
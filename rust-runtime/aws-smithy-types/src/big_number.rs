@@ -0,0 +1,392 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! Arbitrary precision numbers for the Smithy `bigInteger` and `bigDecimal` shapes.
+//!
+//! [`BigInteger`] and [`BigDecimal`] hold the decimal literal of a number exactly as it was parsed,
+//! so that values that don't fit an `i64` or `f64` round trip without losing precision. They don't
+//! implement arithmetic: convert them to the arbitrary precision type of your choice, for example
+//! with `big.as_str().parse()`.
+
+use std::error::Error;
+use std::fmt;
+use std::str::FromStr;
+
+/// An arbitrary precision integer.
+///
+/// The integer is stored as a decimal literal: an optional `-` sign followed by digits, without
+/// leading zeros. Equality compares the literals.
+///
+/// ```
+/// use aws_smithy_types::BigInteger;
+///
+/// let big: BigInteger = "-123456789012345678901234567890".parse().unwrap();
+/// assert_eq!("-123456789012345678901234567890", big.as_str());
+/// assert_eq!(BigInteger::from(42u64), "42".parse().unwrap());
+/// assert!("1.5".parse::<BigInteger>().is_err());
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct BigInteger(String);
+
+/// An arbitrary precision decimal number.
+///
+/// The number is stored as a decimal literal following the JSON number grammar: an optional `-`
+/// sign, an integer part without leading zeros, and optional fraction and exponent parts.
+/// Equality compares the literals, so `1.0` and `1.00` are different values.
+///
+/// ```
+/// use aws_smithy_types::BigDecimal;
+///
+/// let big: BigDecimal = "0.1000000000000000000000000001".parse().unwrap();
+/// assert_eq!("0.1000000000000000000000000001", big.as_str());
+/// assert_eq!(0.1, big.to_f64_lossy());
+/// assert!("1e400".parse::<BigDecimal>().is_ok());
+/// assert!(".5".parse::<BigDecimal>().is_err());
+/// ```
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct BigDecimal(String);
+
+impl BigInteger {
+    /// Returns the decimal literal of this integer.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Converts to an `f64` lossily.
+    pub fn to_f64_lossy(&self) -> f64 {
+        self.0.parse().expect("integer literals are valid floats")
+    }
+}
+
+impl BigDecimal {
+    /// Returns the decimal literal of this number.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    /// Converts to an `f64` lossily.
+    ///
+    /// Numbers whose magnitude is too large for an `f64` are converted to an infinity.
+    pub fn to_f64_lossy(&self) -> f64 {
+        self.0.parse().expect("decimal literals are valid floats")
+    }
+}
+
+/// Returns the length of the run of ASCII digits at the start of `bytes`.
+fn digits(bytes: &[u8]) -> usize {
+    bytes.iter().take_while(|b| b.is_ascii_digit()).count()
+}
+
+/// Returns the length of the optionally negative integer literal at the start of `bytes`.
+fn integer_literal(bytes: &[u8]) -> Option<usize> {
+    let sign = usize::from(bytes.first() == Some(&b'-'));
+    match (digits(&bytes[sign..]), bytes.get(sign)) {
+        (0, _) => None,
+        (len, Some(b'0')) if len > 1 => None,
+        (len, _) => Some(sign + len),
+    }
+}
+
+fn is_decimal_literal(bytes: &[u8]) -> bool {
+    let Some(mut index) = integer_literal(bytes) else {
+        return false;
+    };
+    if bytes.get(index) == Some(&b'.') {
+        match digits(&bytes[index + 1..]) {
+            0 => return false,
+            len => index += 1 + len,
+        }
+    }
+    if matches!(bytes.get(index), Some(b'e' | b'E')) {
+        index += 1;
+        if matches!(bytes.get(index), Some(b'+' | b'-')) {
+            index += 1;
+        }
+        match digits(&bytes[index..]) {
+            0 => return false,
+            len => index += len,
+        }
+    }
+    index == bytes.len()
+}
+
+impl FromStr for BigInteger {
+    type Err = BigNumberParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if integer_literal(s.as_bytes()) == Some(s.len()) {
+            Ok(Self(s.into()))
+        } else {
+            Err(BigNumberParseError("BigInteger"))
+        }
+    }
+}
+
+impl FromStr for BigDecimal {
+    type Err = BigNumberParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if is_decimal_literal(s.as_bytes()) {
+            Ok(Self(s.into()))
+        } else {
+            Err(BigNumberParseError("BigDecimal"))
+        }
+    }
+}
+
+impl fmt::Display for BigInteger {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl fmt::Display for BigDecimal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl AsRef<str> for BigInteger {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+impl AsRef<str> for BigDecimal {
+    fn as_ref(&self) -> &str {
+        &self.0
+    }
+}
+
+macro_rules! from_integer {
+    ($($typ:ty),*) => {
+        $(
+            impl From<$typ> for BigInteger {
+                fn from(value: $typ) -> Self {
+                    Self(value.to_string())
+                }
+            }
+
+            impl From<$typ> for BigDecimal {
+                fn from(value: $typ) -> Self {
+                    Self(value.to_string())
+                }
+            }
+        )*
+    };
+}
+
+from_integer!(i8, i16, i32, i64, i128, u8, u16, u32, u64, u128);
+
+impl From<BigInteger> for BigDecimal {
+    fn from(value: BigInteger) -> Self {
+        Self(value.0)
+    }
+}
+
+/// The error returned when a string isn't a valid [`BigInteger`] or [`BigDecimal`] literal.
+#[derive(Debug)]
+pub struct BigNumberParseError(&'static str);
+
+impl fmt::Display for BigNumberParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "failed to parse input as {}", self.0)
+    }
+}
+
+impl Error for BigNumberParseError {}
+
+#[cfg(feature = "serde-serialize")]
+mod ser {
+    use super::{BigDecimal, BigInteger};
+
+    /// Serialized as a string, since most formats can't represent arbitrary precision numbers.
+    impl serde::Serialize for BigInteger {
+        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: serde::Serializer,
+        {
+            serializer.serialize_str(&self.0)
+        }
+    }
+
+    /// Serialized as a string, since most formats can't represent arbitrary precision numbers.
+    impl serde::Serialize for BigDecimal {
+        fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+        where
+            S: serde::Serializer,
+        {
+            serializer.serialize_str(&self.0)
+        }
+    }
+}
+
+#[cfg(feature = "serde-deserialize")]
+mod de {
+    use super::{BigDecimal, BigInteger};
+    use std::fmt;
+    use std::marker::PhantomData;
+    use std::str::FromStr;
+
+    /// Deserializes a big number from a string or, for convenience, from a 64-bit integer.
+    struct BigNumberVisitor<T>(PhantomData<T>);
+
+    impl<T> serde::de::Visitor<'_> for BigNumberVisitor<T>
+    where
+        T: FromStr + From<i64> + From<u64>,
+        T::Err: fmt::Display,
+    {
+        type Value = T;
+
+        fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+            formatter.write_str("a decimal number literal")
+        }
+
+        fn visit_i64<E>(self, v: i64) -> Result<Self::Value, E>
+        where
+            E: serde::de::Error,
+        {
+            Ok(T::from(v))
+        }
+
+        fn visit_u64<E>(self, v: u64) -> Result<Self::Value, E>
+        where
+            E: serde::de::Error,
+        {
+            Ok(T::from(v))
+        }
+
+        fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+        where
+            E: serde::de::Error,
+        {
+            T::from_str(v).map_err(E::custom)
+        }
+    }
+
+    impl<'de> serde::Deserialize<'de> for BigInteger {
+        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: serde::Deserializer<'de>,
+        {
+            deserializer.deserialize_any(BigNumberVisitor(PhantomData))
+        }
+    }
+
+    impl<'de> serde::Deserialize<'de> for BigDecimal {
+        fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+        where
+            D: serde::Deserializer<'de>,
+        {
+            deserializer.deserialize_any(BigNumberVisitor(PhantomData))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{BigDecimal, BigInteger};
+    use proptest::prelude::*;
+
+    #[test]
+    fn parse_big_integer() {
+        for valid in [
+            "0",
+            "-0",
+            "7",
+            "-7",
+            "18446744073709551616",
+            "-99999999999999999999999",
+        ] {
+            assert_eq!(valid, valid.parse::<BigInteger>().unwrap().as_str());
+        }
+        for invalid in [
+            "", "-", "+1", "01", "-01", "1.0", "1e5", " 1", "1 ", "0x10", "١",
+        ] {
+            assert!(invalid.parse::<BigInteger>().is_err(), "{invalid:?}");
+        }
+    }
+
+    #[test]
+    fn parse_big_decimal() {
+        for valid in [
+            "0",
+            "-0.0",
+            "1.5",
+            "12345678901234567890.12345678901234567890",
+            "1e400",
+            "-1E-400",
+            "2.5e+3",
+            "0.001",
+        ] {
+            assert_eq!(valid, valid.parse::<BigDecimal>().unwrap().as_str());
+        }
+        for invalid in [
+            "", "-", "+1", "01.5", ".5", "5.", "1e", "1e+", "1.e5", "NaN", "Infinity", "1.5 ",
+        ] {
+            assert!(invalid.parse::<BigDecimal>().is_err(), "{invalid:?}");
+        }
+    }
+
+    #[test]
+    fn conversions() {
+        assert_eq!("-5", BigInteger::from(-5i32).as_str());
+        assert_eq!(
+            "340282366920938463463374607431768211455",
+            BigInteger::from(u128::MAX).as_str()
+        );
+        assert_eq!(
+            BigDecimal::from(u64::MAX),
+            BigDecimal::from(BigInteger::from(u64::MAX))
+        );
+        assert_eq!(
+            1e30,
+            "1000000000000000000000000000000"
+                .parse::<BigInteger>()
+                .unwrap()
+                .to_f64_lossy()
+        );
+        assert_eq!(
+            f64::INFINITY,
+            "1e400".parse::<BigDecimal>().unwrap().to_f64_lossy()
+        );
+    }
+
+    #[test]
+    #[cfg(all(feature = "serde-serialize", feature = "serde-deserialize"))]
+    fn serde() {
+        let big: BigDecimal = "3.14159265358979323846264338327950288".parse().unwrap();
+        let json = serde_json::to_string(&big).unwrap();
+        assert_eq!(r#""3.14159265358979323846264338327950288""#, json);
+        assert_eq!(big, serde_json::from_str(&json).unwrap());
+
+        assert_eq!(BigInteger::from(-5i64), serde_json::from_str("-5").unwrap());
+        assert_eq!(BigDecimal::from(5u64), serde_json::from_str("5").unwrap());
+        assert!(serde_json::from_str::<BigInteger>(r#""1.5""#).is_err());
+    }
+
+    proptest! {
+        #[test]
+        fn parses_integers(value: i128) {
+            prop_assert_eq!(BigInteger::from(value), value.to_string().parse().unwrap());
+        }
+
+        #[test]
+        fn parses_floats(value: f64) {
+            prop_assume!(value.is_finite());
+            let literal = format!("{value:e}");
+            let big = literal.parse::<BigDecimal>().unwrap();
+            prop_assert_eq!(value, big.to_f64_lossy());
+        }
+
+        #[test]
+        fn decimal_literals_are_valid_floats(
+            literal in "-?(0|[1-9][0-9]{0,30})(\\.[0-9]{1,30})?([eE][+-]?[0-9]{1,4})?"
+        ) {
+            let big = literal.parse::<BigDecimal>().unwrap();
+            prop_assert!(big.as_str().parse::<f64>().is_ok());
+        }
+    }
+}
@@ -31,7 +31,7 @@ impl AsRef<[u8]> for Blob {
     }
 }
 
-#[cfg(all(aws_sdk_unstable, feature = "serde-serialize"))]
+#[cfg(feature = "serde-serialize")]
 mod serde_serialize {
     use super::*;
     use serde::Serialize;
@@ -50,7 +50,7 @@ mod serde_serialize {
     }
 }
 
-#[cfg(all(aws_sdk_unstable, feature = "serde-deserialize"))]
+#[cfg(feature = "serde-deserialize")]
 mod serde_deserialize {
     use super::*;
     use serde::{de::Visitor, Deserialize};
@@ -103,11 +103,7 @@ mod serde_deserialize {
 }
 
 #[cfg(test)]
-#[cfg(all(
-    aws_sdk_unstable,
-    feature = "serde-serialize",
-    feature = "serde-deserialize"
-))]
+#[cfg(all(feature = "serde-serialize", feature = "serde-deserialize"))]
 mod test_serde {
     use crate::Blob;
     use serde::{Deserialize, Serialize};
@@ -146,7 +142,7 @@ mod test_serde {
         // checks whether the bytes are deserialized properly
         let n: HashMap<String, CString> =
             ciborium::de::from_reader(std::io::Cursor::new(buf.clone())).unwrap();
-        assert!(n.get("blob").is_some());
+        assert!(n.contains_key("blob"));
         assert!(n.get("blob") == CString::new([65, 87, 83]).ok().as_ref());
 
         let de: ForTest = ciborium::de::from_reader(std::io::Cursor::new(buf)).unwrap();
@@ -122,6 +122,27 @@
 //! }
 //! # }
 //! ```
+//!
+//! ### Observe or throttle a ByteStream
+//!
+//! _Note: This is only available with `http-body-0-4-x` enabled. Rate limiting also requires
+//! `rt-tokio`._
+//!
+//! [`ByteStream::with_progress`], [`ByteStream::rate_limit`], and [`ByteStream::tee`] report
+//! progress, cap throughput with a [`BandwidthLimiter`] shared between streams, and copy the data
+//! to a second sink. They don't change the data, so retryable streams stay retryable.
+//!
+//! ```no_run
+//! # #[cfg(all(feature = "http-body-0-4-x", feature = "rt-tokio"))]
+//! # {
+//! use aws_smithy_types::byte_stream::{BandwidthLimiter, ByteStream};
+//!
+//! let limiter = BandwidthLimiter::new(10 * 1024 * 1024);
+//! let stream = ByteStream::from_static(b"some data")
+//!     .rate_limit(&limiter)
+//!     .with_progress(|progress| println!("read {} bytes", progress.bytes_transferred()));
+//! # }
+//! ```
 
 use crate::body::SdkBody;
 use crate::byte_stream::error::Error;
@@ -141,6 +162,13 @@ pub use bytestream_util::Length;
 
 pub mod error;
 
+#[cfg(feature = "http-body-0-4-x")]
+mod adapters;
+#[cfg(all(feature = "http-body-0-4-x", feature = "rt-tokio"))]
+pub use adapters::BandwidthLimiter;
+#[cfg(feature = "http-body-0-4-x")]
+pub use adapters::{Progress, Tee};
+
 #[cfg(feature = "rt-tokio")]
 pub use self::bytestream_util::FsBuilder;
 
@@ -0,0 +1,600 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! Adapters that observe or throttle the data read from a [`ByteStream`] or [`SdkBody`].
+//!
+//! None of the adapters change the data of the body, so they keep the body retryable when it was
+//! retryable to begin with. Each retry starts a new attempt from the beginning of the body.
+
+use crate::body::{Error, SdkBody};
+use crate::byte_stream::ByteStream;
+use bytes::Bytes;
+#[cfg(feature = "rt-tokio")]
+use std::fmt;
+#[cfg(feature = "rt-tokio")]
+use std::future::Future as _;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{ready, Context, Poll};
+use std::time::{Duration, Instant};
+
+/// The progress of reading a [`ByteStream`] or [`SdkBody`], as reported to the callback given to
+/// [`ByteStream::with_progress`].
+#[derive(Clone, Copy, Debug)]
+pub struct Progress {
+    bytes_transferred: u64,
+    total_bytes: Option<u64>,
+    elapsed: Duration,
+}
+
+impl Progress {
+    /// Returns the number of bytes read so far.
+    pub fn bytes_transferred(&self) -> u64 {
+        self.bytes_transferred
+    }
+
+    /// Returns the total number of bytes in the body, if the body has a known length.
+    pub fn total_bytes(&self) -> Option<u64> {
+        self.total_bytes
+    }
+
+    /// Returns the time elapsed since the body was first polled.
+    pub fn elapsed(&self) -> Duration {
+        self.elapsed
+    }
+
+    /// Returns the average number of bytes read per second since the body was first polled.
+    pub fn bytes_per_second(&self) -> f64 {
+        match self.elapsed.as_secs_f64() {
+            secs if secs > 0.0 => self.bytes_transferred as f64 / secs,
+            _ => 0.0,
+        }
+    }
+}
+
+type ProgressCallback = Arc<dyn Fn(Progress) + Send + Sync>;
+
+struct ProgressBody {
+    inner: SdkBody,
+    on_progress: ProgressCallback,
+    bytes_transferred: u64,
+    total_bytes: Option<u64>,
+    started: Option<Instant>,
+}
+
+impl http_body_0_4::Body for ProgressBody {
+    type Data = Bytes;
+    type Error = Error;
+
+    // Throughput is reported in terms of wall-clock time, so there's no time source to inject
+    #[allow(clippy::disallowed_methods)]
+    fn poll_data(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Self::Data, Self::Error>>> {
+        let started = *self.started.get_or_insert_with(Instant::now);
+        let data = ready!(Pin::new(&mut self.inner).poll_data(cx));
+        if let Some(Ok(data)) = &data {
+            self.bytes_transferred += data.len() as u64;
+            (self.on_progress)(Progress {
+                bytes_transferred: self.bytes_transferred,
+                total_bytes: self.total_bytes,
+                elapsed: started.elapsed(),
+            });
+        }
+        Poll::Ready(data)
+    }
+
+    fn poll_trailers(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Result<Option<http::HeaderMap>, Self::Error>> {
+        Pin::new(&mut self.inner).poll_trailers(cx)
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.inner.is_end_stream()
+    }
+
+    fn size_hint(&self) -> http_body_0_4::SizeHint {
+        http_body_0_4::Body::size_hint(&self.inner)
+    }
+}
+
+/// A token bucket that limits the rate at which data is read from one or more bodies.
+///
+/// Cloning a `BandwidthLimiter` shares its bucket, so that the combined throughput of every
+/// [rate limited](ByteStream::rate_limit) body stays under the limit. Chunks larger than the
+/// bucket are let through after a proportionally longer wait.
+///
+/// ```no_run
+/// use aws_smithy_types::byte_stream::{BandwidthLimiter, ByteStream};
+///
+/// // Read at most 1 MiB per second, across both streams
+/// let limiter = BandwidthLimiter::new(1024 * 1024);
+/// let first = ByteStream::from_static(b"hello").rate_limit(&limiter);
+/// let second = ByteStream::from_static(b"world").rate_limit(&limiter);
+/// ```
+#[cfg(feature = "rt-tokio")]
+#[derive(Clone)]
+pub struct BandwidthLimiter {
+    bucket: Arc<std::sync::Mutex<Bucket>>,
+}
+
+#[cfg(feature = "rt-tokio")]
+struct Bucket {
+    bytes_per_second: f64,
+    capacity: f64,
+    // May be negative when chunks were let through on credit
+    tokens: f64,
+    last_refill: tokio::time::Instant,
+}
+
+#[cfg(feature = "rt-tokio")]
+impl BandwidthLimiter {
+    /// Creates a limiter that allows `bytes_per_second` bytes per second, with bursts of up to
+    /// one second's worth of data.
+    ///
+    /// # Panics
+    /// This panics if `bytes_per_second` is zero.
+    pub fn new(bytes_per_second: u64) -> Self {
+        Self::with_burst(bytes_per_second, bytes_per_second)
+    }
+
+    /// Creates a limiter that allows `bytes_per_second` bytes per second, with bursts of up to
+    /// `burst` bytes.
+    ///
+    /// # Panics
+    /// This panics if `bytes_per_second` is zero.
+    pub fn with_burst(bytes_per_second: u64, burst: u64) -> Self {
+        assert!(
+            bytes_per_second > 0,
+            "bytes_per_second must be greater than zero"
+        );
+        Self {
+            bucket: Arc::new(std::sync::Mutex::new(Bucket {
+                bytes_per_second: bytes_per_second as f64,
+                capacity: burst as f64,
+                tokens: burst as f64,
+                last_refill: tokio::time::Instant::now(),
+            })),
+        }
+    }
+
+    /// Takes `bytes` tokens from the bucket, returning how long to wait before using them.
+    fn acquire(&self, bytes: usize) -> Duration {
+        let mut bucket = self.bucket.lock().unwrap();
+        let now = tokio::time::Instant::now();
+        let refill = now.duration_since(bucket.last_refill).as_secs_f64() * bucket.bytes_per_second;
+        bucket.tokens = bucket.capacity.min(bucket.tokens + refill);
+        bucket.last_refill = now;
+        bucket.tokens -= bytes as f64;
+        match bucket.tokens {
+            tokens if tokens >= 0.0 => Duration::ZERO,
+            tokens => Duration::from_secs_f64(-tokens / bucket.bytes_per_second),
+        }
+    }
+}
+
+#[cfg(feature = "rt-tokio")]
+impl fmt::Debug for BandwidthLimiter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let bucket = self.bucket.lock().unwrap();
+        f.debug_struct("BandwidthLimiter")
+            .field("bytes_per_second", &bucket.bytes_per_second)
+            .field("burst", &bucket.capacity)
+            .finish()
+    }
+}
+
+#[cfg(feature = "rt-tokio")]
+struct RateLimitedBody {
+    inner: SdkBody,
+    limiter: BandwidthLimiter,
+    // A chunk that is held back until the limiter allows it through
+    delayed: Option<(Pin<Box<tokio::time::Sleep>>, Bytes)>,
+}
+
+#[cfg(feature = "rt-tokio")]
+impl http_body_0_4::Body for RateLimitedBody {
+    type Data = Bytes;
+    type Error = Error;
+
+    fn poll_data(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Self::Data, Self::Error>>> {
+        if let Some((sleep, _)) = &mut self.delayed {
+            ready!(sleep.as_mut().poll(cx));
+            let (_, data) = self.delayed.take().expect("checked above");
+            return Poll::Ready(Some(Ok(data)));
+        }
+        match ready!(Pin::new(&mut self.inner).poll_data(cx)) {
+            Some(Ok(data)) => match self.limiter.acquire(data.len()) {
+                Duration::ZERO => Poll::Ready(Some(Ok(data))),
+                delay => {
+                    self.delayed = Some((Box::pin(tokio::time::sleep(delay)), data));
+                    self.poll_data(cx)
+                }
+            },
+            other => Poll::Ready(other),
+        }
+    }
+
+    fn poll_trailers(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Result<Option<http::HeaderMap>, Self::Error>> {
+        Pin::new(&mut self.inner).poll_trailers(cx)
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.delayed.is_none() && self.inner.is_end_stream()
+    }
+
+    fn size_hint(&self) -> http_body_0_4::SizeHint {
+        let mut hint = http_body_0_4::Body::size_hint(&self.inner);
+        if let Some((_, data)) = &self.delayed {
+            let delayed = data.len() as u64;
+            hint.set_lower(hint.lower() + delayed);
+            if let Some(upper) = hint.upper() {
+                hint.set_upper(upper + delayed);
+            }
+        }
+        hint
+    }
+}
+
+/// A destination for a copy of the data read from a [`ByteStream::tee`].
+///
+/// This is implemented for closures that take a `&Bytes`, for sinks that don't need to know when
+/// the body ends.
+pub trait Tee: Send + Sync {
+    /// Called with each chunk of data, in order, as it is read from the body.
+    fn data(&mut self, data: &Bytes);
+
+    /// Called once after the last chunk, when the body was read to the end without errors.
+    fn end(&mut self) {}
+}
+
+impl<F> Tee for F
+where
+    F: FnMut(&Bytes) + Send + Sync,
+{
+    fn data(&mut self, data: &Bytes) {
+        self(data)
+    }
+}
+
+struct TeeBody {
+    inner: SdkBody,
+    tee: Box<dyn Tee>,
+}
+
+impl http_body_0_4::Body for TeeBody {
+    type Data = Bytes;
+    type Error = Error;
+
+    fn poll_data(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<Self::Data, Self::Error>>> {
+        let data = ready!(Pin::new(&mut self.inner).poll_data(cx));
+        match &data {
+            Some(Ok(data)) => self.tee.data(data),
+            Some(Err(_)) => {}
+            None => self.tee.end(),
+        }
+        Poll::Ready(data)
+    }
+
+    fn poll_trailers(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Result<Option<http::HeaderMap>, Self::Error>> {
+        Pin::new(&mut self.inner).poll_trailers(cx)
+    }
+
+    fn is_end_stream(&self) -> bool {
+        // Not forwarded, so that the sink sees the end of the body
+        false
+    }
+
+    fn size_hint(&self) -> http_body_0_4::SizeHint {
+        http_body_0_4::Body::size_hint(&self.inner)
+    }
+}
+
+impl SdkBody {
+    /// Calls `on_progress` each time a chunk of data is read from this body.
+    ///
+    /// If this body is retried, the progress of the new attempt starts from zero.
+    pub fn with_progress(self, on_progress: impl Fn(Progress) + Send + Sync + 'static) -> SdkBody {
+        let on_progress: ProgressCallback = Arc::new(on_progress);
+        self.map_preserve_contents(move |inner| {
+            SdkBody::from_body_0_4(ProgressBody {
+                total_bytes: inner.content_length(),
+                inner,
+                on_progress: on_progress.clone(),
+                bytes_transferred: 0,
+                started: None,
+            })
+        })
+    }
+
+    /// Limits the rate at which data is read from this body with the given `limiter`.
+    ///
+    /// _Note: This is only available with `rt-tokio` enabled, and must be read within a Tokio
+    /// runtime._
+    #[cfg(feature = "rt-tokio")]
+    pub fn rate_limit(self, limiter: &BandwidthLimiter) -> SdkBody {
+        let limiter = limiter.clone();
+        self.map_preserve_contents(move |inner| {
+            SdkBody::from_body_0_4(RateLimitedBody {
+                inner,
+                limiter: limiter.clone(),
+                delayed: None,
+            })
+        })
+    }
+
+    /// Copies each chunk of data read from this body to a [`Tee`].
+    ///
+    /// `new_tee` is called to create a sink for every attempt at reading the body, including
+    /// each retry of a retryable body, so that a sink never sees the data of an attempt twice.
+    pub fn tee<T>(self, new_tee: impl Fn() -> T + Send + Sync + 'static) -> SdkBody
+    where
+        T: Tee + 'static,
+    {
+        self.map_preserve_contents(move |inner| {
+            SdkBody::from_body_0_4(TeeBody {
+                inner,
+                tee: Box::new(new_tee()),
+            })
+        })
+    }
+}
+
+impl ByteStream {
+    /// Calls `on_progress` each time a chunk of data is read from this `ByteStream`.
+    ///
+    /// If the `ByteStream` is retried, the progress of the new attempt starts from zero.
+    ///
+    /// ```no_run
+    /// use aws_smithy_types::byte_stream::ByteStream;
+    ///
+    /// let stream = ByteStream::from_static(b"hello world").with_progress(|progress| {
+    ///     println!(
+    ///         "read {} of {:?} bytes ({:.0} bytes/s)",
+    ///         progress.bytes_transferred(),
+    ///         progress.total_bytes(),
+    ///         progress.bytes_per_second()
+    ///     );
+    /// });
+    /// ```
+    pub fn with_progress(self, on_progress: impl Fn(Progress) + Send + Sync + 'static) -> Self {
+        ByteStream::new(self.into_inner().with_progress(on_progress))
+    }
+
+    /// Limits the rate at which data is read from this `ByteStream` with the given `limiter`.
+    ///
+    /// See [`BandwidthLimiter`] for an example.
+    ///
+    /// _Note: This is only available with `rt-tokio` enabled, and must be read within a Tokio
+    /// runtime._
+    #[cfg(feature = "rt-tokio")]
+    pub fn rate_limit(self, limiter: &BandwidthLimiter) -> Self {
+        ByteStream::new(self.into_inner().rate_limit(limiter))
+    }
+
+    /// Copies each chunk of data read from this `ByteStream` to a [`Tee`], such as a hasher or a
+    /// channel to a task that writes a local copy.
+    ///
+    /// `new_tee` is called to create a sink for every attempt at reading the stream, including
+    /// each retry of a retryable stream, so that a sink never sees the data of an attempt twice.
+    ///
+    /// ```no_run
+    /// use aws_smithy_types::byte_stream::ByteStream;
+    /// use std::sync::mpsc;
+    ///
+    /// let (tx, rx) = mpsc::channel();
+    /// let stream = ByteStream::from_static(b"hello world").tee(move || {
+    ///     let tx = tx.clone();
+    ///     move |data: &bytes::Bytes| {
+    ///         let _ = tx.send(data.clone());
+    ///     }
+    /// });
+    /// ```
+    pub fn tee<T>(self, new_tee: impl Fn() -> T + Send + Sync + 'static) -> Self
+    where
+        T: Tee + 'static,
+    {
+        ByteStream::new(self.into_inner().tee(new_tee))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Progress, Tee};
+    use crate::body::SdkBody;
+    use crate::byte_stream::ByteStream;
+    use bytes::Bytes;
+    use std::pin::Pin;
+    use std::sync::{Arc, Mutex};
+    use std::task::{Context, Poll};
+
+    /// A streaming body of unknown length that returns each of `chunks` in turn.
+    struct Chunks(std::collections::VecDeque<Bytes>);
+
+    impl http_body_0_4::Body for Chunks {
+        type Data = Bytes;
+        type Error = crate::body::Error;
+
+        fn poll_data(
+            mut self: Pin<&mut Self>,
+            _cx: &mut Context<'_>,
+        ) -> Poll<Option<Result<Self::Data, Self::Error>>> {
+            Poll::Ready(self.0.pop_front().map(Ok))
+        }
+
+        fn poll_trailers(
+            self: Pin<&mut Self>,
+            _cx: &mut Context<'_>,
+        ) -> Poll<Result<Option<http::HeaderMap>, Self::Error>> {
+            Poll::Ready(Ok(None))
+        }
+    }
+
+    fn chunked_body(chunks: &'static [&'static str]) -> SdkBody {
+        SdkBody::retryable(move || {
+            let chunks = chunks
+                .iter()
+                .map(|chunk| Bytes::from_static(chunk.as_bytes()));
+            SdkBody::from_body_0_4(Chunks(chunks.collect()))
+        })
+    }
+
+    #[tokio::test]
+    async fn progress() {
+        let reports = Arc::new(Mutex::new(Vec::<Progress>::new()));
+        let body = SdkBody::from("hello world").with_progress({
+            let reports = reports.clone();
+            move |progress| reports.lock().unwrap().push(progress)
+        });
+        assert_eq!(Some(b"hello world".as_slice()), body.bytes());
+        assert_eq!(Some(11), body.content_length());
+
+        let retry = body.try_clone().expect("retryable");
+        for body in [body, retry] {
+            let data = ByteStream::new(body).collect().await.unwrap().into_bytes();
+            assert_eq!(data, "hello world");
+        }
+        let reports = reports.lock().unwrap();
+        assert_eq!(2, reports.len());
+        for report in reports.iter() {
+            assert_eq!(11, report.bytes_transferred());
+            assert_eq!(Some(11), report.total_bytes());
+        }
+    }
+
+    #[tokio::test]
+    async fn progress_of_streaming_body() {
+        let reports = Arc::new(Mutex::new(Vec::new()));
+        let stream = ByteStream::new(chunked_body(&["ab", "cde", "f"])).with_progress({
+            let reports = reports.clone();
+            move |progress| {
+                reports
+                    .lock()
+                    .unwrap()
+                    .push((progress.bytes_transferred(), progress.total_bytes()))
+            }
+        });
+        assert_eq!(b"abcdef", &stream.collect().await.unwrap().to_vec()[..]);
+        assert_eq!(
+            vec![(2, None), (5, None), (6, None)],
+            *reports.lock().unwrap()
+        );
+    }
+
+    #[tokio::test]
+    async fn tee() {
+        #[derive(Default)]
+        struct Collect {
+            data: Vec<u8>,
+            ended: bool,
+        }
+        struct Sink(Arc<Mutex<Vec<Collect>>>, usize);
+        impl Tee for Sink {
+            fn data(&mut self, data: &Bytes) {
+                self.0.lock().unwrap()[self.1].data.extend_from_slice(data);
+            }
+            fn end(&mut self) {
+                self.0.lock().unwrap()[self.1].ended = true;
+            }
+        }
+
+        let sinks = Arc::new(Mutex::new(Vec::new()));
+        let stream = ByteStream::new(chunked_body(&["ab", "cde", "f"])).tee({
+            let sinks = sinks.clone();
+            move || {
+                let mut all = sinks.lock().unwrap();
+                all.push(Collect::default());
+                Sink(sinks.clone(), all.len() - 1)
+            }
+        });
+        let body = stream.into_inner();
+        let mut retry = body.try_clone().expect("the source is retryable");
+
+        // Read the retry partially and the original fully
+        assert_eq!("ab", retry.next().await.unwrap().unwrap());
+        let data = ByteStream::new(body).collect().await.unwrap().into_bytes();
+        assert_eq!("abcdef", data);
+
+        // Each attempt has its own sink
+        let read: Vec<_> = sinks
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|sink| (String::from_utf8(sink.data.clone()).unwrap(), sink.ended))
+            .collect();
+        assert_eq!(
+            vec![("abcdef".to_owned(), true), ("ab".to_owned(), false)],
+            read
+        );
+    }
+
+    #[tokio::test]
+    async fn tee_closure() {
+        let copy = Arc::new(Mutex::new(Vec::new()));
+        let stream = ByteStream::from_static(b"hello").tee({
+            let copy = copy.clone();
+            move || {
+                let copy = copy.clone();
+                move |data: &Bytes| copy.lock().unwrap().extend_from_slice(data)
+            }
+        });
+        assert_eq!("hello", stream.collect().await.unwrap().into_bytes());
+        assert_eq!(b"hello", &copy.lock().unwrap()[..]);
+    }
+
+    #[cfg(feature = "rt-tokio")]
+    #[tokio::test(start_paused = true)]
+    async fn rate_limit() {
+        use super::BandwidthLimiter;
+        use std::time::Duration;
+        use tokio::time::Instant;
+
+        let limiter = BandwidthLimiter::with_burst(10, 10);
+        let first =
+            ByteStream::new(chunked_body(&["0123456789", "0123456789"])).rate_limit(&limiter);
+        let second = ByteStream::from_static(b"0123456789").rate_limit(&limiter);
+
+        let start = Instant::now();
+        let (first, second) = tokio::join!(first.collect(), second.collect());
+        assert_eq!(20, first.unwrap().into_bytes().len());
+        assert_eq!(10, second.unwrap().into_bytes().len());
+        // 30 bytes at 10 bytes per second, with the first 10 bytes allowed through immediately
+        let elapsed = start.elapsed();
+        assert!(elapsed >= Duration::from_secs(2), "{elapsed:?}");
+        assert!(elapsed < Duration::from_millis(2100), "{elapsed:?}");
+    }
+
+    #[cfg(feature = "rt-tokio")]
+    #[tokio::test(start_paused = true)]
+    async fn rate_limit_is_retryable() {
+        use super::BandwidthLimiter;
+
+        let limiter = BandwidthLimiter::new(1024);
+        let body = SdkBody::from("hello").rate_limit(&limiter);
+        assert_eq!(Some(5), body.content_length());
+        let retry = body.try_clone().expect("retryable");
+        for body in [body, retry] {
+            assert_eq!(
+                "hello",
+                ByteStream::new(body).collect().await.unwrap().into_bytes()
+            );
+        }
+    }
+}
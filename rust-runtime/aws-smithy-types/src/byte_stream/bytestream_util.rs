@@ -5,7 +5,10 @@
 
 use crate::body::SdkBody;
 use crate::byte_stream::{error::Error, error::ErrorKind, ByteStream};
+#[cfg(feature = "byte-stream-mmap")]
+use bytes::Bytes;
 use std::future::Future;
+use std::ops::Range;
 use std::path::PathBuf;
 use std::pin::Pin;
 use tokio::fs::File;
@@ -53,6 +56,13 @@ impl PathBody {
     }
 }
 
+/// A retryable body that hands out zero-copy slices of a memory-mapped file.
+#[cfg(feature = "byte-stream-mmap")]
+struct MappedBody {
+    data: Bytes,
+    buffer_size: usize,
+}
+
 /// Builder for creating [`ByteStreams`](ByteStream) from a file/path, with full control over advanced options.
 ///
 /// ```no_run
@@ -78,6 +88,28 @@ impl PathBody {
 /// }
 /// # }
 /// ```
+///
+/// To upload a large file in parts, use [`build_ranges`](FsBuilder::build_ranges) to get one
+/// `ByteStream` per part:
+///
+/// ```no_run
+/// # #[cfg(feature = "rt-tokio")]
+/// # {
+/// use aws_smithy_types::byte_stream::ByteStream;
+///
+/// async fn parts_of_file(file_size: u64) -> Vec<ByteStream> {
+///     const PART_SIZE: u64 = 8 * 1024 * 1024;
+///     let ranges = (0..file_size)
+///         .step_by(PART_SIZE as usize)
+///         .map(|start| start..file_size.min(start + PART_SIZE));
+///     ByteStream::read_from()
+///         .path("docs/some-large-file.csv")
+///         .build_ranges(ranges)
+///         .await
+///         .expect("valid path and ranges")
+/// }
+/// # }
+/// ```
 #[allow(missing_debug_implementations)]
 pub struct FsBuilder {
     file: Option<File>,
@@ -85,6 +117,8 @@ pub struct FsBuilder {
     length: Option<Length>,
     buffer_size: usize,
     offset: Option<u64>,
+    #[cfg(feature = "byte-stream-mmap")]
+    memory_map: bool,
 }
 
 impl Default for FsBuilder {
@@ -114,6 +148,8 @@ impl FsBuilder {
             length: None,
             offset: None,
             path: None,
+            #[cfg(feature = "byte-stream-mmap")]
+            memory_map: false,
         }
     }
 
@@ -163,6 +199,23 @@ impl FsBuilder {
         self
     }
 
+    /// Read the file through a memory map instead of a read buffer.
+    ///
+    /// The resulting ByteStream (after calling [build](FsBuilder::build)) hands out zero-copy
+    /// slices of the mapped file, each at most [`buffer_size`](FsBuilder::buffer_size) bytes
+    /// long. It is retryable even when built from a [`file`](FsBuilder::file), since a retry
+    /// only rewinds to the start of the mapping.
+    ///
+    /// # Safety
+    /// The file MUST NOT be modified or truncated, by this process or any other, until the
+    /// resulting ByteStreams and all of the `Bytes` read from them have been dropped. Reading
+    /// from a mapping while the file changes is undefined behavior.
+    #[cfg(feature = "byte-stream-mmap")]
+    pub unsafe fn memory_map(mut self) -> Self {
+        self.memory_map = true;
+        self
+    }
+
     /// Returns a [`ByteStream`] from this builder.
     pub async fn build(self) -> Result<ByteStream, Error> {
         if self.path.is_some() && self.file.is_some() {
@@ -189,19 +242,21 @@ impl FsBuilder {
             None => file_length - offset,
         };
 
-        if let Some(path) = self.path {
-            let body_loader = move || {
-                // If an offset was provided, seeking will be handled in `PathBody::poll_data` each
-                // time the file is loaded.
-                SdkBody::from_body_0_4_internal(PathBody::from_path(
-                    path.clone(),
-                    length,
-                    buffer_size,
-                    self.offset,
-                ))
-            };
+        #[cfg(feature = "byte-stream-mmap")]
+        if self.memory_map {
+            // A mapping can't extend past the end of the file
+            let length = length.min(file_length - offset);
+            let data = self.map(offset..offset + length).await?;
+            return Ok(MappedBody::byte_stream(data, buffer_size));
+        }
 
-            Ok(ByteStream::new(SdkBody::retryable(body_loader)))
+        if let Some(path) = self.path {
+            Ok(PathBody::byte_stream(
+                path,
+                length,
+                buffer_size,
+                self.offset,
+            ))
         } else if let Some(mut file) = self.file {
             // When starting from a `File`, we need to do our own seeking
             if offset != 0 {
@@ -217,6 +272,100 @@ impl FsBuilder {
         }
     }
 
+    /// Returns one [`ByteStream`] for each of the given byte `ranges` of the file.
+    ///
+    /// Ranges are byte offsets from the start of the file. They must not overlap, and they must
+    /// end at or before the end of the file. Each ByteStream reads only its own range, provides
+    /// an exact size hint, and is retried independently of the others, which makes them suitable
+    /// for the parts of a multipart upload.
+    ///
+    /// An error is returned when reading from a [`file`](FsBuilder::file) that isn't
+    /// memory-mapped, since a single file handle can't be read at several positions at once. Use
+    /// [`path`](FsBuilder::path) instead.
+    ///
+    /// # Panics
+    /// This panics if [`offset`](FsBuilder::offset) or [`length`](FsBuilder::length) were set,
+    /// since each range specifies its own.
+    pub async fn build_ranges(
+        self,
+        ranges: impl IntoIterator<Item = Range<u64>>,
+    ) -> Result<Vec<ByteStream>, Error> {
+        if self.path.is_some() && self.file.is_some() {
+            panic!("The 'file' and 'path' options on an FsBuilder are mutually exclusive but both were set. Please set only one")
+        };
+        if self.offset.is_some() || self.length.is_some() {
+            panic!("The 'offset' and 'length' options on an FsBuilder can't be used with `build_ranges`. Each range specifies its own offset and length")
+        }
+
+        let file_length = self.get_file_size().await?;
+        let ranges: Vec<_> = ranges.into_iter().collect();
+        validate_ranges(&ranges, file_length)?;
+
+        #[cfg(feature = "byte-stream-mmap")]
+        if self.memory_map {
+            // Map the file once, from the start of the first range to the end of the last one
+            let non_empty = || ranges.iter().filter(|range| !range.is_empty());
+            let start = non_empty().map(|range| range.start).min().unwrap_or(0);
+            let end = non_empty().map(|range| range.end).max().unwrap_or(0);
+            let buffer_size = self.buffer_size;
+            let data = self.map(start..end).await?;
+            return Ok(ranges
+                .into_iter()
+                .map(|range| match range.is_empty() {
+                    true => Bytes::new(),
+                    false => {
+                        data.slice((range.start - start) as usize..(range.end - start) as usize)
+                    }
+                })
+                .map(|data| MappedBody::byte_stream(data, buffer_size))
+                .collect());
+        }
+
+        match self.path {
+            Some(path) => Ok(ranges
+                .into_iter()
+                .map(|range| {
+                    PathBody::byte_stream(
+                        path.clone(),
+                        range.end - range.start,
+                        self.buffer_size,
+                        Some(range.start),
+                    )
+                })
+                .collect()),
+            None if self.file.is_some() => Err(ErrorKind::RangesOfUnmappedFile.into()),
+            None => panic!("FsBuilder constructed without a file or a path"),
+        }
+    }
+
+    /// Maps the given `range` of the file into memory.
+    #[cfg(feature = "byte-stream-mmap")]
+    async fn map(self, range: Range<u64>) -> Result<Bytes, Error> {
+        if range.is_empty() {
+            // Empty mappings aren't supported on every platform
+            return Ok(Bytes::new());
+        }
+        let file = match (self.path, self.file) {
+            (Some(path), _) => File::open(path).await?.into_std().await,
+            (None, Some(file)) => file.into_std().await,
+            (None, None) => panic!("FsBuilder constructed without a file or a path"),
+        };
+        let length = usize::try_from(range.end - range.start).map_err(|_| {
+            io::Error::new(
+                io::ErrorKind::InvalidInput,
+                "range is too large to be memory-mapped",
+            )
+        })?;
+        // SAFETY: the caller of `FsBuilder::memory_map` promised that the file won't be modified
+        let mmap = unsafe {
+            memmap2::MmapOptions::new()
+                .offset(range.start)
+                .len(length)
+                .map(&file)?
+        };
+        Ok(Bytes::from_owner(mmap))
+    }
+
     async fn get_file_size(&self) -> Result<u64, Error> {
         Ok(match self.path.as_ref() {
             Some(path) => tokio::fs::metadata(path).await,
@@ -227,6 +376,95 @@ impl FsBuilder {
     }
 }
 
+fn validate_ranges(ranges: &[Range<u64>], file_length: u64) -> Result<(), Error> {
+    let mut sorted = Vec::with_capacity(ranges.len());
+    for range in ranges {
+        if range.start > range.end {
+            return Err(ErrorKind::RangeStartGreaterThanEnd.into());
+        }
+        if range.end > file_length {
+            return Err(ErrorKind::RangeEndLargerThanFileSize.into());
+        }
+        if !range.is_empty() {
+            sorted.push(range);
+        }
+    }
+    sorted.sort_unstable_by_key(|range| range.start);
+    if sorted.windows(2).any(|pair| pair[1].start < pair[0].end) {
+        return Err(ErrorKind::OverlappingRanges.into());
+    }
+    Ok(())
+}
+
+impl PathBody {
+    /// Returns a retryable [`ByteStream`] that reads `length` bytes of the file at `path`.
+    fn byte_stream(
+        path: PathBuf,
+        length: u64,
+        buffer_size: usize,
+        offset: Option<u64>,
+    ) -> ByteStream {
+        let body_loader = move || {
+            // If an offset was provided, seeking will be handled in `PathBody::poll_data` each
+            // time the file is loaded.
+            SdkBody::from_body_0_4_internal(PathBody::from_path(
+                path.clone(),
+                length,
+                buffer_size,
+                offset,
+            ))
+        };
+        ByteStream::new(SdkBody::retryable(body_loader))
+    }
+}
+
+#[cfg(feature = "byte-stream-mmap")]
+impl MappedBody {
+    /// Returns a retryable [`ByteStream`] that reads `data` in slices of `buffer_size` bytes.
+    fn byte_stream(data: Bytes, buffer_size: usize) -> ByteStream {
+        // A zero buffer size would never make progress
+        let buffer_size = buffer_size.max(1);
+        ByteStream::new(SdkBody::retryable(move || {
+            SdkBody::from_body_0_4_internal(MappedBody {
+                data: data.clone(),
+                buffer_size,
+            })
+        }))
+    }
+}
+
+#[cfg(feature = "byte-stream-mmap")]
+impl http_body_0_4::Body for MappedBody {
+    type Data = Bytes;
+    type Error = std::convert::Infallible;
+
+    fn poll_data(
+        mut self: Pin<&mut Self>,
+        _cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Option<Result<Self::Data, Self::Error>>> {
+        let length = self.data.len().min(self.buffer_size);
+        std::task::Poll::Ready(match length {
+            0 => None,
+            _ => Some(Ok(self.data.split_to(length))),
+        })
+    }
+
+    fn poll_trailers(
+        self: Pin<&mut Self>,
+        _cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Result<Option<http::HeaderMap>, Self::Error>> {
+        std::task::Poll::Ready(Ok(None))
+    }
+
+    fn is_end_stream(&self) -> bool {
+        self.data.is_empty()
+    }
+
+    fn size_hint(&self) -> http_body_0_4::SizeHint {
+        http_body_0_4::SizeHint::with_exact(self.data.len() as u64)
+    }
+}
+
 enum State {
     Unloaded(PathBuf),
     Loading(Pin<Box<dyn Future<Output = io::Result<File>> + Send + Sync + 'static>>),
@@ -600,4 +838,178 @@ mod test {
 
         assert_eq!(data_str, in_memory_copy_of_file_contents);
     }
+
+    fn file_with_lines(lines: usize) -> (NamedTempFile, String) {
+        let mut file = NamedTempFile::new().unwrap();
+        let mut contents = String::new();
+        for i in 0..lines {
+            contents.push_str(&format!("Line {:04}\n", i));
+        }
+        file.write_all(contents.as_bytes()).unwrap();
+        file.flush().expect("flushing is OK");
+        (file, contents)
+    }
+
+    #[tokio::test]
+    async fn fsbuilder_build_ranges() {
+        let (file, contents) = file_with_lines(100);
+        let ranges = [0..10, 10..500, 700..1000, 500..500];
+
+        let byte_streams = FsBuilder::new()
+            .path(&file)
+            .buffer_size(64)
+            .build_ranges(ranges.clone())
+            .await
+            .unwrap();
+        assert_eq!(byte_streams.len(), ranges.len());
+
+        for (byte_stream, range) in byte_streams.into_iter().zip(ranges) {
+            let expected = &contents[range.start as usize..range.end as usize];
+            let body = byte_stream.into_inner();
+            assert_eq!(body.content_length(), Some(expected.len() as u64));
+
+            // Each range can be retried independently of the others
+            let retry = body.try_clone().expect("retryable bodies are cloneable");
+            for body in [body, retry] {
+                let data = ByteStream::new(body).collect().await.unwrap().into_bytes();
+                assert_eq!(data, expected.as_bytes());
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn fsbuilder_build_ranges_validates_ranges() {
+        let (file, _contents) = file_with_lines(10);
+        let build_ranges = |ranges: Vec<std::ops::Range<u64>>| async {
+            FsBuilder::new()
+                .path(&file)
+                .build_ranges(ranges)
+                .await
+                .map(|_| ())
+                .map_err(|err| err.to_string())
+        };
+
+        assert_eq!(Ok(()), build_ranges(vec![]).await);
+        assert_eq!(Ok(()), build_ranges(vec![0..50, 50..100, 20..20]).await);
+        assert_eq!(
+            Err("range end must be less than or equal to file size but was greater than".into()),
+            build_ranges(vec![0..50, 50..101]).await
+        );
+        assert_eq!(
+            Err("ranges must not overlap".into()),
+            build_ranges(vec![50..100, 0..51]).await
+        );
+        #[allow(clippy::reversed_empty_ranges)]
+        let reversed = 20..10;
+        assert_eq!(
+            Err("range start must be less than or equal to range end".into()),
+            build_ranges(vec![reversed]).await
+        );
+    }
+
+    #[tokio::test]
+    async fn fsbuilder_build_ranges_requires_path() {
+        let (file, _contents) = file_with_lines(10);
+        let file = tokio::fs::File::open(file.path()).await.unwrap();
+        let err = FsBuilder::new()
+            .file(file)
+            .build_ranges(std::iter::once(0..10))
+            .await
+            .unwrap_err();
+        assert_eq!(
+            "ranges can only be built from a file that is memory-mapped, or from a path",
+            err.to_string()
+        );
+    }
+
+    #[cfg(feature = "byte-stream-mmap")]
+    #[tokio::test]
+    async fn fsbuilder_memory_map() {
+        let (file, contents) = file_with_lines(1000);
+
+        // SAFETY: the file isn't modified while it is mapped
+        let body = unsafe { FsBuilder::new().path(&file).memory_map() }
+            .buffer_size(1000)
+            .offset(5)
+            .length(Length::UpTo(1_000_000))
+            .build()
+            .await
+            .unwrap()
+            .into_inner();
+        let expected = &contents.as_bytes()[5..];
+        assert_eq!(body.content_length(), Some(expected.len() as u64));
+
+        let mut body1 = body.try_clone().expect("retryable bodies are cloneable");
+        let chunk = body1.next().await.unwrap().unwrap();
+        assert_eq!(chunk, expected[..1000]);
+        assert_eq!(
+            ByteStream::new(body1).collect().await.unwrap().remaining(),
+            expected.len() - 1000
+        );
+        assert_eq!(
+            ByteStream::new(body).collect().await.unwrap().into_bytes(),
+            expected
+        );
+    }
+
+    #[cfg(feature = "byte-stream-mmap")]
+    #[tokio::test]
+    async fn fsbuilder_memory_map_file_is_retryable() {
+        let (file, contents) = file_with_lines(10);
+        let file = tokio::fs::File::open(file.path()).await.unwrap();
+
+        // SAFETY: the file isn't modified while it is mapped
+        let body = unsafe { FsBuilder::new().file(file).memory_map() }
+            .build()
+            .await
+            .unwrap()
+            .into_inner();
+        let retry = body
+            .try_clone()
+            .expect("memory-mapped bodies are cloneable");
+        for body in [body, retry] {
+            let data = ByteStream::new(body).collect().await.unwrap().into_bytes();
+            assert_eq!(data, contents.as_bytes());
+        }
+    }
+
+    #[cfg(feature = "byte-stream-mmap")]
+    #[tokio::test]
+    async fn fsbuilder_memory_map_empty() {
+        let file = NamedTempFile::new().unwrap();
+
+        // SAFETY: the file isn't modified while it is mapped
+        let byte_stream = unsafe { FsBuilder::new().path(&file).memory_map() }
+            .build()
+            .await
+            .unwrap();
+        assert_eq!(byte_stream.size_hint(), (0, Some(0)));
+        assert!(byte_stream.collect().await.unwrap().into_bytes().is_empty());
+    }
+
+    #[cfg(feature = "byte-stream-mmap")]
+    #[tokio::test]
+    async fn fsbuilder_memory_map_build_ranges() {
+        let (file, contents) = file_with_lines(100);
+        let ranges = [100..300, 0..100, 300..300, 800..1000];
+        let file = tokio::fs::File::open(file.path()).await.unwrap();
+
+        // SAFETY: the file isn't modified while it is mapped
+        let byte_streams = unsafe { FsBuilder::new().file(file).memory_map() }
+            .buffer_size(30)
+            .build_ranges(ranges.clone())
+            .await
+            .unwrap();
+        for (byte_stream, range) in byte_streams.into_iter().zip(ranges) {
+            let expected = &contents[range.start as usize..range.end as usize];
+            let body = byte_stream.into_inner();
+            assert_eq!(body.content_length(), Some(expected.len() as u64));
+            let retry = body.try_clone().expect("retryable bodies are cloneable");
+            for body in [body, retry] {
+                let data = ByteStream::new(body).collect().await.unwrap();
+                assert!(data.clone().into_segments().all(|chunk| chunk.len() <= 30));
+                assert_eq!(data.into_bytes(), expected.as_bytes());
+            }
+        }
+    }
 }
@@ -15,6 +15,14 @@ pub(super) enum ErrorKind {
     OffsetLargerThanFileSize,
     #[cfg(feature = "rt-tokio")]
     LengthLargerThanFileSizeMinusReadOffset,
+    #[cfg(feature = "rt-tokio")]
+    RangeStartGreaterThanEnd,
+    #[cfg(feature = "rt-tokio")]
+    RangeEndLargerThanFileSize,
+    #[cfg(feature = "rt-tokio")]
+    OverlappingRanges,
+    #[cfg(feature = "rt-tokio")]
+    RangesOfUnmappedFile,
     IoError(IoError),
     StreamingError(Box<dyn StdError + Send + Sync + 'static>),
 }
@@ -56,6 +64,22 @@ impl fmt::Display for Error {
                 f,
                 "`Length::Exact` was larger than file size minus read offset"
             ),
+            #[cfg(feature = "rt-tokio")]
+            ErrorKind::RangeStartGreaterThanEnd => {
+                write!(f, "range start must be less than or equal to range end")
+            }
+            #[cfg(feature = "rt-tokio")]
+            ErrorKind::RangeEndLargerThanFileSize => write!(
+                f,
+                "range end must be less than or equal to file size but was greater than"
+            ),
+            #[cfg(feature = "rt-tokio")]
+            ErrorKind::OverlappingRanges => write!(f, "ranges must not overlap"),
+            #[cfg(feature = "rt-tokio")]
+            ErrorKind::RangesOfUnmappedFile => write!(
+                f,
+                "ranges can only be built from a file that is memory-mapped, or from a path"
+            ),
             ErrorKind::IoError(_) => write!(f, "IO error"),
             ErrorKind::StreamingError(_) => write!(f, "streaming error"),
         }
@@ -69,7 +93,11 @@ impl StdError for Error {
             ErrorKind::StreamingError(err) => Some(err.as_ref() as _),
             #[cfg(feature = "rt-tokio")]
             ErrorKind::OffsetLargerThanFileSize
-            | ErrorKind::LengthLargerThanFileSizeMinusReadOffset => None,
+            | ErrorKind::LengthLargerThanFileSizeMinusReadOffset
+            | ErrorKind::RangeStartGreaterThanEnd
+            | ErrorKind::RangeEndLargerThanFileSize
+            | ErrorKind::OverlappingRanges
+            | ErrorKind::RangesOfUnmappedFile => None,
         }
     }
 }
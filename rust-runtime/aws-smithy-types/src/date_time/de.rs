@@ -44,7 +44,7 @@ impl<'de> Visitor<'de> for NonHumanReadableDateTimeVisitor {
                     seconds,
                     subsecond_nanos,
                 }),
-                _ => return Err(Error::custom("datatype mismatch")),
+                _ => Err(Error::custom("datatype mismatch")),
             },
             _ => Err(Error::custom("Size mismatch")),
         }
@@ -64,7 +64,7 @@ impl<'de> Deserialize<'de> for DateTime {
     }
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "serde-serialize"))]
 mod test {
     use super::*;
 
@@ -79,7 +79,7 @@ mod test {
             datetime: DateTime,
         }
         let datetime_json = r#"{"datetime":"2019-12-16T23:48:18Z"}"#;
-        let test = serde_json::from_str::<Test>(&datetime_json).ok();
+        let test = serde_json::from_str::<Test>(datetime_json).ok();
         assert!(test == Some(Test { datetime }));
     }
 
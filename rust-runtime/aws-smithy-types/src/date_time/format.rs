@@ -79,8 +79,19 @@ fn remove_trailing_zeros(string: &mut String) {
     }
 }
 
+/// Pushes the first `precision` digits of the sub-second fraction `nanos`, including the `.`.
+/// Nothing is pushed when `precision` is 0.
+fn push_fraction(into: &mut String, nanos: u32, precision: u8) {
+    debug_assert!(nanos < NANOS_PER_SECOND);
+    let precision = usize::from(precision.min(9));
+    if precision > 0 {
+        into.push('.');
+        into.push_str(&format!("{:0>9}", nanos)[..precision]);
+    }
+}
+
 pub(crate) mod epoch_seconds {
-    use super::remove_trailing_zeros;
+    use super::{push_fraction, remove_trailing_zeros};
     use super::{DateTimeParseError, DateTimeParseErrorKind};
     use crate::DateTime;
     use std::str::FromStr;
@@ -96,6 +107,14 @@ pub(crate) mod epoch_seconds {
         }
     }
 
+    /// Formats a `DateTime` into the epoch seconds format with exactly `precision` fractional
+    /// digits.
+    pub(crate) fn format_with_precision(date_time: &DateTime, precision: u8) -> String {
+        let mut result = date_time.seconds.to_string();
+        push_fraction(&mut result, date_time.subsecond_nanos, precision);
+        result
+    }
+
     /// Parses the Smithy epoch seconds date-time format into a `DateTime`.
     pub(crate) fn parse(value: &str) -> Result<DateTime, DateTimeParseError> {
         let mut parts = value.splitn(2, '.');
@@ -128,6 +147,139 @@ pub(crate) mod epoch_seconds {
     }
 }
 
+pub(crate) mod epoch_millis {
+    use super::{DateTimeFormatError, DateTimeFormatErrorKind};
+    use super::{DateTimeParseError, DateTimeParseErrorKind};
+    use crate::DateTime;
+
+    /// Formats a `DateTime` as an integer number of milliseconds since the Unix epoch.
+    ///
+    /// Sub-millisecond precision is truncated towards the past.
+    pub(crate) fn format(date_time: &DateTime) -> Result<String, DateTimeFormatError> {
+        date_time
+            .to_millis()
+            .map(|millis| millis.to_string())
+            .map_err(|_| {
+                DateTimeFormatErrorKind::OutOfRange(
+                    "epoch milliseconds must fit into an i64".into(),
+                )
+                .into()
+            })
+    }
+
+    /// Parses an integer number of milliseconds since the Unix epoch into a `DateTime`.
+    pub(crate) fn parse(value: &str) -> Result<DateTime, DateTimeParseError> {
+        if value.starts_with('+') {
+            return Err(
+                DateTimeParseErrorKind::Invalid("invalid epoch-millis timestamp".into()).into(),
+            );
+        }
+        let millis = value
+            .parse::<i64>()
+            .map_err(|_| DateTimeParseErrorKind::IntParseError)?;
+        Ok(DateTime::from_millis(millis))
+    }
+}
+
+pub(crate) mod iso8601_basic {
+    use super::{DateTimeFormatError, DateTimeFormatErrorKind};
+    use super::{DateTimeParseError, DateTimeParseErrorKind};
+    use crate::DateTime;
+    use time::{Date, Month, OffsetDateTime, PrimitiveDateTime, Time};
+
+    /// Length of `YYYYMMDDTHHMMSSZ`
+    const LENGTH: usize = 16;
+
+    /// Formats a `DateTime` in the ISO-8601 basic format, e.g. `20150830T123600Z`.
+    ///
+    /// Sub-second nanos are not emitted.
+    pub(crate) fn format(date_time: &DateTime) -> Result<String, DateTimeFormatError> {
+        let structured = OffsetDateTime::from_unix_timestamp(date_time.seconds)
+            .ok()
+            .filter(|structured| (0..=9_999).contains(&structured.year()))
+            .ok_or_else(|| {
+                DateTimeFormatErrorKind::OutOfRange(
+                    "ISO-8601 basic date-times support years between 0000 and 9999".into(),
+                )
+            })?;
+        Ok(format!(
+            "{:04}{:02}{:02}T{:02}{:02}{:02}Z",
+            structured.year(),
+            u8::from(structured.month()),
+            structured.day(),
+            structured.hour(),
+            structured.minute(),
+            structured.second()
+        ))
+    }
+
+    /// Parses an ISO-8601 basic format date-time in UTC, e.g. `20150830T123600Z`.
+    pub(crate) fn parse(s: &str) -> Result<DateTime, DateTimeParseError> {
+        let bytes = s.as_bytes();
+        if bytes.len() != LENGTH
+            || bytes[8] != b'T'
+            || bytes[15] != b'Z'
+            || !bytes[..8]
+                .iter()
+                .chain(&bytes[9..15])
+                .all(u8::is_ascii_digit)
+        {
+            return Err(DateTimeParseErrorKind::Invalid(
+                "ISO-8601 basic date-times must look like `20150830T123600Z`".into(),
+            )
+            .into());
+        }
+        // Every component is made of ASCII digits, so parsing them can't fail
+        let number = |range: std::ops::Range<usize>| s[range].parse::<u16>().unwrap();
+        let invalid = |err: time::error::ComponentRange| {
+            DateTimeParseErrorKind::Invalid(format!("component is out of range: {}", err).into())
+        };
+        let month = Month::try_from(number(4..6) as u8).map_err(invalid)?;
+        let date = Date::from_calendar_date(number(0..4) as i32, month, number(6..8) as u8)
+            .map_err(invalid)?;
+        let time = Time::from_hms(
+            number(9..11) as u8,
+            number(11..13) as u8,
+            number(13..15) as u8,
+        )
+        .map_err(invalid)?;
+        Ok(DateTime::from_secs(
+            PrimitiveDateTime::new(date, time)
+                .assume_utc()
+                .unix_timestamp(),
+        ))
+    }
+}
+
+pub(crate) mod rfc2822 {
+    use super::{http_date, DateTimeFormatError, DateTimeParseError, DateTimeParseErrorKind};
+    use crate::DateTime;
+    use time::format_description::well_known::Rfc2822;
+    use time::OffsetDateTime;
+
+    /// Formats a `DateTime` in the RFC-2822 format, e.g. `Mon, 16 Dec 2019 23:48:18 +0000`.
+    ///
+    /// Sub-second nanos are not emitted.
+    pub(crate) fn format(date_time: &DateTime) -> Result<String, DateTimeFormatError> {
+        // This is the HTTP date format, with a numeric offset instead of the obsolete `GMT` zone
+        let mut formatted = http_date::format(date_time)?;
+        formatted.truncate(formatted.len() - "GMT".len());
+        formatted.push_str("+0000");
+        Ok(formatted)
+    }
+
+    /// Parses an RFC-2822 date-time, e.g. `Mon, 16 Dec 2019 23:48:18 +0100`.
+    ///
+    /// The day of the week is optional, and any UTC offset is supported.
+    pub(crate) fn parse(s: &str) -> Result<DateTime, DateTimeParseError> {
+        let date_time = OffsetDateTime::parse(s, &Rfc2822).map_err(|err| {
+            DateTimeParseErrorKind::Invalid(format!("invalid RFC-2822 date-time: {}", err).into())
+        })?;
+        Ok(DateTime::from_nanos(date_time.unix_timestamp_nanos())
+            .expect("this date format cannot produce out of range date-times"))
+    }
+}
+
 pub(crate) mod http_date {
     use crate::date_time::format::{
         DateTimeFormatError, DateTimeFormatErrorKind, DateTimeParseError, DateTimeParseErrorKind,
@@ -426,6 +578,15 @@ pub(crate) mod rfc3339 {
 
     /// Format a [DateTime] in the RFC-3339 date format
     pub(crate) fn format(date_time: &DateTime) -> Result<String, DateTimeFormatError> {
+        format_with_precision(date_time, None)
+    }
+
+    /// Format a [DateTime] in the RFC-3339 date format, with exactly `precision` fractional
+    /// digits if given, or otherwise up to microsecond precision without trailing zeros.
+    pub(crate) fn format_with_precision(
+        date_time: &DateTime,
+        precision: Option<u8>,
+    ) -> Result<String, DateTimeFormatError> {
         use std::fmt::Write;
         fn out_of_range<E: std::fmt::Display>(cause: E) -> DateTimeFormatError {
             DateTimeFormatErrorKind::OutOfRange(
@@ -438,7 +599,7 @@ pub(crate) mod rfc3339 {
             )
             .into()
         }
-        let (year, month, day, hour, minute, second, micros) = {
+        let (year, month, day, hour, minute, second, nanos) = {
             let s = OffsetDateTime::from_unix_timestamp_nanos(date_time.as_nanos())
                 .map_err(out_of_range)?;
             (
@@ -448,7 +609,7 @@ pub(crate) mod rfc3339 {
                 s.hour(),
                 s.minute(),
                 s.second(),
-                s.microsecond(),
+                s.nanosecond(),
             )
         };
 
@@ -465,7 +626,10 @@ pub(crate) mod rfc3339 {
             year, month, day, hour, minute, second
         )
         .unwrap();
-        format_subsecond_fraction(&mut out, micros);
+        match precision {
+            Some(precision) => super::push_fraction(&mut out, nanos, precision),
+            None => format_subsecond_fraction(&mut out, nanos / 1_000),
+        }
         out.push('Z');
         Ok(out)
     }
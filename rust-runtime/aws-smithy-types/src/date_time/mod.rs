@@ -18,11 +18,13 @@ use std::time::Duration;
 use std::time::SystemTime;
 use std::time::UNIX_EPOCH;
 
-#[cfg(all(aws_sdk_unstable, feature = "serde-deserialize"))]
+#[cfg(feature = "serde-deserialize")]
 mod de;
 mod format;
-#[cfg(all(aws_sdk_unstable, feature = "serde-serialize"))]
+#[cfg(feature = "serde-serialize")]
 mod ser;
+#[cfg(any(feature = "serde-serialize", feature = "serde-deserialize"))]
+pub mod serde_format;
 
 pub use self::format::DateTimeFormatError;
 pub use self::format::DateTimeParseError;
@@ -168,6 +170,15 @@ impl DateTime {
         }
     }
 
+    /// Parses a `DateTime` from a string using the given [`ExtendedFormat`].
+    pub fn from_str_extended(s: &str, format: ExtendedFormat) -> Result<Self, DateTimeParseError> {
+        match format {
+            ExtendedFormat::EpochMillis => format::epoch_millis::parse(s),
+            ExtendedFormat::Iso8601Basic => format::iso8601_basic::parse(s),
+            ExtendedFormat::Rfc2822 => format::rfc2822::parse(s),
+        }
+    }
+
     /// Returns true if sub-second nanos is greater than zero.
     pub fn has_subsec_nanos(&self) -> bool {
         self.subsecond_nanos != 0
@@ -235,6 +246,32 @@ impl DateTime {
                 (Self::from_str(s, format)?, rest)
             }
         };
+        Self::skip_delim(inst, next, delim)
+    }
+
+    /// Read 1 date of the given [`ExtendedFormat`] from `s`, expecting either `delim` or EOF
+    ///
+    /// Enable parsing multiple dates from the same string
+    pub fn read_extended(
+        s: &str,
+        format: ExtendedFormat,
+        delim: char,
+    ) -> Result<(Self, &str), DateTimeParseError> {
+        // Skip the comma following the optional day of the week of RFC-2822 dates
+        let start = match format {
+            ExtendedFormat::Rfc2822 if s.starts_with(|c: char| c.is_ascii_alphabetic()) => {
+                s.find(',').map_or(0, |index| index + 1)
+            }
+            _ => 0,
+        };
+        let split_point = s[start..]
+            .find(delim)
+            .map_or(s.len(), |index| start + index);
+        let (s, next) = s.split_at(split_point);
+        Self::skip_delim(Self::from_str_extended(s, format)?, next, delim)
+    }
+
+    fn skip_delim(inst: Self, next: &str, delim: char) -> Result<(Self, &str), DateTimeParseError> {
         if next.is_empty() {
             Ok((inst, next))
         } else if next.starts_with(delim) {
@@ -254,6 +291,97 @@ impl DateTime {
             Format::HttpDate => format::http_date::format(self),
         }
     }
+
+    /// Formats the `DateTime` to a string using the given [`ExtendedFormat`].
+    ///
+    /// Returns an error if the given `DateTime` cannot be represented by the desired format.
+    ///
+    /// # Example
+    /// ```
+    /// # use aws_smithy_types::date_time::{DateTime, ExtendedFormat};
+    /// let date_time = DateTime::from_secs(1576540098);
+    /// assert_eq!(
+    ///     "20191216T234818Z",
+    ///     date_time.fmt_extended(ExtendedFormat::Iso8601Basic).unwrap()
+    /// );
+    /// ```
+    pub fn fmt_extended(&self, format: ExtendedFormat) -> Result<String, DateTimeFormatError> {
+        match format {
+            ExtendedFormat::EpochMillis => format::epoch_millis::format(self),
+            ExtendedFormat::Iso8601Basic => format::iso8601_basic::format(self),
+            ExtendedFormat::Rfc2822 => format::rfc2822::format(self),
+        }
+    }
+
+    /// Formats the `DateTime` to a string using the given `format`, with exactly `precision`
+    /// digits of fractional seconds.
+    ///
+    /// Sub-second digits beyond `precision` are truncated, and a `precision` above 9 is treated
+    /// as 9 (nanoseconds). Only [`Format::DateTime`], [`Format::DateTimeWithOffset`] and
+    /// [`Format::EpochSeconds`] have fractional seconds: other formats are the same as with
+    /// [`DateTime::fmt`].
+    ///
+    /// # Example
+    /// ```
+    /// # use aws_smithy_types::date_time::{DateTime, Format};
+    /// let date_time = DateTime::from_secs_and_nanos(1576540098, 520_000_000);
+    /// assert_eq!(
+    ///     "2019-12-16T23:48:18.520Z",
+    ///     date_time.fmt_with_precision(Format::DateTime, 3).unwrap()
+    /// );
+    /// assert_eq!(
+    ///     "1576540098",
+    ///     date_time.fmt_with_precision(Format::EpochSeconds, 0).unwrap()
+    /// );
+    /// ```
+    pub fn fmt_with_precision(
+        &self,
+        format: Format,
+        precision: u8,
+    ) -> Result<String, DateTimeFormatError> {
+        match format {
+            Format::DateTime | Format::DateTimeWithOffset => {
+                format::rfc3339::format_with_precision(self, Some(precision))
+            }
+            Format::EpochSeconds => Ok(format::epoch_seconds::format_with_precision(
+                self, precision,
+            )),
+            _ => self.fmt(format),
+        }
+    }
+
+    /// Returns the `DateTime` that is `duration` later, or `None` if it would overflow.
+    pub fn checked_add(&self, duration: Duration) -> Option<DateTime> {
+        let nanos = self.as_nanos().checked_add(duration.as_nanos() as i128)?;
+        DateTime::from_nanos(nanos).ok()
+    }
+
+    /// Returns the `DateTime` that is `duration` earlier, or `None` if it would overflow.
+    pub fn checked_sub(&self, duration: Duration) -> Option<DateTime> {
+        let nanos = self.as_nanos().checked_sub(duration.as_nanos() as i128)?;
+        DateTime::from_nanos(nanos).ok()
+    }
+
+    /// Returns the amount of time elapsed from `earlier` to this `DateTime`, or `None` if
+    /// `earlier` is later than this `DateTime`.
+    ///
+    /// # Example
+    /// ```
+    /// # use aws_smithy_types::DateTime;
+    /// # use std::time::Duration;
+    /// let earlier = DateTime::from_secs(1576540098);
+    /// let later = earlier.checked_add(Duration::from_millis(1500)).unwrap();
+    /// assert_eq!(Some(Duration::from_millis(1500)), later.duration_since(earlier));
+    /// assert_eq!(None, earlier.duration_since(later));
+    /// ```
+    pub fn duration_since(&self, earlier: DateTime) -> Option<Duration> {
+        // The difference of two `DateTime`s always fits into a `Duration`
+        let nanos = u128::try_from(self.as_nanos() - earlier.as_nanos()).ok()?;
+        Some(Duration::new(
+            (nanos / NANOS_PER_SECOND as u128) as u64,
+            (nanos % NANOS_PER_SECOND as u128) as u32,
+        ))
+    }
 }
 
 /// Tries to convert a [`DateTime`] into a [`SystemTime`].
@@ -375,9 +503,29 @@ pub enum Format {
     EpochSeconds,
 }
 
+/// Formats for representing a `DateTime` outside of the Smithy protocols.
+#[non_exhaustive]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum ExtendedFormat {
+    /// Number of milliseconds since the Unix epoch formatted as an integer. Sub-millisecond
+    /// precision is truncated.
+    /// e.g. `1576540098520`
+    EpochMillis,
+
+    /// ISO-8601 basic format in UTC, as used by the SigV4 `X-Amz-Date` header. Subsecond nanos
+    /// are not emitted.
+    /// e.g. `20191216T234818Z`
+    Iso8601Basic,
+
+    /// Date format of internet messages, specified in RFC-2822. Any offset is accepted when
+    /// parsing, and dates are formatted in UTC. Subsecond nanos are not emitted.
+    /// e.g. `Mon, 16 Dec 2019 23:48:18 +0000`
+    Rfc2822,
+}
+
 #[cfg(test)]
 mod test {
-    use crate::date_time::Format;
+    use crate::date_time::{ExtendedFormat, Format};
     use crate::DateTime;
     use proptest::proptest;
     use std::convert::TryFrom;
@@ -485,6 +633,153 @@ mod test {
         assert_eq!(next, "Tue, 17 Dec 2019 23:48:18 GMT");
     }
 
+    #[test]
+    fn test_fmt_additional_formats() {
+        let date_time = DateTime::from_secs_and_nanos(1576540098, 520_999_999);
+        assert_eq!(
+            date_time.fmt_extended(ExtendedFormat::EpochMillis).unwrap(),
+            "1576540098520"
+        );
+        assert_eq!(
+            date_time
+                .fmt_extended(ExtendedFormat::Iso8601Basic)
+                .unwrap(),
+            "20191216T234818Z"
+        );
+        assert_eq!(
+            date_time.fmt_extended(ExtendedFormat::Rfc2822).unwrap(),
+            "Mon, 16 Dec 2019 23:48:18 +0000"
+        );
+        assert_eq!(
+            DateTime::from_millis(-1)
+                .fmt_extended(ExtendedFormat::EpochMillis)
+                .unwrap(),
+            "-1"
+        );
+        assert!(DateTime::from_secs(i64::MAX)
+            .fmt_extended(ExtendedFormat::EpochMillis)
+            .is_err());
+        assert!(DateTime::from_secs(-62_167_219_201)
+            .fmt_extended(ExtendedFormat::Iso8601Basic)
+            .is_err());
+    }
+
+    #[test]
+    fn test_parse_additional_formats() {
+        let expected = DateTime::from_secs(1576540098);
+        for (input, format) in [
+            ("1576540098000", ExtendedFormat::EpochMillis),
+            ("20191216T234818Z", ExtendedFormat::Iso8601Basic),
+            ("Mon, 16 Dec 2019 23:48:18 +0000", ExtendedFormat::Rfc2822),
+            ("Tue, 17 Dec 2019 00:48:18 +0100", ExtendedFormat::Rfc2822),
+            ("16 Dec 2019 23:48:18 GMT", ExtendedFormat::Rfc2822),
+        ] {
+            assert_eq!(
+                expected,
+                DateTime::from_str_extended(input, format).unwrap(),
+                "{input}"
+            );
+        }
+        assert_eq!(
+            DateTime::from_millis(-1500),
+            DateTime::from_str_extended("-1500", ExtendedFormat::EpochMillis).unwrap()
+        );
+        for (input, format) in [
+            ("+1576540098000", ExtendedFormat::EpochMillis),
+            ("1576540098.5", ExtendedFormat::EpochMillis),
+            ("20191216T234818", ExtendedFormat::Iso8601Basic),
+            ("2019-12-16T23:48:18Z", ExtendedFormat::Iso8601Basic),
+            ("20191316T234818Z", ExtendedFormat::Iso8601Basic),
+            ("20191216T244818Z", ExtendedFormat::Iso8601Basic),
+            ("2019121àT234818Z", ExtendedFormat::Iso8601Basic),
+            ("Mon, 16 Dec 2019 23:48:18", ExtendedFormat::Rfc2822),
+            ("Mon, 16 Dec 2019 23:48:18 UTC+1", ExtendedFormat::Rfc2822),
+        ] {
+            assert!(
+                DateTime::from_str_extended(input, format).is_err(),
+                "{input}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_read_many_additional_formats() {
+        let s = "20191216T234818Z,20191217T234818Z";
+        let (date_time, next) =
+            DateTime::read_extended(s, ExtendedFormat::Iso8601Basic, ',').expect("valid");
+        assert_eq!(date_time, DateTime::from_secs(1576540098));
+        assert_eq!(next, "20191217T234818Z");
+
+        let s = "Mon, 16 Dec 2019 23:48:18 +0000,16 Dec 2019 23:48:18 GMT";
+        let (date_time, next) =
+            DateTime::read_extended(s, ExtendedFormat::Rfc2822, ',').expect("valid");
+        assert_eq!(date_time, DateTime::from_secs(1576540098));
+        assert_eq!(next, "16 Dec 2019 23:48:18 GMT");
+        let (date_time, next) =
+            DateTime::read_extended(next, ExtendedFormat::Rfc2822, ',').expect("valid");
+        assert_eq!(date_time, DateTime::from_secs(1576540098));
+        assert_eq!(next, "");
+    }
+
+    #[test]
+    fn test_fmt_with_precision() {
+        let date_time = DateTime::from_secs_and_nanos(1576540098, 123_456_789);
+        let cases = [
+            (Format::DateTime, 0, "2019-12-16T23:48:18Z"),
+            (Format::DateTime, 3, "2019-12-16T23:48:18.123Z"),
+            (Format::DateTimeWithOffset, 6, "2019-12-16T23:48:18.123456Z"),
+            (Format::DateTime, 9, "2019-12-16T23:48:18.123456789Z"),
+            (Format::DateTime, 12, "2019-12-16T23:48:18.123456789Z"),
+            (Format::EpochSeconds, 0, "1576540098"),
+            (Format::EpochSeconds, 2, "1576540098.12"),
+            (Format::HttpDate, 3, "Mon, 16 Dec 2019 23:48:18 GMT"),
+        ];
+        for (format, precision, expected) in cases {
+            assert_eq!(
+                expected,
+                date_time.fmt_with_precision(format, precision).unwrap()
+            );
+        }
+        assert_eq!(
+            "2019-12-16T23:48:18.000Z",
+            DateTime::from_secs(1576540098)
+                .fmt_with_precision(Format::DateTime, 3)
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn test_arithmetic() {
+        use std::time::Duration;
+
+        let date_time = DateTime::from_secs_and_nanos(-1, 900_000_000);
+        let later = date_time.checked_add(Duration::from_millis(250)).unwrap();
+        assert_eq!(DateTime::from_secs_and_nanos(0, 150_000_000), later);
+        assert_eq!(
+            date_time,
+            later.checked_sub(Duration::from_millis(250)).unwrap()
+        );
+        assert_eq!(
+            Some(Duration::from_millis(250)),
+            later.duration_since(date_time)
+        );
+        assert_eq!(Some(Duration::ZERO), later.duration_since(later));
+        assert_eq!(None, date_time.duration_since(later));
+
+        let (min, max) = (
+            DateTime::from_secs(i64::MIN),
+            DateTime::from_secs_and_nanos(i64::MAX, 999_999_999),
+        );
+        assert_eq!(None, max.checked_add(Duration::from_nanos(1)));
+        assert_eq!(None, min.checked_sub(Duration::from_nanos(1)));
+        assert_eq!(Some(max), min.checked_add(Duration::MAX));
+        assert_eq!(None, max.checked_add(Duration::MAX));
+        assert_eq!(
+            Some(Duration::new(u64::MAX, 999_999_999)),
+            max.duration_since(min)
+        );
+    }
+
     #[derive(Debug)]
     struct EpochMillisTestCase {
         _rfc3339: &'static str,
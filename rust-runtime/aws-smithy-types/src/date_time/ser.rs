@@ -25,7 +25,7 @@ impl serde::Serialize for DateTime {
     }
 }
 
-#[cfg(test)]
+#[cfg(all(test, feature = "serde-deserialize"))]
 mod test {
     use super::*;
 
@@ -0,0 +1,324 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! Adapters to (de)serialize a [`DateTime`] in a specific [`Format`] with serde.
+//!
+//! The `Serialize` and `Deserialize` implementations of [`DateTime`] use RFC-3339 strings for
+//! human-readable formats. Use these modules with `#[serde(with = "...")]` to pick another format
+//! for a field, or their `option` submodules for `Option<DateTime>` fields:
+//!
+//! ```
+//! # #[cfg(all(feature = "serde-serialize", feature = "serde-deserialize"))]
+//! # {
+//! use aws_smithy_types::date_time::serde_format;
+//! use aws_smithy_types::DateTime;
+//! use serde::{Deserialize, Serialize};
+//!
+//! #[derive(Serialize, Deserialize)]
+//! struct Object {
+//!     #[serde(with = "serde_format::http_date")]
+//!     last_modified: DateTime,
+//!     #[serde(with = "serde_format::epoch_seconds::option")]
+//!     expires: Option<DateTime>,
+//! }
+//!
+//! let object = Object {
+//!     last_modified: DateTime::from_secs(1576540098),
+//!     expires: Some(DateTime::from_secs_and_nanos(1576540098, 500_000_000)),
+//! };
+//! assert_eq!(
+//!     r#"{"last_modified":"Mon, 16 Dec 2019 23:48:18 GMT","expires":1576540098.5}"#,
+//!     serde_json::to_string(&object).unwrap()
+//! );
+//! # }
+//! ```
+//!
+//! [`epoch_seconds`] serializes whole seconds as integers and other timestamps as floating point
+//! numbers. It deserializes integers, floating point numbers, and strings.
+
+use super::{DateTime, Format};
+#[cfg(feature = "serde-deserialize")]
+use std::fmt;
+
+macro_rules! format_module {
+    ($(#[$doc:meta])* $name:ident, $format:expr) => {
+        $(#[$doc])*
+        pub mod $name {
+            use super::*;
+
+            /// Serializes a [`DateTime`] in this format.
+            #[cfg(feature = "serde-serialize")]
+            pub fn serialize<S>(date_time: &DateTime, serializer: S) -> Result<S::Ok, S::Error>
+            where
+                S: serde::Serializer,
+            {
+                serde::Serialize::serialize(&Formatted(date_time, $format), serializer)
+            }
+
+            /// Deserializes a [`DateTime`] in this format.
+            #[cfg(feature = "serde-deserialize")]
+            pub fn deserialize<'de, D>(deserializer: D) -> Result<DateTime, D::Error>
+            where
+                D: serde::Deserializer<'de>,
+            {
+                serde::de::DeserializeSeed::deserialize(FormatSeed($format), deserializer)
+            }
+
+            /// Adapters for an `Option<DateTime>` in this format.
+            pub mod option {
+                use super::super::*;
+
+                /// Serializes an `Option<DateTime>` in this format.
+                #[cfg(feature = "serde-serialize")]
+                pub fn serialize<S>(
+                    date_time: &Option<DateTime>,
+                    serializer: S,
+                ) -> Result<S::Ok, S::Error>
+                where
+                    S: serde::Serializer,
+                {
+                    match date_time {
+                        Some(date_time) => serializer.serialize_some(&Formatted(date_time, $format)),
+                        None => serializer.serialize_none(),
+                    }
+                }
+
+                /// Deserializes an `Option<DateTime>` in this format.
+                #[cfg(feature = "serde-deserialize")]
+                pub fn deserialize<'de, D>(deserializer: D) -> Result<Option<DateTime>, D::Error>
+                where
+                    D: serde::Deserializer<'de>,
+                {
+                    deserializer.deserialize_option(OptionVisitor($format))
+                }
+            }
+        }
+    };
+}
+
+format_module!(
+    /// RFC-3339 strings, as with [`Format::DateTime`].
+    date_time,
+    Format::DateTime
+);
+format_module!(
+    /// HTTP date strings, as with [`Format::HttpDate`].
+    http_date,
+    Format::HttpDate
+);
+format_module!(
+    /// Numbers of seconds since the Unix epoch, as with [`Format::EpochSeconds`].
+    epoch_seconds,
+    Format::EpochSeconds
+);
+
+#[cfg(feature = "serde-serialize")]
+struct Formatted<'a>(&'a DateTime, Format);
+
+#[cfg(feature = "serde-serialize")]
+impl serde::Serialize for Formatted<'_> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let Formatted(date_time, format) = *self;
+        match format {
+            Format::EpochSeconds if date_time.has_subsec_nanos() => {
+                serializer.serialize_f64(date_time.as_secs_f64())
+            }
+            Format::EpochSeconds => serializer.serialize_i64(date_time.secs()),
+            _ => match date_time.fmt(format) {
+                Ok(value) => serializer.serialize_str(&value),
+                Err(err) => Err(serde::ser::Error::custom(err)),
+            },
+        }
+    }
+}
+
+#[cfg(feature = "serde-deserialize")]
+struct FormatSeed(Format);
+
+#[cfg(feature = "serde-deserialize")]
+impl<'de> serde::de::DeserializeSeed<'de> for FormatSeed {
+    type Value = DateTime;
+
+    fn deserialize<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        match self.0 {
+            Format::EpochSeconds => deserializer.deserialize_any(FormatVisitor(self.0)),
+            _ => deserializer.deserialize_str(FormatVisitor(self.0)),
+        }
+    }
+}
+
+#[cfg(feature = "serde-deserialize")]
+struct FormatVisitor(Format);
+
+#[cfg(feature = "serde-deserialize")]
+impl serde::de::Visitor<'_> for FormatVisitor {
+    type Value = DateTime;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.0 {
+            Format::EpochSeconds => formatter.write_str("a number of seconds since the Unix epoch"),
+            Format::HttpDate => formatter.write_str("an HTTP date"),
+            _ => formatter.write_str("an RFC-3339 date time"),
+        }
+    }
+
+    fn visit_i64<E>(self, v: i64) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        match self.0 {
+            Format::EpochSeconds => Ok(DateTime::from_secs(v)),
+            _ => Err(E::invalid_type(serde::de::Unexpected::Signed(v), &self)),
+        }
+    }
+
+    fn visit_u64<E>(self, v: u64) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        match (self.0, i64::try_from(v)) {
+            (Format::EpochSeconds, Ok(seconds)) => Ok(DateTime::from_secs(seconds)),
+            _ => Err(E::invalid_value(serde::de::Unexpected::Unsigned(v), &self)),
+        }
+    }
+
+    fn visit_f64<E>(self, v: f64) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        match self.0 {
+            Format::EpochSeconds if v.is_finite() => Ok(DateTime::from_secs_f64(v)),
+            _ => Err(E::invalid_value(serde::de::Unexpected::Float(v), &self)),
+        }
+    }
+
+    fn visit_str<E>(self, v: &str) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        DateTime::from_str(v, self.0).map_err(E::custom)
+    }
+}
+
+#[cfg(feature = "serde-deserialize")]
+struct OptionVisitor(Format);
+
+#[cfg(feature = "serde-deserialize")]
+impl<'de> serde::de::Visitor<'de> for OptionVisitor {
+    type Value = Option<DateTime>;
+
+    fn expecting(&self, formatter: &mut fmt::Formatter<'_>) -> fmt::Result {
+        formatter.write_str("an optional ")?;
+        serde::de::Visitor::expecting(&FormatVisitor(self.0), formatter)
+    }
+
+    fn visit_none<E>(self) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        Ok(None)
+    }
+
+    fn visit_unit<E>(self) -> Result<Self::Value, E>
+    where
+        E: serde::de::Error,
+    {
+        Ok(None)
+    }
+
+    fn visit_some<D>(self, deserializer: D) -> Result<Self::Value, D::Error>
+    where
+        D: serde::Deserializer<'de>,
+    {
+        serde::de::DeserializeSeed::deserialize(FormatSeed(self.0), deserializer).map(Some)
+    }
+}
+
+#[cfg(all(test, feature = "serde-serialize", feature = "serde-deserialize"))]
+mod tests {
+    use super::*;
+    use serde::{Deserialize, Serialize};
+
+    #[derive(Debug, PartialEq, Serialize, Deserialize)]
+    struct Timestamps {
+        #[serde(with = "date_time")]
+        date_time: DateTime,
+        #[serde(with = "http_date")]
+        http_date: DateTime,
+        #[serde(with = "epoch_seconds")]
+        epoch_seconds: DateTime,
+        #[serde(with = "epoch_seconds::option", default)]
+        optional: Option<DateTime>,
+    }
+
+    #[test]
+    fn round_trip() {
+        let timestamps = Timestamps {
+            date_time: DateTime::from_secs_and_nanos(1576540098, 520_000_000),
+            http_date: DateTime::from_secs(1576540098),
+            epoch_seconds: DateTime::from_secs_and_nanos(1576540098, 500_000_000),
+            optional: Some(DateTime::from_secs(-1)),
+        };
+        let json = serde_json::to_string(&timestamps).unwrap();
+        assert_eq!(
+            r#"{"date_time":"2019-12-16T23:48:18.52Z","http_date":"Mon, 16 Dec 2019 23:48:18 GMT","epoch_seconds":1576540098.5,"optional":-1}"#,
+            json
+        );
+        assert_eq!(timestamps, serde_json::from_str(&json).unwrap());
+
+        let timestamps = Timestamps {
+            optional: None,
+            ..timestamps
+        };
+        let json = serde_json::to_string(&timestamps).unwrap();
+        assert!(json.ends_with(r#""optional":null}"#), "{json}");
+        assert_eq!(timestamps, serde_json::from_str(&json).unwrap());
+    }
+
+    #[test]
+    fn epoch_seconds_accepts_numbers_and_strings() {
+        #[derive(Deserialize)]
+        struct Epoch(#[serde(with = "epoch_seconds")] DateTime);
+
+        for (json, expected) in [
+            ("1576540098", DateTime::from_secs(1576540098)),
+            ("-1576540098", DateTime::from_secs(-1576540098)),
+            (
+                "1576540098.25",
+                DateTime::from_secs_and_nanos(1576540098, 250_000_000),
+            ),
+            (
+                r#""1576540098.25""#,
+                DateTime::from_secs_and_nanos(1576540098, 250_000_000),
+            ),
+        ] {
+            assert_eq!(
+                expected,
+                serde_json::from_str::<Epoch>(json).unwrap().0,
+                "{json}"
+            );
+        }
+        assert!(serde_json::from_str::<Epoch>("18446744073709551615").is_err());
+        assert!(serde_json::from_str::<Epoch>(r#""not a number""#).is_err());
+    }
+
+    #[test]
+    fn string_formats_reject_numbers() {
+        let from_json =
+            |json| http_date::deserialize(&mut serde_json::Deserializer::from_str(json));
+        assert!(from_json("1576540098").is_err());
+        assert!(from_json(r#""2019-12-16T23:48:18Z""#).is_err());
+        assert_eq!(
+            DateTime::from_secs(1576540098),
+            from_json(r#""Mon, 16 Dec 2019 23:48:18 GMT""#).unwrap()
+        );
+    }
+}
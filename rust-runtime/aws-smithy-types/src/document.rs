@@ -7,12 +7,6 @@ use crate::Number;
 use std::borrow::Cow;
 use std::collections::HashMap;
 
-#[cfg(any(
-    all(aws_sdk_unstable, feature = "serde-deserialize"),
-    all(aws_sdk_unstable, feature = "serde-serialize")
-))]
-use serde;
-
 /* ANCHOR: document */
 
 /// Document Type
@@ -22,19 +16,10 @@ use serde;
 /// modeled using rigid types, or data that has a schema that evolves outside of the purview of a model.
 /// The serialization format of a document is an implementation detail of a protocol.
 #[derive(Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde-serialize", derive(serde::Serialize))]
+#[cfg_attr(feature = "serde-deserialize", derive(serde::Deserialize))]
 #[cfg_attr(
-    all(aws_sdk_unstable, feature = "serde-serialize"),
-    derive(serde::Serialize)
-)]
-#[cfg_attr(
-    all(aws_sdk_unstable, feature = "serde-deserialize"),
-    derive(serde::Deserialize)
-)]
-#[cfg_attr(
-    any(
-        all(aws_sdk_unstable, feature = "serde-deserialize"),
-        all(aws_sdk_unstable, feature = "serde-serialize")
-    ),
+    any(feature = "serde-deserialize", feature = "serde-serialize"),
     serde(untagged)
 )]
 pub enum Document {
@@ -154,6 +139,133 @@ impl Document {
     pub fn is_null(&self) -> bool {
         matches!(self, Self::Null)
     }
+
+    /// Looks up a value by a [JSON Pointer](https://datatracker.ietf.org/doc/html/rfc6901).
+    ///
+    /// A JSON Pointer is a string of reference tokens each prefixed by `/`, where `~1` and `~0`
+    /// escape `/` and `~` in object keys. Tokens index into arrays when they are decimal numbers.
+    /// The empty pointer refers to the whole document. `None` is returned when the pointer is
+    /// malformed or doesn't refer to a value.
+    ///
+    /// ```
+    /// use aws_smithy_types::{document, Document};
+    ///
+    /// let doc = document!({ "a": [{ "b": "c" }], "d/e": true });
+    /// assert_eq!(Some("c"), doc.pointer("/a/0/b").and_then(Document::as_string));
+    /// assert_eq!(Some(true), doc.pointer("/d~1e").and_then(Document::as_bool));
+    /// assert!(doc.pointer("/a/1").is_none());
+    /// ```
+    pub fn pointer(&self, pointer: &str) -> Option<&Document> {
+        pointer_tokens(pointer)?.try_fold(self, |target, token| match target {
+            Self::Object(object) => object.get(token.as_ref()),
+            Self::Array(array) => array.get(array_index(&token)?),
+            _ => None,
+        })
+    }
+
+    /// Looks up a value by a [JSON Pointer](https://datatracker.ietf.org/doc/html/rfc6901), and
+    /// returns a mutable reference to it.
+    ///
+    /// See [`pointer`](Self::pointer) for the pointer syntax.
+    ///
+    /// ```
+    /// use aws_smithy_types::document;
+    ///
+    /// let mut doc = document!({ "a": [1, 2] });
+    /// *doc.pointer_mut("/a/1").unwrap() = document!("two");
+    /// assert_eq!(document!({ "a": [1, "two"] }), doc);
+    /// ```
+    pub fn pointer_mut(&mut self, pointer: &str) -> Option<&mut Document> {
+        pointer_tokens(pointer)?.try_fold(self, |target, token| match target {
+            Self::Object(object) => object.get_mut(token.as_ref()),
+            Self::Array(array) => array.get_mut(array_index(&token)?),
+            _ => None,
+        })
+    }
+
+    /// Removes the value referred to by a
+    /// [JSON Pointer](https://datatracker.ietf.org/doc/html/rfc6901) from its parent object or
+    /// array, and returns it.
+    ///
+    /// See [`pointer`](Self::pointer) for the pointer syntax. `None` is returned, and the document
+    /// is left untouched, when the pointer doesn't refer to a value or is the empty pointer.
+    ///
+    /// ```
+    /// use aws_smithy_types::document;
+    ///
+    /// let mut doc = document!({ "a": [1, 2, 3], "b": null });
+    /// assert_eq!(Some(document!(2)), doc.remove("/a/1"));
+    /// assert_eq!(Some(document!(null)), doc.remove("/b"));
+    /// assert_eq!(document!({ "a": [1, 3] }), doc);
+    /// ```
+    pub fn remove(&mut self, pointer: &str) -> Option<Document> {
+        let (parent, last) = pointer.rsplit_once('/')?;
+        let last = unescape_token(last);
+        match self.pointer_mut(parent)? {
+            Self::Object(object) => object.remove(last.as_ref()),
+            Self::Array(array) => {
+                let index = array_index(&last)?;
+                (index < array.len()).then(|| array.remove(index))
+            }
+            _ => None,
+        }
+    }
+
+    /// Applies `patch` to this document following the
+    /// [JSON Merge Patch](https://datatracker.ietf.org/doc/html/rfc7396) rules.
+    ///
+    /// When `patch` is an object, its members are merged recursively into this document, which is
+    /// first replaced by an empty object if it isn't one, and members whose value is `null` are
+    /// removed. Any other `patch` replaces this document.
+    ///
+    /// ```
+    /// use aws_smithy_types::document;
+    ///
+    /// let mut doc = document!({ "a": { "b": 1, "c": 2 }, "d": [1] });
+    /// doc.merge(document!({ "a": { "b": null, "e": 3 }, "d": [2] }));
+    /// assert_eq!(document!({ "a": { "c": 2, "e": 3 }, "d": [2] }), doc);
+    /// ```
+    pub fn merge(&mut self, patch: Document) {
+        let Self::Object(patch) = patch else {
+            *self = patch;
+            return;
+        };
+        if !self.is_object() {
+            *self = Self::Object(HashMap::new());
+        }
+        let object = self.as_object_mut().expect("set to an object above");
+        for (key, value) in patch {
+            if value.is_null() {
+                object.remove(&key);
+            } else {
+                object.entry(key).or_default().merge(value);
+            }
+        }
+    }
+}
+
+/// Splits a JSON Pointer into its unescaped reference tokens.
+fn pointer_tokens(pointer: &str) -> Option<impl Iterator<Item = Cow<'_, str>>> {
+    if !pointer.is_empty() && !pointer.starts_with('/') {
+        return None;
+    }
+    Some(pointer.split('/').skip(1).map(unescape_token))
+}
+
+fn unescape_token(token: &str) -> Cow<'_, str> {
+    if token.contains('~') {
+        Cow::Owned(token.replace("~1", "/").replace("~0", "~"))
+    } else {
+        Cow::Borrowed(token)
+    }
+}
+
+/// Parses an array index, which JSON Pointer restricts to decimal digits without leading zeros.
+fn array_index(token: &str) -> Option<usize> {
+    if token.starts_with('+') || (token.starts_with('0') && token.len() > 1) {
+        return None;
+    }
+    token.parse().ok()
 }
 
 /// The default value is `Document::Null`.
@@ -231,15 +343,186 @@ impl From<Number> for Document {
 
 /* ANCHOR END: document */
 
+#[cfg(feature = "serde-json")]
+impl From<serde_json::Value> for Document {
+    fn from(value: serde_json::Value) -> Self {
+        match value {
+            serde_json::Value::Null => Document::Null,
+            serde_json::Value::Bool(value) => Document::Bool(value),
+            serde_json::Value::Number(value) => {
+                Document::Number(match (value.as_u64(), value.as_i64(), value.as_f64()) {
+                    (Some(value), _, _) => Number::PosInt(value),
+                    (_, Some(value), _) => Number::NegInt(value),
+                    (_, _, value) => Number::Float(value.unwrap_or(f64::NAN)),
+                })
+            }
+            serde_json::Value::String(value) => Document::String(value),
+            serde_json::Value::Array(values) => {
+                Document::Array(values.into_iter().map(Document::from).collect())
+            }
+            serde_json::Value::Object(values) => Document::Object(
+                values
+                    .into_iter()
+                    .map(|(key, value)| (key, Document::from(value)))
+                    .collect(),
+            ),
+        }
+    }
+}
+
+/// Non-finite floating point numbers, which can't be represented in JSON, are converted to
+/// `Value::Null`.
+#[cfg(feature = "serde-json")]
+impl From<Document> for serde_json::Value {
+    fn from(value: Document) -> Self {
+        match value {
+            Document::Null => serde_json::Value::Null,
+            Document::Bool(value) => serde_json::Value::Bool(value),
+            Document::Number(Number::PosInt(value)) => serde_json::Value::from(value),
+            Document::Number(Number::NegInt(value)) => serde_json::Value::from(value),
+            Document::Number(Number::Float(value)) => serde_json::Value::from(value),
+            Document::String(value) => serde_json::Value::String(value),
+            Document::Array(values) => {
+                serde_json::Value::Array(values.into_iter().map(serde_json::Value::from).collect())
+            }
+            Document::Object(values) => serde_json::Value::Object(
+                values
+                    .into_iter()
+                    .map(|(key, value)| (key, serde_json::Value::from(value)))
+                    .collect(),
+            ),
+        }
+    }
+}
+
+/// Constructs a [`Document`] from JSON-like syntax.
+///
+/// Objects, arrays, and `null` are written as in JSON. Object keys are string literals, or
+/// expressions in parentheses. Any other value is an expression converted with
+/// [`Document::from`], so that variables and nested documents can be interpolated. As in JSON,
+/// non-negative integer literals are [`Number::PosInt`](crate::Number::PosInt), whatever their
+/// type.
+///
+/// ```
+/// use aws_smithy_types::{document, Document, Number};
+///
+/// let name = "smithy";
+/// let tags = vec![Document::from("rust"), Document::from("sdk")];
+/// let doc = document!({
+///     "name": name,
+///     "stars": 1.5,
+///     "archived": false,
+///     "owner": null,
+///     "tags": tags,
+///     (format!("{name}-version")): [1, { "minor": 2 }],
+/// });
+///
+/// assert_eq!(Some("smithy"), doc.pointer("/name").and_then(Document::as_string));
+/// let minor = doc.pointer("/smithy-version/1/minor");
+/// assert_eq!(Some(&Number::PosInt(2)), minor.and_then(Document::as_number));
+/// assert!(doc.pointer("/owner").unwrap().is_null());
+/// ```
+#[macro_export]
+macro_rules! document {
+    // Munches the elements of an array, accumulating them in brackets.
+    (@array [$($elements:expr,)*]) => {
+        ::std::vec![$($elements,)*]
+    };
+    (@array [$($elements:expr,)*] , $($rest:tt)*) => {
+        $crate::document!(@array [$($elements,)*] $($rest)*)
+    };
+    (@array [$($elements:expr,)*] null $($rest:tt)*) => {
+        $crate::document!(@array [$($elements,)* $crate::document!(null),] $($rest)*)
+    };
+    (@array [$($elements:expr,)*] [ $($array:tt)* ] $($rest:tt)*) => {
+        $crate::document!(@array [$($elements,)* $crate::document!([$($array)*]),] $($rest)*)
+    };
+    (@array [$($elements:expr,)*] { $($object:tt)* } $($rest:tt)*) => {
+        $crate::document!(@array [$($elements,)* $crate::document!({$($object)*}),] $($rest)*)
+    };
+    (@array [$($elements:expr,)*] $next:literal , $($rest:tt)*) => {
+        $crate::document!(@array [$($elements,)* $crate::document!($next),] $($rest)*)
+    };
+    (@array [$($elements:expr,)*] $last:literal) => {
+        $crate::document!(@array [$($elements,)* $crate::document!($last),])
+    };
+    (@array [$($elements:expr,)*] $next:expr , $($rest:tt)*) => {
+        $crate::document!(@array [$($elements,)* $crate::document!($next),] $($rest)*)
+    };
+    (@array [$($elements:expr,)*] $last:expr) => {
+        $crate::document!(@array [$($elements,)* $crate::document!($last),])
+    };
+
+    // Munches the members of an object, inserting them into `$object`.
+    (@object $object:ident) => {};
+    (@object $object:ident , $($rest:tt)*) => {
+        $crate::document!(@object $object $($rest)*);
+    };
+    (@object $object:ident $key:literal : $($rest:tt)*) => {
+        $crate::document!(@member $object ($key) $($rest)*);
+    };
+    (@object $object:ident ($key:expr) : $($rest:tt)*) => {
+        $crate::document!(@member $object ($key) $($rest)*);
+    };
+    (@member $object:ident ($key:expr) null $($rest:tt)*) => {
+        $object.insert(::std::string::String::from($key), $crate::document!(null));
+        $crate::document!(@object $object $($rest)*);
+    };
+    (@member $object:ident ($key:expr) [ $($array:tt)* ] $($rest:tt)*) => {
+        $object.insert(::std::string::String::from($key), $crate::document!([$($array)*]));
+        $crate::document!(@object $object $($rest)*);
+    };
+    (@member $object:ident ($key:expr) { $($members:tt)* } $($rest:tt)*) => {
+        $object.insert(::std::string::String::from($key), $crate::document!({$($members)*}));
+        $crate::document!(@object $object $($rest)*);
+    };
+    (@member $object:ident ($key:expr) $value:literal , $($rest:tt)*) => {
+        $object.insert(::std::string::String::from($key), $crate::document!($value));
+        $crate::document!(@object $object $($rest)*);
+    };
+    (@member $object:ident ($key:expr) $value:literal) => {
+        $object.insert(::std::string::String::from($key), $crate::document!($value));
+    };
+    (@member $object:ident ($key:expr) $value:expr , $($rest:tt)*) => {
+        $object.insert(::std::string::String::from($key), $crate::document!($value));
+        $crate::document!(@object $object $($rest)*);
+    };
+    (@member $object:ident ($key:expr) $value:expr) => {
+        $object.insert(::std::string::String::from($key), $crate::document!($value));
+    };
+
+    (null) => {
+        $crate::Document::Null
+    };
+    ([ $($elements:tt)* ]) => {
+        $crate::Document::Array($crate::document!(@array [] $($elements)*))
+    };
+    ({ $($members:tt)* }) => {{
+        #[allow(unused_mut)]
+        let mut object: ::std::collections::HashMap<::std::string::String, $crate::Document> =
+            ::std::collections::HashMap::new();
+        $crate::document!(@object object $($members)*);
+        $crate::Document::Object(object)
+    }};
+    // Unsuffixed integer literals are `i32`, so non-negative integers are normalized to `PosInt`
+    ($value:literal) => {
+        match $crate::Document::from($value) {
+            $crate::Document::Number($crate::Number::NegInt(value)) if value >= 0 => {
+                $crate::Document::Number($crate::Number::PosInt(value as u64))
+            }
+            document => document,
+        }
+    };
+    ($value:expr) => {
+        $crate::Document::from($value)
+    };
+}
+
 #[cfg(test)]
 mod test {
     /// checks if a) serialization of json suceeds and b) it is compatible with serde_json
     #[test]
-    #[cfg(all(
-        aws_sdk_unstable,
-        feature = "serde-serialize",
-        feature = "serde-deserialize"
-    ))]
+    #[cfg(all(feature = "serde-serialize", feature = "serde-deserialize"))]
     fn serialize_json() {
         use crate::Document;
         use crate::Number;
@@ -248,15 +531,9 @@ mod test {
         // string
         map.insert("hello".into(), "world".to_string().into());
         // numbers
-        map.insert("pos_int".into(), Document::Number(Number::PosInt(1).into()));
-        map.insert(
-            "neg_int".into(),
-            Document::Number(Number::NegInt(-1).into()),
-        );
-        map.insert(
-            "float".into(),
-            Document::Number(Number::Float(0.1 + 0.2).into()),
-        );
+        map.insert("pos_int".into(), Document::Number(Number::PosInt(1)));
+        map.insert("neg_int".into(), Document::Number(Number::NegInt(-1)));
+        map.insert("float".into(), Document::Number(Number::Float(0.1 + 0.2)));
         // booleans
         map.insert("true".into(), true.into());
         map.insert("false".into(), false.into());
@@ -284,4 +561,199 @@ mod test {
         let doc: Result<Document, _> = serde_json::from_str(target_file);
         assert_eq!(obj, doc.unwrap());
     }
+    #[test]
+    fn document_macro() {
+        use crate::{Document, Number};
+        use std::collections::HashMap;
+
+        let nested = Document::from("nested");
+        let key = "key";
+        let doc = document!({
+            "null": null,
+            "bool": true,
+            "pos_int": 1,
+            "neg_int": -1,
+            "float": 0.5,
+            "string": "hello",
+            "array": [null, [], {}, -1, nested.clone(), { "a": [1] }],
+            (key): nested.clone(),
+            "empty": {},
+        });
+
+        let mut expected = HashMap::new();
+        expected.insert("null".to_string(), Document::Null);
+        expected.insert("bool".to_string(), Document::Bool(true));
+        expected.insert("pos_int".to_string(), Document::Number(Number::PosInt(1)));
+        expected.insert("neg_int".to_string(), Document::Number(Number::NegInt(-1)));
+        expected.insert("float".to_string(), Document::Number(Number::Float(0.5)));
+        expected.insert("string".to_string(), Document::String("hello".into()));
+        let mut a = HashMap::new();
+        a.insert(
+            "a".to_string(),
+            Document::Array(vec![Document::Number(Number::PosInt(1))]),
+        );
+        expected.insert(
+            "array".to_string(),
+            Document::Array(vec![
+                Document::Null,
+                Document::Array(vec![]),
+                Document::Object(HashMap::new()),
+                Document::Number(Number::NegInt(-1)),
+                nested.clone(),
+                Document::Object(a),
+            ]),
+        );
+        expected.insert("key".to_string(), nested);
+        expected.insert("empty".to_string(), Document::Object(HashMap::new()));
+        assert_eq!(Document::Object(expected), doc);
+
+        assert_eq!(Document::Array(vec![]), document!([]));
+        assert_eq!(Document::Null, document!(null));
+        assert_eq!(Document::Bool(false), document!(false));
+        assert_eq!(Document::Number(Number::PosInt(0)), document!(0));
+        assert_eq!(Document::Number(Number::NegInt(-2)), document!(-2));
+    }
+
+    #[test]
+    fn pointer() {
+        let mut doc = document!({
+            "a": [{ "b": "c" }, 1],
+            "": "empty key",
+            "m~n": "tilde",
+            "x/y": "slash",
+            "01": "leading zero",
+        });
+        assert_eq!(Some(&doc), doc.pointer(""));
+        assert_eq!(Some(&document!("c")), doc.pointer("/a/0/b"));
+        assert_eq!(Some(&document!(1)), doc.pointer("/a/1"));
+        assert_eq!(Some(&document!("empty key")), doc.pointer("/"));
+        assert_eq!(Some(&document!("tilde")), doc.pointer("/m~0n"));
+        assert_eq!(Some(&document!("slash")), doc.pointer("/x~1y"));
+        assert_eq!(Some(&document!("leading zero")), doc.pointer("/01"));
+        for missing in [
+            "a", "/b", "/a/2", "/a/01", "/a/+1", "/a/-1", "/a/0/b/c", "/a/1/0",
+        ] {
+            assert_eq!(None, doc.pointer(missing), "{missing}");
+        }
+
+        *doc.pointer_mut("/a/0/b").unwrap() = document!(["d"]);
+        assert_eq!(Some(&document!("d")), doc.pointer("/a/0/b/0"));
+        assert!(doc.pointer_mut("/a/2").is_none());
+    }
+
+    #[test]
+    fn remove() {
+        let mut doc = document!({ "a": [1u64, 2u64, { "b/c": true }], "d": "e" });
+        assert_eq!(None, doc.remove(""));
+        assert_eq!(None, doc.remove("/a/3"));
+        assert_eq!(None, doc.remove("/a/x"));
+        assert_eq!(None, doc.remove("/d/0"));
+        assert_eq!(Some(document!(true)), doc.remove("/a/2/b~1c"));
+        assert_eq!(Some(document!(1u64)), doc.remove("/a/0"));
+        assert_eq!(Some(document!("e")), doc.remove("/d"));
+        assert_eq!(document!({ "a": [2u64, {}] }), doc);
+    }
+
+    #[test]
+    fn merge() {
+        // Test cases from RFC 7396, Appendix A
+        let cases = [
+            (
+                document!({"a":"b"}),
+                document!({"a":"c"}),
+                document!({"a":"c"}),
+            ),
+            (
+                document!({"a":"b"}),
+                document!({"b":"c"}),
+                document!({"a":"b","b":"c"}),
+            ),
+            (document!({"a":"b"}), document!({"a":null}), document!({})),
+            (
+                document!({"a":"b","b":"c"}),
+                document!({"a":null}),
+                document!({"b":"c"}),
+            ),
+            (
+                document!({"a":["b"]}),
+                document!({"a":"c"}),
+                document!({"a":"c"}),
+            ),
+            (
+                document!({"a":"c"}),
+                document!({"a":["b"]}),
+                document!({"a":["b"]}),
+            ),
+            (
+                document!({"a":{"b":"c"}}),
+                document!({"a":{"b":"d","c":null}}),
+                document!({"a":{"b":"d"}}),
+            ),
+            (
+                document!({"a":[{"b":"c"}]}),
+                document!({"a":[1u64]}),
+                document!({"a":[1u64]}),
+            ),
+            (
+                document!(["a", "b"]),
+                document!(["c", "d"]),
+                document!(["c", "d"]),
+            ),
+            (document!({"a":"b"}), document!(["c"]), document!(["c"])),
+            (document!({"a":"foo"}), document!(null), document!(null)),
+            (document!({"a":"foo"}), document!("bar"), document!("bar")),
+            (
+                document!({"e":null}),
+                document!({"a":1u64}),
+                document!({"e":null,"a":1u64}),
+            ),
+            (
+                document!([1u64, 2u64]),
+                document!({"a":"b","c":null}),
+                document!({"a":"b"}),
+            ),
+            (
+                document!({}),
+                document!({"a":{"bb":{"ccc":null}}}),
+                document!({"a":{"bb":{}}}),
+            ),
+        ];
+        for (mut target, patch, expected) in cases {
+            target.merge(patch.clone());
+            assert_eq!(expected, target, "patch: {patch:?}");
+        }
+    }
+
+    #[test]
+    #[cfg(feature = "serde-json")]
+    fn serde_json_value_conversions() {
+        use crate::{Document, Number};
+
+        let value = serde_json::json!({
+            "null": null,
+            "bool": true,
+            "pos_int": 1,
+            "neg_int": -1,
+            "float": 0.5,
+            "string": "hello",
+            "array": [1, { "nested": [] }],
+        });
+        let doc = document!({
+            "null": null,
+            "bool": true,
+            "pos_int": 1u64,
+            "neg_int": -1i64,
+            "float": 0.5,
+            "string": "hello",
+            "array": [1u64, { "nested": [] }],
+        });
+        assert_eq!(doc, Document::from(value.clone()));
+        assert_eq!(value, serde_json::Value::from(doc));
+
+        // JSON can't represent non-finite numbers
+        assert_eq!(
+            serde_json::Value::Null,
+            serde_json::Value::from(Document::Number(Number::Float(f64::NAN)))
+        );
+    }
 }
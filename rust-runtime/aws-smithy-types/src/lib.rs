@@ -7,6 +7,15 @@
 #![cfg_attr(docsrs, feature(doc_auto_cfg))]
 /* End of automatically managed default lints */
 //! Protocol-agnostic types for smithy-rs.
+//!
+//! # Crate Features
+//!
+//! - `serde-serialize` and `serde-deserialize`: implement `serde::Serialize` and
+//!   `serde::Deserialize` for [`Document`], [`Number`], [`BigInteger`], [`BigDecimal`], [`Blob`],
+//!   and [`DateTime`]. Timestamps use
+//!   RFC-3339 strings by default; `date_time::serde_format` has adapters for the other formats.
+//! - `serde-json`: conversions between [`Document`] and `serde_json::Value`.
+//! - `byte-stream-mmap`: memory-mapped file `ByteStream`s, with `FsBuilder::memory_map`.
 
 #![allow(clippy::derive_partial_eq_without_eq)]
 #![warn(
@@ -18,6 +27,7 @@
 )]
 
 pub mod base64;
+pub mod big_number;
 pub mod body;
 pub mod byte_stream;
 /// A typemap for storing configuration.
@@ -38,6 +48,7 @@ mod document;
 mod number;
 pub mod str_bytes;
 
+pub use big_number::{BigDecimal, BigInteger};
 pub use blob::Blob;
 pub use date_time::DateTime;
 pub use document::Document;
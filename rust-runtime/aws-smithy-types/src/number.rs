@@ -6,28 +6,14 @@
 //! A number type that implements Javascript / JSON semantics.
 
 use crate::error::{TryFromNumberError, TryFromNumberErrorKind};
-#[cfg(all(
-    aws_sdk_unstable,
-    any(feature = "serde-serialize", feature = "serde-deserialize")
-))]
-use serde;
 
 /// A number type that implements Javascript / JSON semantics, modeled on serde_json:
 /// <https://docs.serde.rs/src/serde_json/number.rs.html#20-22>
 #[derive(Debug, Clone, Copy, PartialEq)]
+#[cfg_attr(feature = "serde-deserialize", derive(serde::Deserialize))]
+#[cfg_attr(feature = "serde-serialize", derive(serde::Serialize))]
 #[cfg_attr(
-    all(aws_sdk_unstable, feature = "serde-deserialize"),
-    derive(serde::Deserialize)
-)]
-#[cfg_attr(
-    all(aws_sdk_unstable, feature = "serde-serialize"),
-    derive(serde::Serialize)
-)]
-#[cfg_attr(
-    any(
-        all(aws_sdk_unstable, feature = "serde-deserialize"),
-        all(aws_sdk_unstable, feature = "serde-serialize")
-    ),
+    any(feature = "serde-deserialize", feature = "serde-serialize"),
     serde(untagged)
 )]
 pub enum Number {
@@ -494,12 +480,7 @@ mod test {
     }
 
     #[test]
-    #[cfg(all(
-        test,
-        aws_sdk_unstable,
-        feature = "serde-deserialize",
-        feature = "serde-serialize"
-    ))]
+    #[cfg(all(test, feature = "serde-deserialize", feature = "serde-serialize"))]
     /// ensures that numbers are deserialized as expected
     /// 0 <= PosInt
     /// 0 > NegInt
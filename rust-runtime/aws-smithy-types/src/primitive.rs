@@ -65,6 +65,8 @@ mod private {
     impl Sealed for f64 {}
     impl Sealed for u64 {}
     impl Sealed for bool {}
+    impl Sealed for crate::BigInteger {}
+    impl Sealed for crate::BigDecimal {}
 }
 
 macro_rules! parse_from_str {
@@ -83,6 +85,18 @@ parse_from_str!(i16);
 parse_from_str!(i32);
 parse_from_str!(i64);
 
+impl Parse for crate::BigInteger {
+    fn parse_smithy_primitive(input: &str) -> Result<Self, PrimitiveParseError> {
+        FromStr::from_str(input).map_err(|_| PrimitiveParseError("BigInteger"))
+    }
+}
+
+impl Parse for crate::BigDecimal {
+    fn parse_smithy_primitive(input: &str) -> Result<Self, PrimitiveParseError> {
+        FromStr::from_str(input).map_err(|_| PrimitiveParseError("BigDecimal"))
+    }
+}
+
 impl Parse for f32 {
     fn parse_smithy_primitive(input: &str) -> Result<Self, PrimitiveParseError> {
         float::parse_f32(input).map_err(|_| PrimitiveParseError("f32"))
@@ -260,6 +274,7 @@ mod float {
 #[cfg(test)]
 mod test {
     use crate::primitive::{Encoder, Parse};
+    use crate::{BigDecimal, BigInteger};
 
     #[test]
     fn bool_format() {
@@ -282,6 +297,24 @@ mod test {
         assert_eq!(Encoder::from(f64::NAN).encode(), "NaN");
     }
 
+    #[test]
+    fn big_number_parse() {
+        let big = "123456789012345678901234567890";
+        assert_eq!(
+            BigInteger::parse_smithy_primitive(big).unwrap().as_str(),
+            big
+        );
+        let err = BigInteger::parse_smithy_primitive("1.5").expect_err("should fail");
+        assert_eq!(err.0, "BigInteger");
+        let big = "-1.23456789012345678901234567890E+5";
+        assert_eq!(
+            BigDecimal::parse_smithy_primitive(big).unwrap().as_str(),
+            big
+        );
+        let err = BigDecimal::parse_smithy_primitive("NaN").expect_err("should fail");
+        assert_eq!(err.0, "BigDecimal");
+    }
+
     #[test]
     fn float_parse() {
         assert_eq!(f64::parse_smithy_primitive("1234.5").unwrap(), 1234.5);
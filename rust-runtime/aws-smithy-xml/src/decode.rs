@@ -10,6 +10,12 @@ use std::error::Error;
 use std::fmt::{Display, Formatter};
 use xmlparser::{ElementEnd, Token, Tokenizer};
 
+mod chunked;
+
+#[cfg(feature = "byte-stream")]
+pub use chunked::ByteStreamDocument;
+pub use chunked::ChunkedDocument;
+
 pub type Depth = usize;
 
 // in general, these errors are just for reporting what happened, there isn't
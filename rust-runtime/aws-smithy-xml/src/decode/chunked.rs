@@ -0,0 +1,623 @@
+/*
+ * Copyright Amazon.com, Inc. or its affiliates. All Rights Reserved.
+ * SPDX-License-Identifier: Apache-2.0
+ */
+
+//! Pull decoder for XML documents received in chunks
+
+use crate::decode::{Document, StartEl, XmlDecodeError};
+use xmlparser::{ElementEnd, Token, Tokenizer};
+
+/// XML document decoded incrementally from chunks of bytes
+///
+/// [`Document::new`] requires the entire document as a `&str`. For large documents, such as an S3
+/// `ListObjectsV2` page, `ChunkedDocument` is fed the document one chunk at a time with
+/// [`push`](Self::push), e.g. with the `Bytes` chunks of a response body as they are received,
+/// and returns each child element of the root element as its own [`Document`] as soon as the
+/// element is complete. Each child is decoded with the regular
+/// [`ScopedDecoder`](super::ScopedDecoder) API, and only the bytes of the elements that haven't
+/// been read yet are kept in memory. Each pushed chunk is tokenized once, however many chunks a
+/// child element spans, but a child element is kept in memory whole until it is complete.
+///
+/// Decoding a chunk doesn't require it to be valid UTF-8 on its own: a code point split across
+/// chunks is reassembled before decoding.
+///
+/// # Example
+///
+/// ```rust
+/// use aws_smithy_xml::decode::{try_data, ChunkedDocument};
+///
+/// let chunks: [&[u8]; 3] = [
+///     b"<ListBucketResult><Name>bucket</Name><Contents><Key>a",
+///     b"</Key></Contents><Contents><Key>b</Key></Con",
+///     b"tents></ListBucketResult>",
+/// ];
+/// let mut document = ChunkedDocument::new();
+/// let mut keys = Vec::new();
+/// for chunk in chunks {
+///     document.push(chunk);
+///     while let Some(element) = document.next_element() {
+///         let mut element = element.unwrap();
+///         let mut contents = element.root_element().unwrap();
+///         if contents.start_el().matches("Contents") {
+///             while let Some(mut tag) = contents.next_tag() {
+///                 if tag.start_el().matches("Key") {
+///                     keys.push(try_data(&mut tag).unwrap().into_owned());
+///                 }
+///             }
+///         }
+///     }
+/// }
+/// document.finish();
+/// assert!(document.next_element().is_none());
+/// assert_eq!(vec!["a", "b"], keys);
+/// ```
+#[derive(Debug, Default)]
+pub struct ChunkedDocument {
+    /// Input that hasn't been consumed yet. Each pushed byte is validated as UTF-8 once, when it
+    /// is appended.
+    buffer: String,
+    /// Index in `buffer` of the next unread byte
+    consumed: usize,
+    /// Progress of the scan for the next child element
+    scan: ScanState,
+    /// Trailing bytes of an incomplete code point, which the next chunk may complete
+    incomplete: Vec<u8>,
+    /// The first invalid UTF-8 in the input
+    utf8_error: Option<std::str::Utf8Error>,
+    /// Source of the root start element once it has been read, e.g. `<ListBucketResult ...>`
+    root: Option<String>,
+    root_closed: bool,
+    finished: bool,
+    failed: bool,
+}
+
+/// Progress of the scan for the next child element, kept between pushes so that the input is only
+/// tokenized once. Indices are relative to the unread input.
+#[derive(Debug, Default, Clone, Copy)]
+struct ScanState {
+    /// End of the last token after which the tokenizer is back in element content, where
+    /// tokenizing resumes
+    resume: usize,
+    /// Nesting depth within the root element at `resume`
+    depth: usize,
+    /// Start of the child element being scanned, if `depth` isn't zero
+    start: usize,
+}
+
+/// Outcome of scanning the buffered input
+enum Scan<'a> {
+    /// A complete child element of the root, as a range of the scanned input
+    Element(usize, usize),
+    /// The end tag of the root element, with its prefix and local name, ending at the given index
+    /// of the scanned input
+    End(&'a str, &'a str, usize),
+    /// More input is required
+    Incomplete(Option<xmlparser::Error>),
+}
+
+impl ChunkedDocument {
+    /// Creates a document with no input.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Appends the next chunk of the document to the input.
+    ///
+    /// # Panics
+    ///
+    /// Panics if called after [`finish`](Self::finish).
+    pub fn push(&mut self, chunk: impl AsRef<[u8]>) {
+        assert!(!self.finished, "cannot push a chunk after `finish`");
+        // Discard the elements that have already been read
+        self.buffer.drain(..self.consumed);
+        self.consumed = 0;
+        if self.utf8_error.is_some() {
+            return;
+        }
+        let chunk = chunk.as_ref();
+        let mut pending = std::mem::take(&mut self.incomplete);
+        let input = if pending.is_empty() {
+            chunk
+        } else {
+            pending.extend_from_slice(chunk);
+            &pending
+        };
+        match std::str::from_utf8(input) {
+            Ok(input) => self.buffer.push_str(input),
+            Err(err) => {
+                let (valid, rest) = input.split_at(err.valid_up_to());
+                self.buffer
+                    .push_str(std::str::from_utf8(valid).expect("valid prefix"));
+                match err.error_len() {
+                    // The end of the input is an incomplete code point
+                    None => self.incomplete = rest.to_vec(),
+                    Some(_) => self.utf8_error = Some(err),
+                }
+            }
+        }
+    }
+
+    /// Signals that the whole document has been pushed.
+    ///
+    /// Afterwards, an incomplete document is reported as an error by
+    /// [`next_element`](Self::next_element).
+    pub fn finish(&mut self) {
+        self.finished = true;
+    }
+
+    /// Returns the start element of the root element, once it has been read.
+    ///
+    /// The root start element is read by [`next_element`](Self::next_element).
+    pub fn root_start_el(&self) -> Option<StartEl<'_>> {
+        Document::new(self.root.as_deref()?).next_start_element()
+    }
+
+    /// Returns the next complete child element of the root element, as a [`Document`] whose root
+    /// element is that child.
+    ///
+    /// `None` is returned when more input is required to complete the next child element or,
+    /// after the end of the root element, at the end of the document. Text directly within the
+    /// root element is skipped. No more elements are returned after an error.
+    pub fn next_element(&mut self) -> Option<Result<Document<'_>, XmlDecodeError>> {
+        match self.next_range()? {
+            Ok((start, end)) => Some(Ok(Document::new(&self.buffer[start..end]))),
+            Err(err) => Some(Err(err)),
+        }
+    }
+
+    /// Returns true once the end of the root element has been read, or after an error.
+    fn is_done(&self) -> bool {
+        self.failed || self.root_closed
+    }
+
+    /// Returns the range in `buffer` of the next complete child element.
+    fn next_range(&mut self) -> Option<Result<(usize, usize), XmlDecodeError>> {
+        if self.is_done() {
+            return None;
+        }
+        match self.read_next() {
+            Ok(range) => Ok(range).transpose(),
+            Err(err) => {
+                self.failed = true;
+                Some(Err(err))
+            }
+        }
+    }
+
+    /// Reads the root start element if needed, and then returns the range in `buffer` of the next
+    /// child element, if it is complete.
+    fn read_next(&mut self) -> Result<Option<(usize, usize)>, XmlDecodeError> {
+        let input = self.valid_input()?;
+        let offset = self.consumed;
+        if self.root.is_none() {
+            match scan_root(input) {
+                Ok(Some((start, end, closed))) => {
+                    self.root = Some(input[start..end].to_owned());
+                    self.root_closed = closed;
+                    self.consumed += end;
+                    if closed {
+                        return Ok(None);
+                    }
+                    return self.read_next();
+                }
+                Ok(None) if self.finished => {
+                    return Err(XmlDecodeError::custom("no root element"));
+                }
+                Ok(None) => return Ok(None),
+                Err(err) if self.finished => return Err(XmlDecodeError::invalid_xml(err)),
+                Err(_) => return Ok(None),
+            }
+        }
+
+        let mut scan = self.scan;
+        match scan_element(input, &mut scan) {
+            Scan::Element(start, end) => {
+                self.consumed += end;
+                self.scan = ScanState::default();
+                Ok(Some((offset + start, offset + end)))
+            }
+            Scan::End(prefix, local, end) => {
+                let root = self.root_start_el().expect("root was read");
+                if (prefix, local) != (root.prefix(), root.local()) {
+                    return Err(XmlDecodeError::custom(format!(
+                        "expected the end of {:?}, found the end of {:?}",
+                        root.local(),
+                        local
+                    )));
+                }
+                self.consumed += end;
+                self.root_closed = true;
+                Ok(None)
+            }
+            Scan::Incomplete(err) if self.finished => Err(match err {
+                Some(err) => XmlDecodeError::invalid_xml(err),
+                None => XmlDecodeError::custom("unexpected end of document"),
+            }),
+            Scan::Incomplete(_) => {
+                self.scan = scan;
+                Ok(None)
+            }
+        }
+    }
+
+    /// Returns the unread input, which is the longest prefix of the unread bytes that is valid
+    /// UTF-8.
+    fn valid_input(&self) -> Result<&str, XmlDecodeError> {
+        if let Some(err) = self.utf8_error {
+            return Err(XmlDecodeError::unhandled(err));
+        }
+        if self.finished && !self.incomplete.is_empty() {
+            let err = std::str::from_utf8(&self.incomplete).expect_err("incomplete code point");
+            return Err(XmlDecodeError::unhandled(err));
+        }
+        Ok(&self.buffer[self.consumed..])
+    }
+}
+
+/// XML document decoded incrementally from a [`ByteStream`](aws_smithy_types::byte_stream::ByteStream)
+///
+/// Feeds each chunk of the body into a [`ChunkedDocument`] as it is received, and returns each
+/// child element of the root element as soon as it is complete.
+///
+/// # Example
+///
+/// ```rust
+/// use aws_smithy_types::byte_stream::ByteStream;
+/// use aws_smithy_xml::decode::{try_data, ByteStreamDocument, XmlDecodeError};
+///
+/// async fn keys(body: ByteStream) -> Result<Vec<String>, XmlDecodeError> {
+///     let mut document = ByteStreamDocument::new(body);
+///     let mut keys = Vec::new();
+///     while let Some(element) = document.next_element().await {
+///         let mut element = element?;
+///         let mut contents = element.root_element()?;
+///         if contents.start_el().matches("Contents") {
+///             while let Some(mut tag) = contents.next_tag() {
+///                 if tag.start_el().matches("Key") {
+///                     keys.push(try_data(&mut tag)?.into_owned());
+///                 }
+///             }
+///         }
+///     }
+///     Ok(keys)
+/// }
+/// ```
+#[cfg(feature = "byte-stream")]
+#[derive(Debug)]
+pub struct ByteStreamDocument {
+    body: aws_smithy_types::byte_stream::ByteStream,
+    document: ChunkedDocument,
+}
+
+#[cfg(feature = "byte-stream")]
+impl ByteStreamDocument {
+    /// Creates a document that is read from `body`.
+    pub fn new(body: aws_smithy_types::byte_stream::ByteStream) -> Self {
+        Self {
+            body,
+            document: ChunkedDocument::new(),
+        }
+    }
+
+    /// Returns the start element of the root element, once it has been read.
+    pub fn root_start_el(&self) -> Option<StartEl<'_>> {
+        self.document.root_start_el()
+    }
+
+    /// Returns the next child element of the root element, reading more of the body as needed.
+    ///
+    /// `None` is returned at the end of the document. Failing to read the body is reported as an
+    /// error, and no more elements are returned after an error.
+    pub async fn next_element(&mut self) -> Option<Result<Document<'_>, XmlDecodeError>> {
+        loop {
+            match self.document.next_range() {
+                Some(Ok((start, end))) => {
+                    return Some(Ok(Document::new(&self.document.buffer[start..end])))
+                }
+                Some(Err(err)) => return Some(Err(err)),
+                None if self.document.is_done() || self.document.finished => return None,
+                None => {}
+            }
+            match self.body.next().await {
+                Some(Ok(chunk)) => self.document.push(chunk),
+                Some(Err(err)) => {
+                    self.document.failed = true;
+                    return Some(Err(XmlDecodeError::unhandled(err)));
+                }
+                None => self.document.finish(),
+            }
+        }
+    }
+}
+
+/// Scans the prolog and the root start element, and returns its range and whether it is
+/// self-closing, e.g. `<Root/>`.
+fn scan_root(input: &str) -> Result<Option<(usize, usize, bool)>, xmlparser::Error> {
+    let mut start = None;
+    for token in Tokenizer::from(input) {
+        match token? {
+            Token::ElementStart { span, .. } => start = Some(span.start()),
+            Token::ElementEnd { end, span } => {
+                let start = start.expect("an element end follows an element start");
+                return Ok(Some((start, span.end(), matches!(end, ElementEnd::Empty))));
+            }
+            _ => {}
+        }
+    }
+    Ok(None)
+}
+
+/// Scans the content of the root element for the next complete child element, resuming from
+/// `state`, which is updated when more input is required.
+fn scan_element<'a>(input: &'a str, state: &mut ScanState) -> Scan<'a> {
+    let (mut depth, mut start) = (state.depth, state.start);
+    for token in Tokenizer::from_fragment(input, state.resume..input.len()) {
+        let token = match token {
+            Ok(token) => token,
+            // Tokens cut off by the end of the input are reported as errors
+            Err(err) => return Scan::Incomplete(Some(err)),
+        };
+        let end = match token {
+            Token::ElementStart { span, .. } => {
+                if depth == 0 {
+                    start = span.start();
+                }
+                depth += 1;
+                // Within a start tag, until its end
+                continue;
+            }
+            Token::Attribute { .. } => continue,
+            Token::ElementEnd {
+                end: ElementEnd::Open,
+                span,
+            } => span.end(),
+            Token::ElementEnd {
+                end: ElementEnd::Close(prefix, local),
+                span,
+            } if depth == 0 => return Scan::End(prefix.as_str(), local.as_str(), span.end()),
+            Token::ElementEnd { span, .. } => {
+                depth -= 1;
+                if depth == 0 {
+                    return Scan::Element(start, span.end());
+                }
+                span.end()
+            }
+            Token::Text { text } => text.end(),
+            Token::Cdata { span, .. }
+            | Token::Comment { span, .. }
+            | Token::ProcessingInstruction { span, .. } => span.end(),
+            // Only found in the prolog
+            _ => continue,
+        };
+        *state = ScanState {
+            resume: end,
+            depth,
+            start,
+        };
+    }
+    Scan::Incomplete(None)
+}
+
+#[cfg(test)]
+mod test {
+    use crate::decode::{try_data, ChunkedDocument, XmlDecodeError};
+
+    const LIST_OBJECTS: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<ListBucketResult xmlns="http://s3.amazonaws.com/doc/2006-03-01/">
+    <Name>bucket</Name>
+    <!-- a comment -->
+    <Contents><Key>a&amp;b</Key><Size>1</Size></Contents>
+    <Contents><Key>ünïcödé 🦀</Key><Size>2</Size></Contents>
+    <IsTruncated/>
+</ListBucketResult>
+"#;
+
+    /// Decodes the children of the root element of `chunks` into `name: data` strings, with
+    /// nested elements rendered as `name: [children]`.
+    fn decode<'a>(
+        chunks: impl IntoIterator<Item = &'a [u8]>,
+    ) -> Result<Vec<String>, XmlDecodeError> {
+        fn render(
+            decoder: &mut crate::decode::ScopedDecoder<'_, '_>,
+        ) -> Result<String, XmlDecodeError> {
+            let name = decoder.start_el().local().to_owned();
+            let mut children = Vec::new();
+            while let Some(mut tag) = decoder.next_tag() {
+                let data = try_data(&mut tag)?.into_owned();
+                children.push(format!("{}: {}", tag.start_el().local(), data));
+            }
+            Ok(format!("{name}: {children:?}"))
+        }
+
+        let mut document = ChunkedDocument::new();
+        let mut elements = Vec::new();
+        let mut read = |document: &mut ChunkedDocument| -> Result<(), XmlDecodeError> {
+            while let Some(element) = document.next_element() {
+                let mut element = element?;
+                elements.push(render(&mut element.root_element()?)?);
+            }
+            Ok(())
+        };
+        for chunk in chunks {
+            document.push(chunk);
+            read(&mut document)?;
+        }
+        document.finish();
+        read(&mut document)?;
+        assert_eq!(
+            "ListBucketResult",
+            document.root_start_el().expect("root was read").local()
+        );
+        Ok(elements)
+    }
+
+    #[test]
+    fn resumes_scanning_where_it_stopped() {
+        let mut document = ChunkedDocument::new();
+        document.push("<Root><Big>");
+        assert!(document.next_element().is_none());
+        let mut scanned = "<Big>".len();
+        for _ in 0..100 {
+            document.push("<A>1</A>");
+            assert!(document.next_element().is_none());
+            scanned += "<A>1</A>".len();
+            // Only the new input is tokenized on every push
+            assert_eq!((scanned, 1), (document.scan.resume, document.scan.depth));
+        }
+        document.push("</Big></Root>");
+        {
+            let mut big = document.next_element().unwrap().unwrap();
+            let mut big = big.root_element().unwrap();
+            let mut children = 0;
+            while big.next_tag().is_some() {
+                children += 1;
+            }
+            assert_eq!(100, children);
+        }
+        assert!(document.next_element().is_none());
+    }
+
+    #[test]
+    fn decodes_every_split() {
+        let input = LIST_OBJECTS.as_bytes();
+        let expected = vec![
+            "Name: []".to_owned(),
+            r#"Contents: ["Key: a&b", "Size: 1"]"#.to_owned(),
+            r#"Contents: ["Key: ünïcödé 🦀", "Size: 2"]"#.to_owned(),
+            "IsTruncated: []".to_owned(),
+        ];
+        for chunk_size in 1..=input.len() {
+            assert_eq!(
+                expected,
+                decode(input.chunks(chunk_size)).unwrap(),
+                "chunk size: {chunk_size}"
+            );
+        }
+    }
+
+    #[test]
+    fn root_attributes() {
+        let mut document = ChunkedDocument::new();
+        document.push(r#"<Root xmlns="http://example.com" a="&quot;b&quot;"><A/>"#);
+        assert!(document.root_start_el().is_none());
+        assert!(document.next_element().unwrap().is_ok());
+        let root = document.root_start_el().unwrap();
+        assert_eq!(Some("\"b\""), root.attr("a"));
+        assert_eq!(Some("http://example.com"), root.attr("xmlns"));
+    }
+
+    #[test]
+    fn self_closing_root() {
+        assert_eq!(
+            Vec::<String>::new(),
+            decode([&b"<ListBucketResult/>"[..]]).unwrap()
+        );
+    }
+
+    #[test]
+    fn only_buffers_unread_elements() {
+        let mut document = ChunkedDocument::new();
+        document.push("<Root><A>1</A><B>2");
+        assert!(document.next_element().unwrap().is_ok());
+        assert!(document.next_element().is_none());
+        document.push("</B>");
+        assert_eq!("<B>2</B>", &document.buffer[document.consumed..]);
+        assert!(document.next_element().unwrap().is_ok());
+        assert!(document.buffer[document.consumed..].is_empty());
+    }
+
+    #[test]
+    fn errors() {
+        let error = |input: &[u8]| decode(input.chunks(3)).expect_err("invalid document");
+        error(b"");
+        error(b"  ");
+        error(b"<Root><A></A>");
+        error(b"<Root><A></A></Other>");
+        error(b"<Root><A>\xFF</A></Root>");
+        error(b"<Root><A>\xC3</A></Root>");
+        error(b"<Root><A><</A></Root>");
+        error(b"<Root><A>\xC3");
+    }
+
+    #[cfg(feature = "byte-stream")]
+    mod byte_stream {
+        use crate::decode::{try_data, ByteStreamDocument};
+        use aws_smithy_types::body::SdkBody;
+        use aws_smithy_types::byte_stream::ByteStream;
+        use bytes::Bytes;
+        use std::collections::VecDeque;
+        use std::pin::Pin;
+        use std::task::{Context, Poll};
+
+        /// Body that returns each of its frames separately, ending with an optional error
+        struct Frames {
+            frames: VecDeque<&'static [u8]>,
+            error: Option<&'static str>,
+        }
+
+        impl http_body_0_4::Body for Frames {
+            type Data = Bytes;
+            type Error = &'static str;
+
+            fn poll_data(
+                mut self: Pin<&mut Self>,
+                _cx: &mut Context<'_>,
+            ) -> Poll<Option<Result<Self::Data, Self::Error>>> {
+                Poll::Ready(match self.frames.pop_front() {
+                    Some(frame) => Some(Ok(Bytes::from_static(frame))),
+                    None => self.error.take().map(Err),
+                })
+            }
+
+            fn poll_trailers(
+                self: Pin<&mut Self>,
+                _cx: &mut Context<'_>,
+            ) -> Poll<Result<Option<http::HeaderMap>, Self::Error>> {
+                Poll::Ready(Ok(None))
+            }
+        }
+
+        fn body(frames: &[&'static [u8]], error: Option<&'static str>) -> ByteStream {
+            ByteStream::new(SdkBody::from_body_0_4(Frames {
+                frames: frames.iter().copied().collect(),
+                error,
+            }))
+        }
+
+        #[tokio::test]
+        async fn decodes_body_frames() {
+            let mut document = ByteStreamDocument::new(body(
+                &[
+                    b"<Root><A>1</A><B>",
+                    b"2</B>",
+                    b"<C>\xF0\x9F",
+                    b"\xA6\x80</C></Root>",
+                ],
+                None,
+            ));
+            let mut elements = Vec::new();
+            while let Some(element) = document.next_element().await {
+                let mut element = element.unwrap();
+                let mut element = element.root_element().unwrap();
+                let name = element.start_el().local().to_owned();
+                elements.push(format!("{name}: {}", try_data(&mut element).unwrap()));
+            }
+            assert_eq!(vec!["A: 1", "B: 2", "C: 🦀"], elements);
+            assert_eq!("Root", document.root_start_el().unwrap().local());
+        }
+
+        #[tokio::test]
+        async fn body_errors() {
+            let mut document = ByteStreamDocument::new(body(&[b"<Root><A>1</A><B>"], Some("boom")));
+            assert!(document.next_element().await.unwrap().is_ok());
+            assert!(document.next_element().await.unwrap().is_err());
+            assert!(document.next_element().await.is_none());
+
+            let mut document = ByteStreamDocument::new(body(&[b"<Root><A>1</A><B>"], None));
+            assert!(document.next_element().await.unwrap().is_ok());
+            assert!(document.next_element().await.unwrap().is_err());
+            assert!(document.next_element().await.is_none());
+        }
+    }
+}
@@ -164,9 +164,190 @@ impl ScopeWriter<'_, '_> {
     }
 }
 
+/// Writes an XML document as a sequence of chunks
+///
+/// [`XmlWriter`] writes an entire document into a single `String`. For documents whose root
+/// element contains a large number of elements, such as an S3 `DeleteObjects` request,
+/// `XmlChunks` is an iterator writing the children of the root element lazily, one for each item
+/// of an iterator, and yielding the document in chunks of roughly `chunk_size` bytes. Each chunk
+/// can be sent as it is produced, e.g. as a frame of a streaming request body, so that the
+/// document is never held in memory all at once.
+/// With the `byte-stream` feature, [`into_body`](Self::into_body) makes a streaming
+/// [`SdkBody`](aws_smithy_types::body::SdkBody) of the chunks.
+///
+/// # Examples
+/// ```rust
+/// use aws_smithy_xml::encode::XmlChunks;
+///
+/// let keys = ["a", "b", "c"];
+/// let chunks: Vec<String> = XmlChunks::new("Delete", keys.iter(), |writer, key| {
+///     let mut object = writer.start_el("Object").finish();
+///     let mut key_scope = object.start_el("Key").finish();
+///     key_scope.data(key);
+/// })
+/// .root_ns("http://s3.amazonaws.com/doc/2006-03-01/", None)
+/// .chunk_size(32)
+/// .collect();
+/// assert_eq!(
+///     chunks.concat(),
+///     "<Delete xmlns=\"http://s3.amazonaws.com/doc/2006-03-01/\">\
+///      <Object><Key>a</Key></Object><Object><Key>b</Key></Object><Object><Key>c</Key></Object>\
+///      </Delete>"
+/// );
+/// assert_eq!(chunks.len(), 3);
+/// ```
+pub struct XmlChunks<I, F> {
+    root: String,
+    root_attributes: Vec<(String, String)>,
+    items: I,
+    write_item: F,
+    chunk_size: usize,
+    state: ChunksState,
+}
+
+enum ChunksState {
+    Start,
+    /// The root start element has been written
+    Items,
+    Done,
+}
+
+impl<I, F> XmlChunks<I, F>
+where
+    I: Iterator,
+    F: FnMut(&mut XmlWriter<'_>, I::Item),
+{
+    /// Chunks of 64 KiB are yielded by default.
+    const DEFAULT_CHUNK_SIZE: usize = 64 * 1024;
+
+    /// Creates an iterator over the chunks of a document whose root element is named `root`.
+    ///
+    /// `write_item` writes the children of the root element for each item.
+    pub fn new(root: impl Into<String>, items: I, write_item: F) -> Self {
+        Self {
+            root: root.into(),
+            root_attributes: Vec::new(),
+            items,
+            write_item,
+            chunk_size: Self::DEFAULT_CHUNK_SIZE,
+            state: ChunksState::Start,
+        }
+    }
+
+    /// Writes an attribute on the root element.
+    pub fn root_attribute(mut self, key: &str, value: &str) -> Self {
+        self.root_attributes.push((key.into(), value.into()));
+        self
+    }
+
+    /// Writes a namespace declaration on the root element.
+    pub fn root_ns(self, namespace: &str, prefix: Option<&str>) -> Self {
+        let key = match prefix {
+            Some(prefix) => format!("xmlns:{}", prefix),
+            None => "xmlns".to_owned(),
+        };
+        self.root_attribute(&key, namespace)
+    }
+
+    /// Sets the size in bytes after which a chunk is yielded.
+    ///
+    /// Chunks are only split between items, so a chunk is larger than `chunk_size` when an item
+    /// is.
+    pub fn chunk_size(mut self, chunk_size: usize) -> Self {
+        self.chunk_size = chunk_size;
+        self
+    }
+}
+
+#[cfg(feature = "byte-stream")]
+impl<I, F> XmlChunks<I, F>
+where
+    I: Iterator + Send + Sync + 'static,
+    F: FnMut(&mut XmlWriter<'_>, I::Item) + Send + Sync + 'static,
+{
+    /// Converts the chunks into a streaming body, e.g. for a request.
+    ///
+    /// The body can't be cloned, and so isn't retried. To retry it, create the body with
+    /// [`SdkBody::retryable`](aws_smithy_types::body::SdkBody::retryable) from a closure that
+    /// creates new chunks every time.
+    pub fn into_body(self) -> aws_smithy_types::body::SdkBody {
+        aws_smithy_types::body::SdkBody::from_body_0_4(ChunksBody(Box::new(self)))
+    }
+}
+
+/// [`http_body_0_4::Body`] of the chunks of an [`XmlChunks`]
+#[cfg(feature = "byte-stream")]
+struct ChunksBody<I, F>(Box<XmlChunks<I, F>>);
+
+#[cfg(feature = "byte-stream")]
+impl<I, F> http_body_0_4::Body for ChunksBody<I, F>
+where
+    I: Iterator,
+    F: FnMut(&mut XmlWriter<'_>, I::Item),
+{
+    type Data = bytes::Bytes;
+    type Error = std::convert::Infallible;
+
+    fn poll_data(
+        mut self: std::pin::Pin<&mut Self>,
+        _cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Option<Result<Self::Data, Self::Error>>> {
+        std::task::Poll::Ready(self.0.next().map(|chunk| Ok(chunk.into())))
+    }
+
+    fn poll_trailers(
+        self: std::pin::Pin<&mut Self>,
+        _cx: &mut std::task::Context<'_>,
+    ) -> std::task::Poll<Result<Option<http::HeaderMap>, Self::Error>> {
+        std::task::Poll::Ready(Ok(None))
+    }
+
+    fn is_end_stream(&self) -> bool {
+        matches!(self.0.state, ChunksState::Done)
+    }
+}
+
+impl<I, F> Iterator for XmlChunks<I, F>
+where
+    I: Iterator,
+    F: FnMut(&mut XmlWriter<'_>, I::Item),
+{
+    type Item = String;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut chunk = String::new();
+        if let ChunksState::Start = self.state {
+            write!(chunk, "<{}", self.root).unwrap();
+            for (key, value) in &self.root_attributes {
+                write!(chunk, " {}=\"{}\"", key, escape(value)).unwrap();
+            }
+            chunk.push('>');
+            self.state = ChunksState::Items;
+        }
+        if let ChunksState::Items = self.state {
+            // Every chunk makes progress, even with a `chunk_size` of 0
+            loop {
+                match self.items.next() {
+                    Some(item) => (self.write_item)(&mut XmlWriter::new(&mut chunk), item),
+                    None => {
+                        write!(chunk, "</{}>", self.root).unwrap();
+                        self.state = ChunksState::Done;
+                        break;
+                    }
+                }
+                if chunk.len() >= self.chunk_size {
+                    break;
+                }
+            }
+            return Some(chunk);
+        }
+        None
+    }
+}
+
 #[cfg(test)]
 mod test {
-    use crate::encode::XmlWriter;
+    use crate::encode::{XmlChunks, XmlWriter};
     use aws_smithy_protocol_test::{assert_ok, validate_body, MediaType};
 
     #[test]
@@ -229,6 +410,70 @@ mod test {
         ));
     }
 
+    #[test]
+    fn chunks_match_writer() {
+        let items: Vec<String> = (0..1000).map(|i| format!("<item {i}>")).collect();
+
+        let mut expected = String::new();
+        {
+            let mut writer = XmlWriter::new(&mut expected);
+            let mut root = writer.start_el("Root").write_ns("http://example.com", None);
+            root.write_attribute("key", "\"value\"");
+            let mut root = root.finish();
+            for item in &items {
+                let mut inner = root.start_el("Item").finish();
+                inner.data(item);
+            }
+        }
+
+        for chunk_size in [0, 1, 100, 1 << 20] {
+            let chunks: Vec<String> = XmlChunks::new("Root", items.iter(), |writer, item| {
+                let mut inner = writer.start_el("Item").finish();
+                inner.data(item);
+            })
+            .root_ns("http://example.com", None)
+            .root_attribute("key", "\"value\"")
+            .chunk_size(chunk_size)
+            .collect();
+            assert_eq!(expected, chunks.concat());
+            if chunk_size == 100 {
+                assert!(chunks.iter().all(|chunk| chunk.len() < 200));
+            }
+        }
+    }
+
+    #[cfg(feature = "byte-stream")]
+    #[tokio::test]
+    async fn chunks_into_body() {
+        use aws_smithy_types::byte_stream::ByteStream;
+
+        let keys: Vec<String> = (0..100).map(|i| format!("key-{i}")).collect();
+        let body = XmlChunks::new("Delete".to_owned(), keys.into_iter(), |writer, key| {
+            let mut object = writer.start_el("Object").finish();
+            object.start_el("Key").finish().data(&key);
+        })
+        .chunk_size(100)
+        .into_body();
+        assert_eq!(None, body.bytes());
+
+        let mut stream = ByteStream::new(body);
+        let mut chunks = Vec::new();
+        while let Some(chunk) = stream.next().await {
+            chunks.push(String::from_utf8(chunk.unwrap().to_vec()).unwrap());
+        }
+        assert!(chunks.len() > 10);
+        let document = chunks.concat();
+        assert!(document.starts_with("<Delete><Object><Key>key-0</Key></Object>"));
+        assert!(document.ends_with("<Object><Key>key-99</Key></Object></Delete>"));
+    }
+
+    #[test]
+    fn chunks_without_items() {
+        let chunks: Vec<String> =
+            XmlChunks::new("Root", std::iter::empty::<()>(), |_, _| {}).collect();
+        assert_eq!(vec!["<Root></Root>".to_owned()], chunks);
+    }
+
     #[test]
     fn escape_data() {
         let mut s = String::new();